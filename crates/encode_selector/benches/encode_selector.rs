@@ -9,7 +9,7 @@ use encode_selector::*;
 
 pub fn encode_selector_benchmarks(c: &mut Criterion) {
 	let alphabet =
-		into_alphabet_set(&"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ");
+		into_alphabet_set("0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ");
 
 	c.bench_function(
 		"encode_selector::to_radix fn (into 1 character length radix)",
@@ -32,13 +32,13 @@ pub fn encode_selector_benchmarks(c: &mut Criterion) {
 	);
 
 	c.bench_function("encode_selector::into_alphabet_set fn (hex)", |b| {
-		b.iter(|| into_alphabet_set(black_box(&"0123456789abcdef")))
+		b.iter(|| into_alphabet_set(black_box("0123456789abcdef")))
 	});
 
 	c.bench_function("encode_selector::into_alphabet_set fn (base 62)", |b| {
 		b.iter(|| {
 			into_alphabet_set(black_box(
-				&"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ",
+				"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ",
 			))
 		})
 	});
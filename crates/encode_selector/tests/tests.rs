@@ -23,7 +23,7 @@ use encode_selector::*;
 fn index_to_base62_standard() {
 	#[rustfmt::skip]
 	let alphabet = into_alphabet_set(
-		&"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ"
+		"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ"
 	);
 
 	// 1 character length encoded selector names:
@@ -77,7 +77,7 @@ fn index_to_base62_standard() {
 fn index_to_base62_nums_last() {
 	#[rustfmt::skip]
 	let alphabet = into_alphabet_set(
-		&"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789"
+		"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789"
 	);
 
 	// 1 character length encoded selector names:
@@ -137,7 +137,7 @@ fn index_to_base62_nums_last() {
 fn index_to_base62_nums_scattered() {
 	#[rustfmt::skip]
 	let alphabet = into_alphabet_set(
-		&"a0bc1d234ef5ghijklmn6opqr78s9tuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ"
+		"a0bc1d234ef5ghijklmn6opqr78s9tuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ"
 	);
 
 	// 1 character length encoded selector names:
@@ -184,7 +184,7 @@ fn index_to_base62_nums_scattered() {
 fn index_to_base64_custom() {
 	#[rustfmt::skip]
 	let alphabet = into_alphabet_set(
-		&"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ-_"
+		"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ-_"
 	);
 
 	// 1 character length encoded selector names:
@@ -219,7 +219,7 @@ fn index_to_base64_custom() {
 
 #[test]
 fn index_to_base26_latin_letters() {
-	let alphabet = into_alphabet_set(&"abcdefghijklmnopqrstuvwxyz");
+	let alphabet = into_alphabet_set("abcdefghijklmnopqrstuvwxyz");
 
 	// 1 character length encoded selector names:
 	// - 26 valid combinations
@@ -256,7 +256,7 @@ fn index_to_base26_latin_letters() {
 #[test]
 fn alphabet_sanitisation() {
 	assert_eq!(
-		into_alphabet_set(&"`~!@#$%^&*()_-+=<>?[]{}|abc"),
+		into_alphabet_set("`~!@#$%^&*()_-+=<>?[]{}|abc"),
 		(vec!['_', '-', 'a', 'b', 'c'], vec![0, 1])
 	);
 }
@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::error::Error;
 use std::ffi::OsStr;
 use std::fs;
@@ -32,6 +33,13 @@ fn minify_selectors() -> Result<(), Box<dyn Error>> {
 	// Read files and note down selectors and their occurrences.
 	process_files(&mut selectors, &config)?;
 
+	// Suggest custom attributes from the selectors found, rather than
+	// minifying.
+	if config.suggest {
+		print_attribute_suggestions(&selectors, &config)?;
+		return Ok(());
+	}
+
 	// Multi-step process (stage 2/3):
 	// Process selectors list and encode into a minified identifier.
 	config.current_step = ProcessingSteps::EncodingSelectors;
@@ -45,6 +53,18 @@ fn minify_selectors() -> Result<(), Box<dyn Error>> {
 	config.current_step = ProcessingSteps::WritingToFiles;
 	process_files(&mut selectors, &config)?;
 
+	// Rewrite end-to-end test files (if any) with the same selectors,
+	// these are only written to and never read from.
+	if let (Some(test_input), Some(test_output)) = (&config.test_input, &config.test_output) {
+		for entry in WalkDir::new(test_input)
+			.into_iter()
+			.filter_map(|e| e.ok())
+			.filter(is_test_file)
+		{
+			write_to_test_file(entry.path(), test_input, test_output, &selectors, &config)?;
+		}
+	}
+
 	println!("minify-selectors finished in: {:.2?}", stopwatch.elapsed());
 
 	Ok(())
@@ -59,7 +79,8 @@ fn process_files(
 		let files = WalkDir::new(&config.input)
 			.into_iter()
 			.filter_map(|e| e.ok())
-			.filter(is_processable)
+			.filter(|e| is_processable(e, config))
+			.filter(|e| !is_in_test_input(e, config))
 			.collect::<Vec<walkdir::DirEntry>>();
 
 		files.into_par_iter().for_each(|entry| {
@@ -70,29 +91,48 @@ fn process_files(
 		for entry in WalkDir::new(&config.input)
 			.into_iter()
 			.filter_map(|e| e.ok())
-			.filter(is_processable)
+			.filter(|e| is_processable(e, config))
+			.filter(|e| !is_in_test_input(e, config))
 		{
 			handle_file(selectors, config, &entry)?;
 		}
 	}
 
-	fn is_processable(item: &walkdir::DirEntry) -> bool {
+	fn is_processable(
+		item: &walkdir::DirEntry,
+		config: &Config,
+	) -> bool {
 		// Check that current path is a file
 		if !item.path().is_file() {
 			return false;
 		};
-		// Finally, check file has a extension that can be processed
-		matches!(
-			item.path()
-				.extension()
-				.and_then(OsStr::to_str)
-				.map(|ext| ext.to_lowercase())
-				.as_deref(),
-			Some("css")
-				| Some("html") | Some("htm")
-				| Some("js") | Some("mjs")
-				| Some("cjs") | Some("svg")
-		)
+		// Finally, check file has a extension that can be processed,
+		// template files only with their template dialect chosen.
+		match item
+			.path()
+			.extension()
+			.and_then(OsStr::to_str)
+			.map(|ext| ext.to_lowercase())
+			.as_deref()
+		{
+			Some("css") | Some("html") | Some("htm") | Some("js") | Some("mjs") | Some("cjs")
+			| Some("jsx") | Some("ts") | Some("mts") | Some("cts") | Some("tsx") | Some("svg")
+			| Some("vue") | Some("svelte") | Some("astro") => true,
+			Some(extension) => get_template_dialect(extension, config).is_some(),
+			None => false,
+		}
+	}
+
+	// End-to-end test files are rewritten separately, if they
+	// happen to be inside the input directory.
+	fn is_in_test_input(
+		item: &walkdir::DirEntry,
+		config: &Config,
+	) -> bool {
+		config
+			.test_input
+			.as_ref()
+			.is_some_and(|test_input| item.path().starts_with(test_input))
 	}
 
 	fn handle_file(
@@ -132,6 +172,7 @@ fn analyse_file(
 	config: &Config,
 ) -> Result<(), std::io::Error> {
 	let mut file_contents = fs::read_to_string(file_path)?;
+	let warnings_count = selectors.warnings.len();
 	println!("Reading file: {}", file_path.display());
 
 	match file_path
@@ -140,13 +181,34 @@ fn analyse_file(
 		.map(|extension| extension.to_lowercase())
 		.as_deref()
 	{
+		Some(extension) if get_template_dialect(extension, config).is_some() => {
+			for dynamic_part in parse_selectors::read_from_template(
+				&mut file_contents,
+				selectors,
+				config,
+				get_template_dialect(extension, config).unwrap(),
+			) {
+				println!("  Dynamic selector left as is: {dynamic_part}");
+			}
+		},
 		Some("css") => parse_selectors::read_from_css(&mut file_contents, selectors, config),
-		Some("html") | Some("svg") => {
+		Some("html") | Some("htm") | Some("svg") => {
 			parse_selectors::read_from_html(&mut file_contents, selectors, config)
 		},
-		Some("js") => parse_selectors::read_from_js(&mut file_contents, selectors, config),
+		Some("js") | Some("mjs") | Some("cjs") | Some("jsx") | Some("ts") | Some("mts")
+		| Some("cts") | Some("tsx") => {
+			parse_selectors::read_from_js(&mut file_contents, selectors, config)
+		},
+		Some("vue") | Some("svelte") | Some("astro") => {
+			parse_selectors::read_from_component(&mut file_contents, selectors, config)
+		},
 		_ => (),
 	}
+
+	for warning in &selectors.warnings[warnings_count..] {
+		println!("  {warning}");
+	}
+
 	Ok(())
 }
 
@@ -164,27 +226,59 @@ fn write_to_file(
 		.map(|extension| extension.to_lowercase())
 		.as_deref()
 	{
+		Some(extension) if get_template_dialect(extension, config).is_some() => {
+			parse_selectors::write_to_template(
+				&mut file_contents,
+				selectors,
+				config,
+				get_template_dialect(extension, config).unwrap(),
+			)
+		},
 		Some("css") => parse_selectors::write_to_css(&mut file_contents, selectors, config),
 		Some("html") | Some("htm") | Some("svg") => {
 			parse_selectors::write_to_html(&mut file_contents, selectors, config)
 		},
-		Some("js") | Some("mjs") | Some("cjs") => {
+		Some("js") | Some("mjs") | Some("cjs") | Some("jsx") | Some("ts") | Some("mts")
+		| Some("cts") | Some("tsx") => {
 			parse_selectors::write_to_js(&mut file_contents, selectors, config)
 		},
+		Some("vue") | Some("svelte") | Some("astro") => {
+			parse_selectors::write_to_component(&mut file_contents, selectors, config)
+		},
 		_ => (),
 	}
 
-	let output_path = match &config.input.is_dir() {
+	save_file(file_path, &config.input, &config.output, file_contents)
+}
+
+fn write_to_test_file(
+	file_path: &Path,
+	input: &Path,
+	output: &Path,
+	selectors: &Selectors,
+	config: &Config,
+) -> Result<(), std::io::Error> {
+	let mut file_contents = fs::read_to_string(file_path)?;
+	println!("Processing test file: {}", file_path.display());
+
+	parse_selectors::write_to_e2e(&mut file_contents, selectors, config);
+
+	save_file(file_path, input, output, file_contents)
+}
+
+fn save_file(
+	file_path: &Path,
+	input: &Path,
+	output: &Path,
+	file_contents: String,
+) -> Result<(), std::io::Error> {
+	let output_path = match input.is_dir() {
 		// Remove given source directory to make each
 		// matched file relative to the output directory.
-		true => {
-			config
-				.output
-				.join(file_path.strip_prefix(&config.input).unwrap())
-		},
+		true => output.join(file_path.strip_prefix(input).unwrap()),
 		// Or if input path was to a file, append only
 		// the file name to the given output directory
-		false => config.output.join(file_path.file_name().unwrap()),
+		false => output.join(file_path.file_name().unwrap()),
 	};
 
 	// Making sure output directory exists or is
@@ -197,3 +291,150 @@ fn write_to_file(
 
 	Ok(())
 }
+
+// Read values of unprocessed attributes in markup and JSX files, then print
+// the custom attributes that refer to known classes or IDs along with a
+// config block to copy.
+fn print_attribute_suggestions(
+	selectors: &Selectors,
+	config: &Config,
+) -> Result<(), std::io::Error> {
+	let mut values = BTreeMap::new();
+
+	for entry in WalkDir::new(&config.input)
+		.into_iter()
+		.filter_map(|e| e.ok())
+		.filter(|entry| is_markup_file(entry) || is_script_file(entry))
+	{
+		let file_contents = fs::read_to_string(entry.path())?;
+		let file_path = entry.path().display().to_string();
+
+		if is_script_file(&entry) {
+			parse_selectors::read_jsx_attribute_values(
+				&file_contents,
+				&file_path,
+				&mut values,
+				config,
+			);
+		} else {
+			parse_selectors::read_attribute_values(&file_contents, &file_path, &mut values, config);
+		}
+	}
+
+	let suggestions = parse_selectors::suggest::suggest_custom_attributes(&values, selectors);
+
+	if suggestions.is_empty() {
+		println!("No custom attributes to suggest.");
+		return Ok(());
+	}
+
+	println!("Suggested custom attributes:");
+	for suggestion in &suggestions {
+		println!(
+			"  {} ({}, {:.0}% confidence, {} of {} values)",
+			suggestion.name,
+			suggestion.kind,
+			suggestion.confidence * 100.0,
+			suggestion.matches,
+			suggestion.occurrences,
+		);
+		for example in &suggestion.examples {
+			println!("    {example}");
+		}
+	}
+
+	let mut kinds: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+	for suggestion in &suggestions {
+		kinds
+			.entry(&suggestion.kind)
+			.or_default()
+			.push(&suggestion.name);
+	}
+
+	println!(
+		"\nConfig file:\n{}",
+		serde_json::to_string_pretty(&serde_json::json!({
+			"input": config.input,
+			"output": config.output,
+			"customAttributes": kinds,
+		}))?
+	);
+
+	Ok(())
+}
+
+// Check that path is a file with markup, which custom attributes are
+// suggested from.
+fn is_markup_file(item: &walkdir::DirEntry) -> bool {
+	item.path().is_file()
+		&& matches!(
+			item.path()
+				.extension()
+				.and_then(OsStr::to_str)
+				.map(|ext| ext.to_lowercase())
+				.as_deref(),
+			Some(
+				"html"
+					| "htm" | "svg" | "vue"
+					| "svelte" | "astro"
+					| "hbs" | "handlebars"
+					| "mustache" | "liquid"
+					| "j2" | "jinja"
+					| "jinja2" | "njk"
+					| "twig" | "erb"
+					| "php"
+			)
+		)
+}
+
+// Check that path is a JS or TypeScript file, which end-to-end
+// tests are written in.
+fn is_test_file(item: &walkdir::DirEntry) -> bool {
+	item.path().is_file()
+		&& matches!(
+			item.path()
+				.extension()
+				.and_then(OsStr::to_str)
+				.map(|ext| ext.to_lowercase())
+				.as_deref(),
+			Some("js" | "mjs" | "cjs" | "jsx" | "ts" | "mts" | "cts" | "tsx")
+		)
+}
+
+// Check that path is a JS or TypeScript file, which custom attributes
+// are suggested from (in JSX).
+fn is_script_file(item: &walkdir::DirEntry) -> bool {
+	item.path().is_file()
+		&& matches!(
+			item.path()
+				.extension()
+				.and_then(OsStr::to_str)
+				.map(|ext| ext.to_lowercase())
+				.as_deref(),
+			Some("js" | "mjs" | "cjs" | "jsx" | "ts" | "mts" | "cts" | "tsx")
+		)
+}
+
+// Work out the server-side template language of a file from its extension,
+// HTML files use the template dialect option (if any). Files with a template
+// extension are only processed when their dialect is the one chosen, Twig
+// being close enough to Jinja to share its dialect.
+fn get_template_dialect<'a>(
+	extension: &str,
+	config: &'a Config,
+) -> Option<&'a str> {
+	let dialect = match extension {
+		"hbs" | "handlebars" | "mustache" => "handlebars",
+		"liquid" => "liquid",
+		"j2" | "jinja" | "jinja2" | "njk" | "twig" => "jinja",
+		"erb" => "erb",
+		"php" => "php",
+		"html" | "htm" => return config.template_dialect.as_deref(),
+		_ => return None,
+	};
+
+	config
+		.template_dialect
+		.as_deref()
+		.filter(|template_dialect| *template_dialect == dialect)
+}
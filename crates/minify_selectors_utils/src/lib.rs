@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::collections::HashSet;
 use std::fs;
 use std::path::PathBuf;
@@ -22,18 +23,36 @@ pub struct Cli {
 		short = 'c',
 		long,
 		conflicts_with_all(["input", "output"]),
-		required_unless_present_all(["input", "output"]),
+		required_unless_present("input"),
 	)]
 	config: Option<String>,
 
 	/// Directory to process from
-	#[clap(short = 'i', long, requires("output"))]
+	#[clap(short = 'i', long)]
 	input: Option<String>,
 
 	/// Output directory to save files to
-	#[clap(short = 'o', long, requires("input"))]
+	#[clap(
+		short = 'o',
+		long,
+		requires("input"),
+		required_unless_present_any(["config", "suggest"]),
+	)]
 	output: Option<String>,
 
+	/// Suggest custom attributes that contain known classes or IDs, rather
+	/// than minifying (no files are written)
+	#[clap(long)]
+	suggest: bool,
+
+	/// Directory of end-to-end test files to rewrite
+	#[clap(long = "test-input", requires("test_output"), conflicts_with("config"))]
+	test_input: Option<String>,
+
+	/// Output directory to save rewritten test files to
+	#[clap(long = "test-output", requires("test_input"), conflicts_with("config"))]
+	test_output: Option<String>,
+
 	/// Index to start encoding from
 	#[clap(long = "start-index")]
 	start_index: Option<usize>,
@@ -50,6 +69,19 @@ pub struct Cli {
 	#[clap(long)]
 	sort: Option<Option<bool>>,
 
+	/// Process jQuery (and Zepto) function calls in JS
+	#[clap(long)]
+	jquery: Option<Option<bool>>,
+
+	/// Attributes of frameworks and libraries to process, i.e. htmx or alpine.
+	#[clap(
+		long = "preset",
+		value_delimiter = ' ',
+		num_args = 1..,
+		value_parser = ["alpine", "angular", "bootstrap", "htmx", "jquery", "vue"],
+	)]
+	preset: Option<Vec<String>>,
+
 	/// Custom attributes that contain space-separated list of classes.
 	#[clap(long = "custom-class-attribute", value_delimiter = ' ', num_args = 1..)]
 	custom_class_attribute: Option<Vec<String>>,
@@ -73,6 +105,71 @@ pub struct Cli {
 	/// Custom attributes that contain JS code.
 	#[clap(long = "custom-script-attribute", value_delimiter = ' ', num_args = 1..)]
 	custom_script_attribute: Option<Vec<String>>,
+
+	/// Custom attributes that contain HTML.
+	#[clap(long = "custom-markup-attribute", value_delimiter = ' ', num_args = 1..)]
+	custom_markup_attribute: Option<Vec<String>>,
+
+	/// Fields of custom attributes with JSON values that contain classes, IDs,
+	/// selectors, etc. as attribute/pointer=kind, i.e.
+	/// data-options/target=selector.
+	#[clap(long = "custom-json-attribute", value_delimiter = ' ', num_args = 1..)]
+	custom_json_attribute: Option<Vec<String>>,
+
+	/// Custom functions that take classes, i.e. clsx or clsx(0) for
+	/// only the first argument.
+	#[clap(long = "custom-class-function", value_delimiter = ' ', num_args = 1..)]
+	custom_class_function: Option<Vec<String>>,
+
+	/// Custom functions that take IDs.
+	#[clap(long = "custom-id-function", value_delimiter = ' ', num_args = 1..)]
+	custom_id_function: Option<Vec<String>>,
+
+	/// Custom functions that take a selector string.
+	#[clap(long = "custom-selector-function", value_delimiter = ' ', num_args = 1..)]
+	custom_selector_function: Option<Vec<String>>,
+
+	/// Script element types that contain HTML templates.
+	#[clap(long = "markup-script-type", value_delimiter = ' ', num_args = 1..)]
+	markup_script_type: Option<Vec<String>>,
+
+	/// Script element types that contain JS code.
+	#[clap(long = "js-script-type", value_delimiter = ' ', num_args = 1..)]
+	js_script_type: Option<Vec<String>>,
+
+	/// Script element types to leave as is.
+	#[clap(long = "ignore-script-type", value_delimiter = ' ', num_args = 1..)]
+	ignore_script_type: Option<Vec<String>>,
+
+	/// Tags of JS template literals that contain HTML, i.e. html (lit).
+	#[clap(long = "markup-template-tag", value_delimiter = ' ', num_args = 1..)]
+	markup_template_tag: Option<Vec<String>>,
+
+	/// Tags of JS template literals that contain CSS, i.e. css (lit) or
+	/// styled (styled-components).
+	#[clap(long = "css-template-tag", value_delimiter = ' ', num_args = 1..)]
+	css_template_tag: Option<Vec<String>>,
+
+	/// Characters that start the prefix of selectors marked to encode or
+	/// ignore, i.e. __ in __class--foo.
+	#[clap(long = "marker-start", allow_hyphen_values = true)]
+	marker_start: Option<String>,
+
+	/// Characters that end the prefix of selectors marked to encode or
+	/// ignore, i.e. -- in __class--foo.
+	#[clap(long = "marker-end", allow_hyphen_values = true)]
+	marker_end: Option<String>,
+
+	/// Level of the HTML, SVG and WAI-ARIA specs to recognise attributes from.
+	#[clap(long = "spec-level", value_parser = ["html5", "living", "experimental"])]
+	spec_level: Option<String>,
+
+	/// Server-side template language used in HTML files.
+	#[clap(
+		long = "template-dialect",
+		value_parser = ["handlebars", "liquid", "jinja", "erb", "php"],
+	)]
+	template_dialect: Option<String>,
 }
 
 
@@ -82,12 +179,24 @@ pub struct Cli {
 pub struct Config {
 	pub input: PathBuf,
 	pub output: PathBuf,
+	pub test_input: Option<PathBuf>,
+	pub test_output: Option<PathBuf>,
 	pub alphabet: (Vec<char>, Vec<usize>),
 	pub start_index: usize,
 	pub current_step: ProcessingSteps,
+	pub suggest: bool,
 	pub parallel: bool,
 	pub sort: bool,
+	pub jquery: bool,
+	pub presets: Vec<String>,
 	pub custom_attributes: Vec<(String, String)>,
+	pub json_attributes: Vec<(String, String, String)>,
+	pub custom_functions: Vec<(String, String)>,
+	pub custom_script_types: Vec<(String, String)>,
+	pub template_tags: Vec<(String, String)>,
+	pub markers: (String, String),
+	pub spec_level: String,
+	pub template_dialect: Option<String>,
 }
 
 #[derive(Clone, Debug, Default, PartialEq)]
@@ -100,7 +209,17 @@ pub enum ProcessingSteps {
 
 impl Config {
 	pub fn new() -> Self {
-		let cli_args = Cli::parse();
+		Config::from_args(std::env::args_os())
+	}
+
+	/// Same as `Config::new()`, but with the given command line arguments
+	/// (the first being the binary name) rather than those of the process.
+	pub fn from_args<I, T>(args: I) -> Self
+	where
+		I: IntoIterator<Item = T>,
+		T: Into<std::ffi::OsString> + Clone,
+	{
+		let cli_args = Cli::parse_from(args);
 		let mut config: Config = Default::default();
 		let external_config: Option<ExternalConfig> = cli_args.config.clone().map(|config_path| {
 			serde_json::from_str(
@@ -114,27 +233,43 @@ impl Config {
 			config.output = PathBuf::from(&external_config.as_ref().unwrap().output);
 		} else {
 			config.input = PathBuf::from(&cli_args.input.unwrap());
-			config.output = PathBuf::from(&cli_args.output.unwrap());
+			config.output = PathBuf::from(&cli_args.output.unwrap_or_default());
 		}
 
-		if external_config.is_some() {
-			if let Some(alphabet) = &external_config.as_ref().unwrap().alphabet {
+		config.suggest = cli_args.suggest;
+
+		if let Some(external_config) = &external_config {
+			if let (Some(test_input), Some(test_output)) =
+				(&external_config.test_input, &external_config.test_output)
+			{
+				config.test_input = Some(PathBuf::from(test_input));
+				config.test_output = Some(PathBuf::from(test_output));
+			}
+		} else if let (Some(test_input), Some(test_output)) =
+			(&cli_args.test_input, &cli_args.test_output)
+		{
+			config.test_input = Some(PathBuf::from(test_input));
+			config.test_output = Some(PathBuf::from(test_output));
+		}
+
+		if let Some(external_config) = &external_config {
+			if let Some(alphabet) = &external_config.alphabet {
 				config.alphabet = encode_selector::into_alphabet_set(alphabet);
 			}
 		} else if let Some(alphabet) = cli_args.alphabet {
 			config.alphabet = encode_selector::into_alphabet_set(&alphabet);
 		}
 
-		if external_config.is_some() {
-			if let Some(index) = external_config.as_ref().unwrap().start_index {
+		if let Some(external_config) = &external_config {
+			if let Some(index) = external_config.start_index {
 				config.start_index = index;
 			}
 		} else if let Some(index) = cli_args.start_index {
 			config.start_index = index;
 		}
 
-		if external_config.is_some() {
-			if let Some(parallel) = external_config.as_ref().unwrap().parallel {
+		if let Some(external_config) = &external_config {
+			if let Some(parallel) = external_config.parallel {
 				config.parallel = parallel;
 			}
 		} else {
@@ -146,8 +281,8 @@ impl Config {
 			};
 		}
 
-		if external_config.is_some() {
-			if let Some(sort) = external_config.as_ref().unwrap().sort {
+		if let Some(external_config) = &external_config {
+			if let Some(sort) = external_config.sort {
 				config.sort = sort;
 			}
 		} else {
@@ -159,13 +294,39 @@ impl Config {
 			};
 		}
 
+		if let Some(external_config) = &external_config {
+			if let Some(jquery) = external_config.jquery {
+				config.jquery = jquery;
+			}
+		} else {
+			config.jquery = match &cli_args.jquery {
+				None => false,
+				Some(None) => true,         // --jquery
+				Some(Some(true)) => true,   // --jquery=true
+				Some(Some(false)) => false, // --jquery=false
+			};
+		}
+
+		if let Some(external_config) = &external_config {
+			if let Some(presets) = &external_config.presets {
+				config.presets = presets.clone();
+			}
+		} else if let Some(presets) = &cli_args.preset {
+			config.presets = presets.clone();
+		}
+
+		// jQuery preset is the same as the jquery option.
+		if config.presets.iter().any(|preset| preset == "jquery") {
+			config.jquery = true;
+		}
+
 		let mut custom_attributes: Vec<(String, String)> = vec![];
 
 		if external_config.is_some() {
 			if let Some(attributes) = external_config
 				.as_ref()
 				.and_then(|external_config| external_config.custom_attributes.as_ref())
-				.and_then(|custom_attributes| custom_attributes.id.as_ref())
+				.and_then(|custom_attributes| custom_attributes.class.as_ref())
 			{
 				for name in attributes {
 					custom_attributes.push((name.to_string(), "class".to_string()));
@@ -181,7 +342,7 @@ impl Config {
 			if let Some(attributes) = external_config
 				.as_ref()
 				.and_then(|external_config| external_config.custom_attributes.as_ref())
-				.and_then(|custom_attributes| custom_attributes.class.as_ref())
+				.and_then(|custom_attributes| custom_attributes.id.as_ref())
 			{
 				for name in attributes {
 					custom_attributes.push((name.to_string(), "id".to_string()));
@@ -257,7 +418,251 @@ impl Config {
 			}
 		}
 
+		if external_config.is_some() {
+			if let Some(attributes) = external_config
+				.as_ref()
+				.and_then(|external_config| external_config.custom_attributes.as_ref())
+				.and_then(|custom_attributes| custom_attributes.markup.as_ref())
+			{
+				for name in attributes {
+					custom_attributes.push((name.to_string(), "markup".to_string()));
+				}
+			}
+		} else if let Some(attributes) = &cli_args.custom_markup_attribute {
+			for name in attributes {
+				custom_attributes.push((name.to_string(), "markup".to_string()));
+			}
+		}
+
+		let mut json_attributes: Vec<(String, String, String)> = vec![];
+
+		if external_config.is_some() {
+			if let Some(attributes) = external_config
+				.as_ref()
+				.and_then(|external_config| external_config.custom_attributes.as_ref())
+				.and_then(|custom_attributes| custom_attributes.json.as_ref())
+			{
+				for (name, fields) in attributes {
+					for (path, kind) in fields {
+						json_attributes.push((
+							name.to_string(),
+							path.to_string(),
+							kind.to_string(),
+						));
+					}
+				}
+			}
+		} else if let Some(rules) = &cli_args.custom_json_attribute {
+			for rule in rules {
+				json_attributes.push(parse_json_attribute_rule(rule));
+			}
+		}
+
+		for (name, _, _) in &json_attributes {
+			if !custom_attributes
+				.iter()
+				.any(|(attribute, _)| attribute == name)
+			{
+				custom_attributes.push((name.to_string(), "json".to_string()));
+			}
+		}
+
 		config.custom_attributes = custom_attributes;
+		config.json_attributes = json_attributes;
+
+		let mut custom_functions: Vec<(String, String)> = vec![];
+
+		if external_config.is_some() {
+			if let Some(functions) = external_config
+				.as_ref()
+				.and_then(|external_config| external_config.custom_functions.as_ref())
+				.and_then(|custom_functions| custom_functions.class.as_ref())
+			{
+				for signature in functions {
+					custom_functions.push((signature.to_string(), "class".to_string()));
+				}
+			}
+		} else if let Some(functions) = &cli_args.custom_class_function {
+			for signature in functions {
+				custom_functions.push((signature.to_string(), "class".to_string()));
+			}
+		}
+
+		if external_config.is_some() {
+			if let Some(functions) = external_config
+				.as_ref()
+				.and_then(|external_config| external_config.custom_functions.as_ref())
+				.and_then(|custom_functions| custom_functions.id.as_ref())
+			{
+				for signature in functions {
+					custom_functions.push((signature.to_string(), "id".to_string()));
+				}
+			}
+		} else if let Some(functions) = &cli_args.custom_id_function {
+			for signature in functions {
+				custom_functions.push((signature.to_string(), "id".to_string()));
+			}
+		}
+
+		if external_config.is_some() {
+			if let Some(functions) = external_config
+				.as_ref()
+				.and_then(|external_config| external_config.custom_functions.as_ref())
+				.and_then(|custom_functions| custom_functions.selector.as_ref())
+			{
+				for signature in functions {
+					custom_functions.push((signature.to_string(), "selector".to_string()));
+				}
+			}
+		} else if let Some(functions) = &cli_args.custom_selector_function {
+			for signature in functions {
+				custom_functions.push((signature.to_string(), "selector".to_string()));
+			}
+		}
+
+		config.custom_functions = custom_functions;
+
+		let mut custom_script_types: Vec<(String, String)> = vec![];
+
+		if external_config.is_some() {
+			if let Some(script_types) = external_config
+				.as_ref()
+				.and_then(|external_config| external_config.script_types.as_ref())
+				.and_then(|script_types| script_types.markup.as_ref())
+			{
+				for script_type in script_types {
+					custom_script_types.push((script_type.to_string(), "markup".to_string()));
+				}
+			}
+		} else if let Some(script_types) = &cli_args.markup_script_type {
+			for script_type in script_types {
+				custom_script_types.push((script_type.to_string(), "markup".to_string()));
+			}
+		}
+
+		if external_config.is_some() {
+			if let Some(script_types) = external_config
+				.as_ref()
+				.and_then(|external_config| external_config.script_types.as_ref())
+				.and_then(|script_types| script_types.js.as_ref())
+			{
+				for script_type in script_types {
+					custom_script_types.push((script_type.to_string(), "script".to_string()));
+				}
+			}
+		} else if let Some(script_types) = &cli_args.js_script_type {
+			for script_type in script_types {
+				custom_script_types.push((script_type.to_string(), "script".to_string()));
+			}
+		}
+
+		if external_config.is_some() {
+			if let Some(script_types) = external_config
+				.as_ref()
+				.and_then(|external_config| external_config.script_types.as_ref())
+				.and_then(|script_types| script_types.ignore.as_ref())
+			{
+				for script_type in script_types {
+					custom_script_types.push((script_type.to_string(), "ignore".to_string()));
+				}
+			}
+		} else if let Some(script_types) = &cli_args.ignore_script_type {
+			for script_type in script_types {
+				custom_script_types.push((script_type.to_string(), "ignore".to_string()));
+			}
+		}
+
+		config.custom_script_types = custom_script_types;
+
+		let mut template_tags: Vec<(String, String)> = vec![];
+
+		if external_config.is_some() {
+			if let Some(tags) = external_config
+				.as_ref()
+				.and_then(|external_config| external_config.template_tags.as_ref())
+				.and_then(|template_tags| template_tags.markup.as_ref())
+			{
+				for tag in tags {
+					template_tags.push((tag.to_string(), "markup".to_string()));
+				}
+			}
+		} else if let Some(tags) = &cli_args.markup_template_tag {
+			for tag in tags {
+				template_tags.push((tag.to_string(), "markup".to_string()));
+			}
+		}
+
+		if external_config.is_some() {
+			if let Some(tags) = external_config
+				.as_ref()
+				.and_then(|external_config| external_config.template_tags.as_ref())
+				.and_then(|template_tags| template_tags.css.as_ref())
+			{
+				for tag in tags {
+					template_tags.push((tag.to_string(), "css".to_string()));
+				}
+			}
+		} else if let Some(tags) = &cli_args.css_template_tag {
+			for tag in tags {
+				template_tags.push((tag.to_string(), "css".to_string()));
+			}
+		}
+
+		config.template_tags = template_tags;
+
+		if let Some(external_config) = &external_config {
+			if let Some(markers) = &external_config.markers {
+				if let Some(start) = &markers.start {
+					config.markers.0 = start.to_string();
+				}
+				if let Some(end) = &markers.end {
+					config.markers.1 = end.to_string();
+				}
+			}
+		} else {
+			if let Some(start) = &cli_args.marker_start {
+				config.markers.0 = start.to_string();
+			}
+			if let Some(end) = &cli_args.marker_end {
+				config.markers.1 = end.to_string();
+			}
+		}
+
+		if config.markers.0.is_empty() || config.markers.1.is_empty() {
+			panic!("Markers of prefixed selectors cannot be empty");
+		}
+		// Class and ID values are split on whitespace, so a marker with
+		// whitespace would never match.
+		if format!("{}{}", config.markers.0, config.markers.1).contains(char::is_whitespace) {
+			panic!("Markers of prefixed selectors cannot contain whitespace");
+		}
+
+		if let Some(external_config) = &external_config {
+			if let Some(spec_level) = &external_config.spec_level {
+				config.spec_level = spec_level.to_string();
+			}
+		} else if let Some(spec_level) = cli_args.spec_level {
+			config.spec_level = spec_level;
+		}
+
+		// Only needed for config files, the command line option is limited
+		// to the same values.
+		if !matches!(
+			config.spec_level.as_str(),
+			"html5" | "living" | "experimental"
+		) {
+			panic!(
+				"Unknown spec level \"{}\", expected html5, living or experimental",
+				config.spec_level
+			);
+		}
+
+		if let Some(external_config) = &external_config {
+			config.template_dialect = external_config.template_dialect.clone();
+		} else {
+			config.template_dialect = cli_args.template_dialect;
+		}
+
 		config
 	}
 }
@@ -267,18 +672,58 @@ impl Default for Config {
 		Self {
 			input: PathBuf::from(""),
 			output: PathBuf::from(""),
+			test_input: None,
+			test_output: None,
 			alphabet: encode_selector::into_alphabet_set(
 				"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ",
 			),
 			start_index: 0,
 			current_step: ProcessingSteps::ReadingFromFiles,
+			suggest: false,
 			parallel: false,
 			sort: true,
+			jquery: false,
+			presets: vec![],
 			custom_attributes: vec![],
+			json_attributes: vec![],
+			custom_functions: vec![],
+			custom_script_types: vec![],
+			template_tags: vec![],
+			markers: (String::from("__"), String::from("--")),
+			spec_level: String::from("living"),
+			template_dialect: None,
 		}
 	}
 }
 
+// Split custom JSON attribute rule from the command line into the attribute,
+// path of the field (JSON pointer) and what it contains, i.e.
+// `data-options/target=selector`.
+fn parse_json_attribute_rule(rule: &str) -> (String, String, String) {
+	let (rule, kind) = rule
+		.rsplit_once('=')
+		.expect("Custom JSON attribute rule is missing what the field contains");
+	let mut depth: usize = 0;
+	let path_start = rule
+		.char_indices()
+		.find(|(_, character)| {
+			match character {
+				'[' => depth += 1,
+				']' => depth = depth.saturating_sub(1),
+				'/' => return depth == 0,
+				_ => {},
+			}
+			false
+		})
+		.map_or(rule.len(), |(index, _)| index);
+
+	(
+		rule[..path_start].to_string(),
+		rule[path_start..].to_string(),
+		kind.to_string(),
+	)
+}
+
 
 
 
@@ -289,13 +734,23 @@ impl Default for Config {
 struct ExternalConfig {
 	input: String,
 	output: String,
+	test_input: Option<String>,
+	test_output: Option<String>,
 	alphabet: Option<String>,
 	// #[serde(rename = "startIndex")]
 	start_index: Option<usize>,
 	parallel: Option<bool>,
 	sort: Option<bool>,
+	jquery: Option<bool>,
+	presets: Option<Vec<String>>,
 	// #[serde(rename = "customAttributes")]
 	custom_attributes: Option<CustomAttributes>,
+	custom_functions: Option<CustomFunctions>,
+	script_types: Option<ScriptTypes>,
+	template_tags: Option<TemplateTags>,
+	markers: Option<Markers>,
+	spec_level: Option<String>,
+	template_dialect: Option<String>,
 }
 #[allow(dead_code)]
 #[derive(Clone, Debug, Deserialize)]
@@ -306,6 +761,34 @@ struct CustomAttributes {
 	anchor: Option<Vec<String>>,
 	style: Option<Vec<String>>,
 	script: Option<Vec<String>>,
+	markup: Option<Vec<String>>,
+	json: Option<BTreeMap<String, BTreeMap<String, String>>>,
+}
+#[allow(dead_code)]
+#[derive(Clone, Debug, Deserialize)]
+struct CustomFunctions {
+	class: Option<Vec<String>>,
+	id: Option<Vec<String>>,
+	selector: Option<Vec<String>>,
+}
+#[allow(dead_code)]
+#[derive(Clone, Debug, Deserialize)]
+struct ScriptTypes {
+	markup: Option<Vec<String>>,
+	js: Option<Vec<String>>,
+	ignore: Option<Vec<String>>,
+}
+#[allow(dead_code)]
+#[derive(Clone, Debug, Deserialize)]
+struct TemplateTags {
+	markup: Option<Vec<String>>,
+	css: Option<Vec<String>>,
+}
+#[allow(dead_code)]
+#[derive(Clone, Debug, Deserialize)]
+struct Markers {
+	start: Option<String>,
+	end: Option<String>,
 }
 
 
@@ -319,6 +802,7 @@ pub struct Selector {
 	pub counter: usize,
 	pub markup_class_counter: usize,
 	pub markup_id_counter: usize,
+	pub markup_part_counter: usize,
 	pub selector_string_counter: usize,
 	pub anchor_counter: usize,
 	pub style_counter: usize,
@@ -330,12 +814,14 @@ pub struct Selector {
 pub enum SelectorType {
 	Class,
 	Id,
+	Part,
 }
 
 #[derive(Clone, Copy, Debug)]
 pub enum SelectorUsage {
 	MarkupClass,
 	MarkupId,
+	MarkupPart,
 	SelectorString,
 	Anchor,
 	Style,
@@ -349,6 +835,8 @@ impl Selector {
 			kind: match selector.chars().next() {
 				Some('.') => Some(SelectorType::Class),
 				Some('#') => Some(SelectorType::Id),
+				// Shadow part names, i.e. `::foo`.
+				Some(':') => Some(SelectorType::Part),
 				_ => panic!("Missing or unknown selector type"),
 			},
 			..Default::default()
@@ -365,6 +853,7 @@ impl Selector {
 		match usage {
 			Some(SelectorUsage::MarkupClass) => self.markup_class_counter += 1,
 			Some(SelectorUsage::MarkupId) => self.markup_id_counter += 1,
+			Some(SelectorUsage::MarkupPart) => self.markup_part_counter += 1,
 			Some(SelectorUsage::SelectorString) => self.selector_string_counter += 1,
 			Some(SelectorUsage::Anchor) => self.anchor_counter += 1,
 			Some(SelectorUsage::Style) => self.style_counter += 1,
@@ -381,6 +870,7 @@ impl Selector {
 		self.counter += incoming.counter;
 		self.markup_class_counter += incoming.markup_class_counter;
 		self.markup_id_counter += incoming.markup_id_counter;
+		self.markup_part_counter += incoming.markup_part_counter;
 		self.selector_string_counter += incoming.selector_string_counter;
 		self.anchor_counter += incoming.anchor_counter;
 		self.style_counter += incoming.style_counter;
@@ -405,6 +895,9 @@ pub struct Selectors {
 	pub map: IndexMap<String, Selector>,
 	pub class_counter: usize,
 	pub id_counter: usize,
+	pub part_counter: usize,
+	// Selectors (or patterns) found that are left as is.
+	pub warnings: Vec<String>,
 }
 
 impl Selectors {
@@ -413,6 +906,8 @@ impl Selectors {
 			map: IndexMap::new(),
 			class_counter: 0,
 			id_counter: 0,
+			part_counter: 0,
+			warnings: vec![],
 		}
 	}
 
@@ -439,6 +934,8 @@ impl Selectors {
 				self.map.insert(key.clone(), val.clone());
 			}
 		}
+
+		self.warnings.extend(incoming.warnings);
 	}
 
 	pub fn process(
@@ -486,6 +983,7 @@ impl Selectors {
 					match value.kind {
 						Some(SelectorType::Class) => &self.class_counter,
 						Some(SelectorType::Id) => &self.id_counter,
+						Some(SelectorType::Part) => &self.part_counter,
 						None => {
 							panic!("Trying to encode a selector with undefined type.");
 						},
@@ -499,6 +997,8 @@ impl Selectors {
 					self.class_counter += 1;
 				} else if value.kind == Some(SelectorType::Id) {
 					self.id_counter += 1;
+				} else if value.kind == Some(SelectorType::Part) {
+					self.part_counter += 1;
 				}
 			}
 
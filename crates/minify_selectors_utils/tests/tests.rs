@@ -0,0 +1,62 @@
+use minify_selectors_utils::*;
+
+
+
+
+#[test]
+fn config_file_custom_attributes() {
+	let config = Config::from_args([
+		"minify-selectors",
+		"--config",
+		"tests/config/custom-attributes.json",
+	]);
+
+	assert!(config
+		.custom_attributes
+		.contains(&("data-toggle-class".to_string(), "class".to_string())));
+	assert!(config
+		.custom_attributes
+		.contains(&("data-target".to_string(), "id".to_string())));
+}
+
+#[test]
+fn markers() {
+	// Markers starting with a hyphen are taken as values, not options.
+	let config = Config::from_args([
+		"minify-selectors",
+		"--input",
+		"src",
+		"--output",
+		"dist",
+		"--marker-start",
+		"@@",
+		"--marker-end",
+		"--",
+	]);
+
+	assert_eq!(config.markers, ("@@".to_string(), "--".to_string()));
+}
+
+#[test]
+#[should_panic(expected = "Markers of prefixed selectors cannot contain whitespace")]
+fn markers_with_whitespace() {
+	Config::from_args([
+		"minify-selectors",
+		"--input",
+		"src",
+		"--output",
+		"dist",
+		"--marker-start",
+		"{ ",
+	]);
+}
+
+#[test]
+#[should_panic(expected = "Unknown spec level \"htm5\", expected html5, living or experimental")]
+fn config_file_unknown_spec_level() {
+	Config::from_args([
+		"minify-selectors",
+		"--config",
+		"tests/config/spec-level.json",
+	]);
+}
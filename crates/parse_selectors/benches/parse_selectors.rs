@@ -66,9 +66,9 @@ fn process_file(
 	config.current_step = ProcessingSteps::WritingToFiles;
 
 	match file_type {
-		"css" => parse_selectors::write_to_css(&mut file, &mut selectors, &config),
-		"js" => parse_selectors::write_to_js(&mut file, &mut selectors, &config),
-		"html" | "svg" => parse_selectors::write_to_html(&mut file, &mut selectors, &config),
+		"css" => parse_selectors::write_to_css(&mut file, &selectors, &config),
+		"js" => parse_selectors::write_to_js(&mut file, &selectors, &config),
+		"html" | "svg" => parse_selectors::write_to_html(&mut file, &selectors, &config),
 		_ => panic!("file_type not one of the following: css, js, html or svg."),
 	}
 
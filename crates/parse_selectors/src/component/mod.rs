@@ -0,0 +1,599 @@
+use minify_selectors_utils::*;
+
+use crate::markup::html_attributes::get_attribute_designation;
+use crate::markup::smil::*;
+use crate::markup::tokenizer;
+use crate::markup::tokenizer::Attribute;
+use crate::markup::*;
+use crate::pragmas::*;
+use crate::script::lexer::TokenKind;
+use crate::script::*;
+use crate::style::*;
+use crate::Replacement;
+
+
+
+
+// Directives in component markup that contain classes, IDs, etc.
+enum Directive {
+	// Attribute bound to an expression, i.e. `:class="…"` (Vue and Alpine),
+	// `[ngClass]="…"` (Angular) or `class={…}` (Svelte and Astro), along with
+	// the attribute name and range of the expression.
+	Binding(String, usize, usize),
+	// Class toggled by the attribute name, i.e. `class:active` (Svelte) or
+	// `[class.active]` (Angular), along with the range of the class name.
+	ClassToggle(usize, usize),
+	// Quoted attribute value with expressions in braces, i.e.
+	// `class="btn {active ? 'on' : ''}"` (Svelte and Astro), along with the
+	// attribute name and range of the value.
+	Interpolation(String, usize, usize),
+}
+
+
+
+
+/// Analyse component file (Vue, Svelte or Astro).
+pub fn analyse_component(
+	file_string: &mut str,
+	selectors: &mut Selectors,
+	config: &Config,
+) {
+	let markup_start = get_frontmatter_end(file_string);
+
+	// Astro frontmatter
+	if markup_start > 0 {
+		let mut frontmatter = file_string[..markup_start].to_string();
+		analyse_js(&mut frontmatter, selectors, config);
+	}
+
+	let mut markup = file_string[markup_start..].to_string();
+	analyse_component_markup(&mut markup, selectors, config);
+	analyse_component_directives(&mut markup, selectors, config, true);
+}
+
+/// Rewrite component file (Vue, Svelte or Astro).
+pub fn rewrite_component(
+	file_string: &mut String,
+	selectors: &Selectors,
+	config: &Config,
+) {
+	let markup_start = get_frontmatter_end(file_string);
+
+	let mut frontmatter = file_string[..markup_start].to_string();
+	if markup_start > 0 {
+		rewrite_js(&mut frontmatter, selectors, config);
+	}
+
+	let mut markup = file_string[markup_start..].to_string();
+	rewrite_component_markup(&mut markup, selectors, config);
+	rewrite_component_directives(&mut markup, selectors, config, true);
+
+	*file_string = frontmatter + &markup;
+}
+
+/// Analyse framework directives in component markup.
+///
+/// Notes:
+///  - Attribute values in braces (i.e. `class={…}` in Svelte and Astro) are
+///    only expressions with `expressions`, elsewhere (i.e. presets in HTML
+///    files) they are just text.
+pub fn analyse_component_directives(
+	file_string: &mut str,
+	selectors: &mut Selectors,
+	config: &Config,
+	expressions: bool,
+) {
+	if let Some((mut masked, _)) = mask_disabled_regions(file_string, CommentSyntax::Markup) {
+		return analyse_component_directives(&mut masked, selectors, config, expressions);
+	}
+
+	let tokens = tokenizer::tokenize(file_string, expressions);
+	let mut element = None;
+
+	for (tag, attribute) in get_processable_attributes(&tokens) {
+		match get_directive(file_string, attribute, expressions) {
+			Some(Directive::ClassToggle(start, end)) => {
+				super::analyse_string_of_tokens(
+					&mut file_string[start..end].to_string(),
+					selectors,
+					"class",
+					Some(SelectorUsage::MarkupClass),
+				);
+			},
+
+			Some(Directive::Binding(name, start, end)) => {
+				let Some(attribute_type_designation) = get_attribute_designation(
+					&name,
+					Some(get_cached_element(&mut element, file_string, tag)),
+				) else {
+					continue;
+				};
+				let mut expression = file_string[start..end].to_string();
+
+				analyse_binding(
+					&mut expression,
+					selectors,
+					config,
+					attribute_type_designation,
+				);
+			},
+
+			Some(Directive::Interpolation(name, start, end)) => {
+				let Some(attribute_type_designation) = get_attribute_designation(
+					&name,
+					Some(get_cached_element(&mut element, file_string, tag)),
+				) else {
+					continue;
+				};
+
+				if is_interpolated(&file_string[start..end], attribute_type_designation) {
+					analyse_interpolation(
+						&file_string[start..end],
+						selectors,
+						config,
+						attribute_type_designation,
+					);
+				}
+			},
+
+			None => continue,
+		}
+	}
+}
+
+/// Rewrite framework directives in component markup.
+pub fn rewrite_component_directives(
+	file_string: &mut String,
+	selectors: &Selectors,
+	config: &Config,
+	expressions: bool,
+) {
+	if let Some((mut masked, regions)) = mask_disabled_regions(file_string, CommentSyntax::Markup) {
+		rewrite_component_directives(&mut masked, selectors, config, expressions);
+		*file_string = unmask_disabled_regions(&masked, &regions, CommentSyntax::Markup);
+		return;
+	}
+
+	let tokens = tokenizer::tokenize(file_string, expressions);
+	let mut replacements: Vec<Replacement> = vec![];
+	let mut element = None;
+
+	for (tag, attribute) in get_processable_attributes(&tokens) {
+		let (start, end, value) = match get_directive(file_string, attribute, expressions) {
+			Some(Directive::ClassToggle(start, end)) => {
+				let mut class = file_string[start..end].to_string();
+				super::rewrite_string_of_tokens(&mut class, selectors, "class");
+				(start, end, class)
+			},
+
+			Some(Directive::Binding(name, start, end)) => {
+				let Some(attribute_type_designation) = get_attribute_designation(
+					&name,
+					Some(get_cached_element(&mut element, file_string, tag)),
+				) else {
+					continue;
+				};
+				let mut expression = file_string[start..end].to_string();
+
+				rewrite_binding(
+					&mut expression,
+					selectors,
+					config,
+					attribute_type_designation,
+				);
+				(start, end, expression)
+			},
+
+			Some(Directive::Interpolation(name, start, end)) => {
+				let Some(attribute_type_designation) = get_attribute_designation(
+					&name,
+					Some(get_cached_element(&mut element, file_string, tag)),
+				) else {
+					continue;
+				};
+
+				if !is_interpolated(&file_string[start..end], attribute_type_designation) {
+					continue;
+				}
+
+				let value = rewrite_interpolation(
+					&file_string[start..end],
+					selectors,
+					config,
+					attribute_type_designation,
+				);
+				(start, end, value)
+			},
+
+			None => continue,
+		};
+
+		if value != file_string[start..end] {
+			replacements.push(Replacement { start, end, value });
+		}
+	}
+
+	super::splice_replacements(file_string, replacements);
+}
+
+// Analyse component markup, same as HTML except that attribute values
+// in braces are expressions.
+fn analyse_component_markup(
+	markup: &mut str,
+	selectors: &mut Selectors,
+	config: &Config,
+) {
+	if let Some((mut masked, _)) = mask_disabled_regions(markup, CommentSyntax::Markup) {
+		return analyse_component_markup(&mut masked, selectors, config);
+	}
+
+	analyse_html_attributes(markup, selectors, config, None, true);
+	analyse_html_scripts(markup, selectors, config);
+	analyse_html_styles(markup, selectors, config);
+	super::analyse_prefixed_selectors(markup, selectors);
+}
+
+// Rewrite component markup, same as HTML except that attribute values
+// in braces are expressions.
+fn rewrite_component_markup(
+	markup: &mut String,
+	selectors: &Selectors,
+	config: &Config,
+) {
+	if let Some((mut masked, regions)) = mask_disabled_regions(markup, CommentSyntax::Markup) {
+		rewrite_component_markup(&mut masked, selectors, config);
+		*markup = unmask_disabled_regions(&masked, &regions, CommentSyntax::Markup);
+		return;
+	}
+
+	rewrite_html_attributes(markup, selectors, config, true);
+	rewrite_html_scripts(markup, selectors, config);
+	rewrite_html_styles(markup, selectors, config);
+	super::rewrite_prefixed_selectors(markup, selectors);
+}
+
+// Analyse expression that is bound to an attribute.
+fn analyse_binding(
+	expression: &mut str,
+	selectors: &mut Selectors,
+	config: &Config,
+	attribute_type_designation: &str,
+) {
+	let tokens = tokenize_js(expression);
+
+	match attribute_type_designation {
+		"id" | "class" | "part" => {
+			analyse_class_names(
+				expression,
+				&tokens,
+				selectors,
+				attribute_type_designation,
+				Some(match attribute_type_designation {
+					"id" => SelectorUsage::MarkupId,
+					"part" => SelectorUsage::MarkupPart,
+					_ => SelectorUsage::MarkupClass,
+				}),
+			);
+		},
+
+		"script" => {
+			analyse_js(expression, selectors, config);
+		},
+
+		// Rest only take a single string literal.
+		_ => {
+			let Some(literal) = get_single_literal(expression, &tokens) else {
+				return;
+			};
+			let mut value = unescape_js_string(literal);
+
+			match attribute_type_designation {
+				"selector" => analyse_css(&mut value, selectors, config),
+				"style" => analyse_css_functions(&mut value, selectors),
+				"anchor" => super::analyse_anchor_links(&mut value, selectors),
+				"exportparts" => {
+					analyse_export_parts(&value, selectors, Some(SelectorUsage::MarkupPart))
+				},
+				"timing" => analyse_smil_timing(&value, selectors, Some(SelectorUsage::MarkupId)),
+				_ => {},
+			}
+		},
+	}
+}
+
+// Rewrite expression that is bound to an attribute.
+fn rewrite_binding(
+	expression: &mut String,
+	selectors: &Selectors,
+	config: &Config,
+	attribute_type_designation: &str,
+) {
+	let tokens = tokenize_js(expression);
+	let mut replacements: Vec<Replacement> = vec![];
+
+	match attribute_type_designation {
+		"id" | "class" | "part" => {
+			replacements.extend(rewrite_class_names(
+				expression,
+				&tokens,
+				selectors,
+				attribute_type_designation,
+			));
+		},
+
+		"script" => {
+			rewrite_js(expression, selectors, config);
+		},
+
+		_ => {
+			let Some(literal) = get_single_literal(expression, &tokens) else {
+				return;
+			};
+			let original = unescape_js_string(literal);
+			let mut value = original.clone();
+
+			match attribute_type_designation {
+				"selector" => rewrite_css(&mut value, selectors, config),
+				"style" => rewrite_css_functions(&mut value, selectors),
+				"anchor" => super::rewrite_anchor_links(&mut value, selectors),
+				"exportparts" => rewrite_export_parts(&mut value, selectors),
+				"timing" => rewrite_smil_timing(&mut value, selectors),
+				_ => {},
+			}
+
+			if value != original {
+				let quote = expression[tokens[0].start..].chars().next().unwrap();
+				replacements.push(Replacement {
+					start: tokens[0].start,
+					end: tokens[0].end,
+					value: format!(
+						"{quote}{value}{quote}",
+						value = escape_js_chars(&value, quote)
+					),
+				});
+			}
+		},
+	}
+
+	super::splice_replacements(expression, replacements);
+}
+
+// Analyse quoted attribute value with expressions in braces, the classes
+// or IDs in between are static and the expressions are analysed the same
+// as a binding.
+fn analyse_interpolation(
+	value: &str,
+	selectors: &mut Selectors,
+	config: &Config,
+	attribute_type_designation: &str,
+) {
+	let interpolations = find_interpolations(value);
+
+	for (start, end) in get_static_ranges(value, &interpolations) {
+		super::analyse_string_of_tokens(
+			&mut value[start..end].to_string(),
+			selectors,
+			attribute_type_designation,
+			Some(match attribute_type_designation {
+				"id" => SelectorUsage::MarkupId,
+				"part" => SelectorUsage::MarkupPart,
+				_ => SelectorUsage::MarkupClass,
+			}),
+		);
+	}
+
+	for (start, end) in interpolations {
+		analyse_binding(
+			&mut value[start + 1..end - 1].to_string(),
+			selectors,
+			config,
+			attribute_type_designation,
+		);
+	}
+}
+
+// Rewrite quoted attribute value with expressions in braces.
+fn rewrite_interpolation(
+	value: &str,
+	selectors: &Selectors,
+	config: &Config,
+	attribute_type_designation: &str,
+) -> String {
+	let interpolations = find_interpolations(value);
+	let mut replacements: Vec<Replacement> = vec![];
+
+	for (start, end) in get_static_ranges(value, &interpolations) {
+		let mut names = value[start..end].to_string();
+		super::rewrite_string_of_tokens(&mut names, selectors, attribute_type_designation);
+		replacements.push(Replacement {
+			start,
+			end,
+			value: names,
+		});
+	}
+
+	for (start, end) in interpolations {
+		let mut expression = value[start + 1..end - 1].to_string();
+		rewrite_binding(
+			&mut expression,
+			selectors,
+			config,
+			attribute_type_designation,
+		);
+		replacements.push(Replacement {
+			start: start + 1,
+			end: end - 1,
+			value: expression,
+		});
+	}
+
+	let mut rewritten = value.to_string();
+	super::splice_replacements(&mut rewritten, replacements);
+	rewritten
+}
+
+// Get ranges of the static parts of an attribute value either side of its
+// expressions. Any class or ID joined to an expression (i.e. `btn-{size}`)
+// is left out, as only part of it is known.
+fn get_static_ranges(
+	value: &str,
+	interpolations: &[(usize, usize)],
+) -> Vec<(usize, usize)> {
+	let mut ranges = vec![];
+	let mut offset: usize = 0;
+
+	for index in 0..=interpolations.len() {
+		let part_end = interpolations
+			.get(index)
+			.map_or(value.len(), |(start, _)| *start);
+		let part = &value[offset..part_end];
+
+		let is_whitespace = |character: char| character.is_ascii_whitespace();
+		let start = match index {
+			0 => 0,
+			_ => part.find(is_whitespace).unwrap_or(part.len()),
+		};
+		let end = match index == interpolations.len() {
+			true => part.len(),
+			false => part.rfind(is_whitespace).map_or(0, |end| end + 1),
+		};
+
+		if start < end {
+			ranges.push((offset + start, offset + end));
+		}
+
+		if let Some((_, interpolation_end)) = interpolations.get(index) {
+			offset = *interpolation_end;
+		}
+	}
+
+	ranges
+}
+
+// Work out if attribute is a framework directive that may contain
+// classes, IDs, etc. With `expressions`, quoted values can have expressions
+// in braces as well.
+fn get_directive(
+	file_string: &str,
+	attribute: &Attribute,
+	expressions: bool,
+) -> Option<Directive> {
+	let name = &file_string[attribute.name_start..attribute.name_end];
+
+	// Range of expression, without any braces.
+	let (start, end) = match attribute.value {
+		Some((start, end)) if attribute.expression => (start + 1, end - 1),
+		Some(value) => value,
+		None => (attribute.name_end, attribute.name_end),
+	};
+
+	// Svelte class directive, or Astro class list.
+	if let Some(class) = name.strip_prefix("class:") {
+		return match class {
+			"list" if attribute.value.is_some() => {
+				Some(Directive::Binding(String::from("class"), start, end))
+			},
+			"list" => None,
+			_ => {
+				Some(Directive::ClassToggle(
+					attribute.name_start + 6,
+					attribute.name_end,
+				))
+			},
+		};
+	}
+
+	// Angular class binding, i.e. `[class.active]`.
+	if let Some(class) = name
+		.strip_prefix("[class.")
+		.and_then(|class| class.strip_suffix(']'))
+	{
+		attribute.value?;
+
+		return Some(Directive::ClassToggle(
+			attribute.name_start + 7,
+			attribute.name_start + 7 + class.len(),
+		));
+	}
+
+	let bound_name = if let Some(event) = name
+		.strip_prefix("v-on:")
+		.or_else(|| name.strip_prefix("x-on:"))
+		.or_else(|| name.strip_prefix('@'))
+		.or_else(|| name.strip_prefix("on:"))
+		.or_else(|| {
+			name.strip_prefix('(')
+				.and_then(|event| event.strip_suffix(')'))
+		}) {
+		format!("on{event}")
+	} else if let Some(bound_name) = name
+		.strip_prefix("v-bind:")
+		.or_else(|| name.strip_prefix("x-bind:"))
+		.or_else(|| name.strip_prefix(':'))
+	{
+		bound_name.to_string()
+	} else if let Some(bound_name) = name
+		.strip_prefix('[')
+		.and_then(|bound_name| bound_name.strip_suffix(']'))
+	{
+		// Angular property binding, i.e. `[id]`, `[attr.aria-owns]` or
+		// `[ngClass]`.
+		match bound_name.strip_prefix("attr.").unwrap_or(bound_name) {
+			"ngClass" => String::from("class"),
+			bound_name => bound_name.to_string(),
+		}
+	} else if attribute.expression {
+		name.to_string()
+	} else if expressions
+		&& attribute
+			.value
+			.is_some_and(|(start, end)| !find_interpolations(&file_string[start..end]).is_empty())
+	{
+		return Some(Directive::Interpolation(attribute.name.clone(), start, end));
+	} else {
+		return None;
+	};
+
+	attribute.value?;
+
+	// Leave off any modifiers, i.e. `@click.prevent` or `on:click|once`.
+	let bound_name = bound_name
+		.split(['.', '|'])
+		.next()
+		.unwrap_or("")
+		.to_ascii_lowercase();
+
+	Some(Directive::Binding(bound_name, start, end))
+}
+
+// Get value of an expression that is only a string literal.
+fn get_single_literal<'a>(
+	expression: &'a str,
+	tokens: &[lexer::Token],
+) -> Option<&'a str> {
+	match tokens {
+		[token]
+			if matches!(token.kind, TokenKind::String | TokenKind::Template)
+				&& token.is_closed(expression) =>
+		{
+			let text = token.text(expression);
+			Some(&text[1..text.len() - 1])
+		},
+		_ => None,
+	}
+}
+
+// Find end of Astro frontmatter (fenced by three hyphens at the start
+// of the file), or zero if there is none.
+fn get_frontmatter_end(file_string: &str) -> usize {
+	let fence_start = file_string.len() - file_string.trim_start().len();
+
+	if !file_string[fence_start..].starts_with("---") {
+		return 0;
+	}
+
+	match file_string[fence_start + 3..].find("\n---") {
+		Some(offset) => fence_start + 3 + offset + 4,
+		None => 0,
+	}
+}
@@ -0,0 +1,300 @@
+use minify_selectors_utils::*;
+
+use crate::pragmas::*;
+use crate::script::*;
+use crate::style::*;
+use crate::Replacement;
+
+
+
+
+// End-to-end test functions that take a selector string, along with the
+// position of the argument. Covers Cypress, Playwright, WebdriverIO and
+// Puppeteer.
+//
+// Names with a leading period are methods that can be called on any object,
+// otherwise the object needs to match as well (e.g. `page.click`).
+const SELECTOR_FUNCTIONS: [(&str, usize); 29] = [
+	// Cypress
+	("cy.get", 0),
+	// Playwright and Puppeteer
+	(".locator", 0),
+	(".waitForSelector", 0),
+	(".$eval", 0),
+	(".$$eval", 0),
+	("page.click", 0),
+	("page.dblclick", 0),
+	("page.tap", 0),
+	("page.hover", 0),
+	("page.focus", 0),
+	("page.fill", 0),
+	("page.type", 0),
+	("page.press", 0),
+	("page.check", 0),
+	("page.uncheck", 0),
+	("page.select", 0),
+	("page.selectOption", 0),
+	("page.setInputFiles", 0),
+	("page.dispatchEvent", 0),
+	("page.textContent", 0),
+	("page.innerText", 0),
+	("page.innerHTML", 0),
+	("page.getAttribute", 0),
+	("page.isVisible", 0),
+	("page.isHidden", 0),
+	("page.isChecked", 0),
+	("page.isEnabled", 0),
+	// WebdriverIO and Puppeteer
+	("$", 0),
+	("$$", 0),
+];
+
+// Cypress commands that query from the previous subject, which are only
+// rewritten when chained off `cy` (i.e. `cy.get('.list').find('.item')`),
+// as arrays and other libraries use the same method names.
+const CYPRESS_CHILD_COMMANDS: [&str; 10] = [
+	".find",
+	".children",
+	".parent",
+	".parents",
+	".parentsUntil",
+	".siblings",
+	".next",
+	".prev",
+	".filter",
+	".not",
+];
+
+// Assertions on classes and IDs of an element.
+const ASSERTION_FUNCTIONS: [&str; 6] = [
+	".toHaveClass",
+	".toHaveElementClass",
+	".toHaveId",
+	".should",
+	".and",
+	".contains",
+];
+
+// Selector engines and prefixes (Playwright, WebdriverIO and Puppeteer)
+// which are followed by a CSS selector, any others (e.g. `text=` or
+// `xpath/`) are left as is.
+const CSS_ENGINES: [&str; 3] = ["css=", "css:light=", "pierce/"];
+
+
+
+
+/// Rewrite end-to-end test file (Cypress, Playwright, WebdriverIO or
+/// Puppeteer) using selectors found in the rest of the files.
+///
+/// Notes:
+///  - Test files are not analysed, so selectors that are only used in tests are
+///    not added to the selectors map and are left as is.
+///  - Rest of the test file is rewritten as JS, i.e. scripts that run in the
+///    page like `page.evaluate(() => document.querySelector(…))`.
+pub fn rewrite_e2e(
+	file_string: &mut String,
+	selectors: &Selectors,
+	config: &Config,
+) {
+	if let Some((mut masked, regions)) = mask_disabled_regions(file_string, CommentSyntax::Script) {
+		rewrite_e2e(&mut masked, selectors, config);
+		*file_string = unmask_disabled_regions(&masked, &regions, CommentSyntax::Script);
+		return;
+	}
+
+	rewrite_e2e_selectors(file_string, selectors, config);
+	rewrite_e2e_assertions(file_string, selectors, config);
+
+	// jQuery functions are left off, as some of them are the same as the
+	// test functions (e.g. `$` and `.find`) which have already been rewritten.
+	rewrite_js(
+		file_string,
+		selectors,
+		&Config {
+			jquery: false,
+			..config.clone()
+		},
+	);
+}
+
+/// Rewrite selector strings passed to end-to-end test functions.
+pub fn rewrite_e2e_selectors(
+	file_string: &mut String,
+	selectors: &Selectors,
+	config: &Config,
+) {
+	let tokens = tokenize_js(file_string);
+	let names: Vec<&str> = SELECTOR_FUNCTIONS
+		.iter()
+		.map(|function| function.0)
+		.chain(CYPRESS_CHILD_COMMANDS)
+		.collect();
+	let mut replacements: Vec<Replacement> = vec![];
+
+	for (index, function, arguments) in find_indexed_function_calls(file_string, &tokens, &names) {
+		let position = match SELECTOR_FUNCTIONS
+			.iter()
+			.find(|selector_function| selector_function.0 == function)
+		{
+			Some((_, position)) => *position,
+			None if get_call_chain(file_string, &tokens, index).last() == Some(&"cy") => 0,
+			None => continue,
+		};
+
+		if let Some(argument) = arguments
+			.get(position)
+			.filter(|argument| argument.kind != ArgumentKind::Expression)
+		{
+			// Remove any additional backslash in JS selector strings.
+			let selector = unescape_js_chars(argument.value(file_string)).replace("\\\\", "\\");
+			let rewritten = rewrite_e2e_selector(&selector, selectors, config);
+			replace_value(
+				&mut replacements,
+				file_string,
+				argument,
+				&selector,
+				rewritten,
+			);
+		}
+	}
+
+	super::splice_replacements(file_string, replacements);
+}
+
+/// Rewrite classes, IDs and selectors in end-to-end test assertions, i.e.
+/// `.should('have.class', …)` (Cypress) or `.toHaveClass(…)` (Playwright).
+pub fn rewrite_e2e_assertions(
+	file_string: &mut String,
+	selectors: &Selectors,
+	config: &Config,
+) {
+	let tokens = tokenize_js(file_string);
+	let mut replacements: Vec<Replacement> = vec![];
+
+	for (index, function, arguments) in
+		find_indexed_function_calls(file_string, &tokens, &ASSERTION_FUNCTIONS)
+	{
+		let chain = get_call_chain(file_string, &tokens, index);
+
+		// Work out which arguments are classes, IDs or a selector string.
+		let (context, arguments): (&str, Vec<Argument>) = match function {
+			".toHaveClass" | ".toHaveElementClass" => ("class", arguments),
+			".toHaveId" => ("id", arguments.into_iter().take(1).collect()),
+
+			// Takes a chainer and the value to assert, i.e.
+			// `.should('have.class', 'active')`, which can be followed by
+			// more with `.and()`.
+			".should" | ".and" if function == ".should" || chain.contains(&"should") => {
+				let Some(chainer) = arguments.first().filter(|argument| argument.is_literal())
+				else {
+					continue;
+				};
+				let context = match chainer.value(file_string).trim_start_matches("not.") {
+					"have.class" => "class",
+					"have.id" => "id",
+					"match" => "selector",
+					_ => continue,
+				};
+
+				(context, arguments.into_iter().skip(1).take(1).collect())
+			},
+
+			// Takes a selector and content, but only content if there is
+			// a single argument, i.e. `cy.contains('.button', 'Save')`.
+			".contains" if chain.last() == Some(&"cy") => {
+				if !arguments
+					.get(1)
+					.is_some_and(|argument| argument.is_literal())
+				{
+					continue;
+				}
+
+				("selector", arguments.into_iter().take(1).collect())
+			},
+
+			_ => continue,
+		};
+
+		for argument in arguments.iter().filter(|argument| argument.is_literal()) {
+			let original = unescape_js_chars(argument.value(file_string));
+			let mut value = original.clone();
+
+			match context {
+				"selector" => value = rewrite_e2e_selector(&value, selectors, config),
+				_ => super::rewrite_string_of_tokens(&mut value, selectors, context),
+			}
+
+			replace_value(&mut replacements, file_string, argument, &original, value);
+		}
+	}
+
+	super::splice_replacements(file_string, replacements);
+}
+
+// Rewrite selector string for an end-to-end test function.
+//
+// Notes:
+//  - Playwright selectors may be chained with `>>`, each part is rewritten
+//    separately.
+//  - Parts using a selector engine other than CSS (e.g. `text=Save`,
+//    `xpath=//div` or `aria/Save`), XPath expressions and WebdriverIO text
+//    selectors (e.g. `=Save` or `*=Save`) are left as is, as are text selectors
+//    that are quoted.
+fn rewrite_e2e_selector(
+	selector: &str,
+	selectors: &Selectors,
+	config: &Config,
+) -> String {
+	selector
+		.split(">>")
+		.map(|part| {
+			let trimmed = part.trim_start();
+
+			if let Some(engine) = CSS_ENGINES
+				.iter()
+				.find(|engine| trimmed.starts_with(*engine))
+			{
+				let leading = &part[..part.len() - trimmed.len() + engine.len()];
+				let mut css = part[leading.len()..].to_string();
+				rewrite_css(&mut css, selectors, config);
+				return format!("{leading}{css}");
+			}
+
+			if let Some(id) = trimmed.strip_prefix("id=") {
+				let mut id = id.to_string();
+				super::rewrite_string_of_tokens(&mut id, selectors, "id");
+				return format!(
+					"{leading}id={id}",
+					leading = &part[..part.len() - trimmed.len()]
+				);
+			}
+
+			if is_css_selector(trimmed) {
+				let mut css = part.to_string();
+				rewrite_css(&mut css, selectors, config);
+				return css;
+			}
+
+			part.to_string()
+		})
+		.collect::<Vec<String>>()
+		.join(">>")
+}
+
+// Check that selector is not using another selector engine or is an
+// XPath expression, text selector, etc.
+fn is_css_selector(selector: &str) -> bool {
+	if selector.starts_with(['/', '(', '=', '*', '"', '\'', '~', '<']) || selector.starts_with("..")
+	{
+		return false;
+	}
+
+	// Selector engine, i.e. `text=` or `data-testid=` (Playwright)
+	// or `text/` (Puppeteer).
+	!matches!(
+		selector.find(['=', '/']),
+		Some(index) if selector[..index]
+			.chars()
+			.all(|character| character.is_ascii_alphanumeric() || matches!(character, '-' | '_' | ':'))
+	)
+}
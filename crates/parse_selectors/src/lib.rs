@@ -1,13 +1,26 @@
+pub mod component;
+pub mod e2e;
 pub mod markup;
+pub mod pragmas;
 pub mod regexes;
 pub mod script;
 pub mod style;
+pub mod suggest;
+pub mod template;
+pub mod xpath;
 
+use std::collections::BTreeMap;
+
+use component::*;
+use e2e::*;
 use markup::*;
 use minify_selectors_utils::*;
 use onig::*;
 use script::*;
 use style::*;
+use suggest::*;
+use template::*;
+use xpath::*;
 
 
 
@@ -17,7 +30,7 @@ pub fn read_from_css(
 	selectors: &mut Selectors,
 	config: &Config,
 ) {
-	markup::html_attributes::init(&config.custom_attributes);
+	init(config);
 	analyse_css(file_string, selectors, config);
 }
 
@@ -34,8 +47,12 @@ pub fn read_from_html(
 	selectors: &mut Selectors,
 	config: &Config,
 ) {
-	markup::html_attributes::init(&config.custom_attributes);
+	init(config);
 	analyse_html(file_string, selectors, config, None);
+
+	if markup::presets::uses_directives(&config.presets) {
+		analyse_component_directives(file_string, selectors, config, false);
+	}
 }
 
 pub fn write_to_html(
@@ -44,6 +61,10 @@ pub fn write_to_html(
 	config: &Config,
 ) {
 	rewrite_html(file_string, selectors, config);
+
+	if markup::presets::uses_directives(&config.presets) {
+		rewrite_component_directives(file_string, selectors, config, false);
+	}
 }
 
 pub fn read_from_js(
@@ -51,7 +72,7 @@ pub fn read_from_js(
 	selectors: &mut Selectors,
 	config: &Config,
 ) {
-	markup::html_attributes::init(&config.custom_attributes);
+	init(config);
 	analyse_js(file_string, selectors, config);
 }
 
@@ -63,6 +84,93 @@ pub fn write_to_js(
 	rewrite_js(file_string, selectors, config);
 }
 
+pub fn read_from_component(
+	file_string: &mut str,
+	selectors: &mut Selectors,
+	config: &Config,
+) {
+	init(config);
+	analyse_component(file_string, selectors, config);
+}
+
+pub fn write_to_component(
+	file_string: &mut String,
+	selectors: &Selectors,
+	config: &Config,
+) {
+	rewrite_component(file_string, selectors, config);
+}
+
+/// Read from server-side template, returning any dynamic class and ID
+/// values that are left as is.
+pub fn read_from_template(
+	file_string: &mut str,
+	selectors: &mut Selectors,
+	config: &Config,
+	dialect: &str,
+) -> Vec<String> {
+	init(config);
+	analyse_template(file_string, selectors, config, dialect)
+}
+
+pub fn write_to_template(
+	file_string: &mut String,
+	selectors: &Selectors,
+	config: &Config,
+	dialect: &str,
+) {
+	rewrite_template(file_string, selectors, config, dialect);
+}
+
+/// Read values of attributes in markup that are not processed, to suggest
+/// custom attributes from once all files have been read.
+pub fn read_attribute_values(
+	file_string: &str,
+	file_path: &str,
+	values: &mut BTreeMap<String, Vec<AttributeValue>>,
+	config: &Config,
+) {
+	init(config);
+	collect_attribute_values(file_string, file_path, values);
+}
+
+/// Read values of JSX element attributes that are not processed, same as
+/// `read_attribute_values`.
+pub fn read_jsx_attribute_values(
+	file_string: &str,
+	file_path: &str,
+	values: &mut BTreeMap<String, Vec<AttributeValue>>,
+	config: &Config,
+) {
+	init(config);
+	collect_jsx_attribute_values(file_string, file_path, values);
+}
+
+/// Write to end-to-end test file, there is no reading from them as only
+/// selectors found in the rest of the files are rewritten.
+pub fn write_to_e2e(
+	file_string: &mut String,
+	selectors: &Selectors,
+	config: &Config,
+) {
+	rewrite_e2e(file_string, selectors, config);
+}
+
+// Set up the attribute rules and regexes that depend on the config. These
+// are only set once, so the config of the first file read applies to all
+// the files that follow.
+fn init(config: &Config) {
+	markup::html_attributes::init(
+		&config.custom_attributes,
+		&config.presets,
+		&config.spec_level,
+	);
+	markup::json::init(&config.json_attributes);
+	markup::script_types::init(&config.custom_script_types);
+	regexes::init(&config.markers);
+	style::regexes::init(&config.markers);
+}
+
 
 
 
@@ -71,37 +179,83 @@ pub fn add_selector_to_map(
 	selectors: &mut Selectors,
 	usage: Option<SelectorUsage>,
 ) {
+	// Selector is partly made up of a template tag.
+	if contains_placeholder(selector) {
+		return;
+	}
+
 	selectors.add(selector.to_owned(), usage);
 }
 
 /// Fetch replacement encoded selector from selectors hashmap.
-// In the event selector has been removed from the mapping because
-// it is deemed not worth encoding, leave it as is by returning
-// the selector right back.
+///
+/// Notes:
+///  - Returns `None` if the selector is not worth encoding or is not in the map
+///    at all (e.g. only used in end-to-end test files, which are not analysed),
+///    so that it is left as is.
 pub fn get_encoded_selector(
 	selector: &str,
 	selectors: &Selectors,
 ) -> Option<String> {
-	if let Some(encoded_selector) = selectors.map.get(selector) {
-		if encoded_selector.replacement.is_some() {
-			encoded_selector.replacement.clone()
-		} else {
-			None
+	if contains_placeholder(selector) {
+		return None;
+	}
+
+	selectors
+		.map
+		.get(selector)
+		.and_then(|encoded_selector| encoded_selector.replacement.clone())
+}
+
+/// Replacement for a range of bytes in a file.
+pub struct Replacement {
+	pub start: usize,
+	pub end: usize,
+	pub value: String,
+}
+
+/// Splice in replacements, the rest of the file is left untouched.
+// Replacements that overlap an earlier one are dropped.
+pub fn splice_replacements(
+	file_string: &mut String,
+	mut replacements: Vec<Replacement>,
+) {
+	if replacements.is_empty() {
+		return;
+	}
+
+	replacements.sort_by_key(|replacement| replacement.start);
+
+	let mut spliced = String::with_capacity(file_string.len());
+	let mut position: usize = 0;
+
+	for replacement in replacements {
+		if replacement.start < position {
+			continue;
 		}
-	} else {
-		Some(selector.to_owned())
+
+		spliced.push_str(&file_string[position..replacement.start]);
+		spliced.push_str(&replacement.value);
+		position = replacement.end;
 	}
+
+	spliced.push_str(&file_string[position..]);
+	*file_string = spliced;
 }
 
 /// Returns an iterator of function arguments.
-pub fn get_function_arguments(string: &str) -> FindCaptures {
+pub fn get_function_arguments(string: &str) -> FindCaptures<'_, '_> {
 	regexes::STRING_DELIMITED_BY_COMMA.captures_iter(string)
 }
 
 /// Checks if a (minify-selector specific) prefixed selector
 /// is used in the given string snippet.
 pub fn is_prefixed_selector(string: &str) -> bool {
-	regexes::PREFIXED_SELECTORS.find(string).is_some()
+	regexes::PREFIXED_SELECTORS
+		.get()
+		.unwrap()
+		.find(string)
+		.is_some()
 }
 
 
@@ -112,7 +266,9 @@ pub fn analyse_prefixed_selectors(
 	file_string: &mut str,
 	selectors: &mut Selectors,
 ) {
-	for capture in regexes::PREFIXED_SELECTORS.captures_iter(file_string) {
+	let prefixed_selectors = regexes::PREFIXED_SELECTORS.get().unwrap();
+
+	for capture in prefixed_selectors.captures_iter(file_string) {
 		// "#__ignore--foo", ".__ignore--bar" or "__ignore--baz"
 		// Note: no need to add a selector that has been marked as ignore
 		// to selectors map.
@@ -146,7 +302,9 @@ pub fn rewrite_prefixed_selectors(
 	file_string: &mut String,
 	selectors: &Selectors,
 ) {
-	*file_string = regexes::PREFIXED_SELECTORS.replace_all(file_string, |capture: &Captures| {
+	let prefixed_selectors = regexes::PREFIXED_SELECTORS.get().unwrap();
+
+	*file_string = prefixed_selectors.replace_all(file_string, |capture: &Captures| {
 		let mut identifier = unescape_css_chars(capture.at(3).unwrap().trim());
 
 		match capture.at(2) {
@@ -207,6 +365,7 @@ pub fn analyse_string_of_tokens(
 	let prefix: &str = match context {
 		"class" => ".",
 		"id" => "#",
+		"part" => "::",
 		_ => "",
 	};
 
@@ -257,6 +416,7 @@ pub fn rewrite_string_of_tokens(
 	let prefix: &str = match context {
 		"class" => ".",
 		"id" => "#",
+		"part" => "::",
 		_ => "",
 	};
 
@@ -311,6 +471,7 @@ pub fn analyse_string_of_arguments(
 	let prefix: &str = match context {
 		"class" => ".",
 		"id" => "#",
+		"part" => "::",
 		_ => "",
 	};
 
@@ -349,6 +510,7 @@ pub fn rewrite_string_of_arguments(
 	let prefix: &str = match context {
 		"class" => ".",
 		"id" => "#",
+		"part" => "::",
 		_ => "",
 	};
 
@@ -410,7 +572,9 @@ pub fn analyse_anchor_links(
 		string.remove(0);
 	}
 
-	for capture in regexes::INTERNAL_ANCHOR_TARGET_ID.captures_iter(string) {
+	let anchor_target_id = regexes::INTERNAL_ANCHOR_TARGET_ID.get().unwrap();
+
+	for capture in anchor_target_id.captures_iter(string) {
 		if capture.at(1).is_none() {
 			continue;
 		}
@@ -442,20 +606,19 @@ pub fn rewrite_anchor_links(
 		string.remove(0);
 	}
 
+	let anchor_target_id = regexes::INTERNAL_ANCHOR_TARGET_ID.get().unwrap();
+
 	*string = format!(
 		"{quote}{url}{quote}",
-		url = regexes::INTERNAL_ANCHOR_TARGET_ID.replace(string, |capture: &Captures| {
+		url = anchor_target_id.replace(string, |capture: &Captures| {
 			if capture.at(1).is_none() {
 				return capture.at(0).unwrap().to_string();
 			}
 
-			format!(
-				"{url}#{target_id}",
-				url = capture.at(1).unwrap_or(""),
-				target_id =
-					get_encoded_selector(&unescape_js_chars(capture.at(2).unwrap()), selectors,)
-						.unwrap_or_else(|| capture.at(2).unwrap().to_string()),
-			)
+			match get_encoded_selector(&unescape_js_chars(capture.at(2).unwrap()), selectors) {
+				Some(target_id) => format!("{url}#{target_id}", url = capture.at(1).unwrap_or("")),
+				None => capture.at(0).unwrap().to_string(),
+			}
 		}),
 		quote = quote_type,
 	);
@@ -1,144 +1,455 @@
+use std::borrow::Cow;
 use std::collections::HashMap;
 
 use once_cell::sync::OnceCell;
 
+use self::SpecLevel::*;
+use crate::markup::presets::get_preset_attributes;
+use crate::markup::unescape_html_chars;
+
 
 
 
 // HTML attributes which its values will contains parsable values
 pub static WHITELIST: OnceCell<HashMap<String, String>> = OnceCell::new();
 
-pub fn init(custom_attributes: &Vec<(String, String)>) {
+// Custom attribute rules that are limited to certain elements or have a
+// wildcard in the name (i.e. `button[data-target]` or `data-*-target`).
+pub static SCOPED_RULES: OnceCell<Vec<AttributeRule>> = OnceCell::new();
+
+/// Custom attribute rule, the attribute name may contain wildcards (`*`).
+#[derive(Debug)]
+pub struct AttributeRule {
+	pub element: ElementFilter,
+	pub name: String,
+	pub kind: String,
+}
+
+/// Simple CSS-like element filter, i.e. `a.nav[data-toggle=tab]`.
+#[derive(Debug, Default)]
+pub struct ElementFilter {
+	pub tag: Option<String>,
+	pub id: Option<String>,
+	pub classes: Vec<String>,
+	pub attributes: Vec<(String, Option<String>)>,
+}
+
+/// Element an attribute is on, with its tag name and the values of its
+/// other attributes.
+pub struct Element<'a> {
+	pub tag: &'a str,
+	pub attributes: Vec<(&'a str, Cow<'a, str>)>,
+	// Values are as written in markup, which are only unescaped when a rule
+	// needs them.
+	pub is_escaped: bool,
+}
+
+impl Element<'_> {
+	/// Get (unescaped) value of an attribute.
+	pub fn get_attribute(
+		&self,
+		name: &str,
+	) -> Option<Cow<'_, str>> {
+		let (_, value) = self
+			.attributes
+			.iter()
+			.find(|(attribute, _)| *attribute == name)?;
+
+		Some(match self.is_escaped {
+			true => Cow::Owned(unescape_html_chars(value)),
+			false => Cow::Borrowed(value),
+		})
+	}
+}
+
+/// Level of the specs (HTML, SVG and WAI-ARIA) an attribute was added in,
+/// attributes above the chosen level are left as is.
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub enum SpecLevel {
+	// HTML5, SVG 1.1 and WAI-ARIA 1.0.
+	Html5,
+	// HTML Living Standard, SVG 2 and WAI-ARIA 1.2.
+	Living,
+	// Proposals that have shipped behind flags or in some browsers.
+	Experimental,
+}
+
+impl SpecLevel {
+	/// Get spec level from its name in the config, which has already been
+	/// checked to be one of `html5`, `living` or `experimental`.
+	pub fn from_name(name: &str) -> Self {
+		match name {
+			"html5" => Html5,
+			"experimental" => Experimental,
+			_ => Living,
+		}
+	}
+}
+
+// Standard attributes that are limited to certain elements, as the same
+// names are used by custom elements and frameworks for other things.
+const STANDARD_SCOPED_RULES: [(&str, &str); 10] = [
+	// Timing (SVG animations)
+	("animate[begin]", "timing"),
+	("animate[end]", "timing"),
+	("animateColor[begin]", "timing"),
+	("animateColor[end]", "timing"),
+	("animateMotion[begin]", "timing"),
+	("animateMotion[end]", "timing"),
+	("animateTransform[begin]", "timing"),
+	("animateTransform[end]", "timing"),
+	("set[begin]", "timing"),
+	("set[end]", "timing"),
+];
+
+pub fn init(
+	custom_attributes: &Vec<(String, String)>,
+	presets: &[String],
+	spec_level: &str,
+) {
+	let spec_level = SpecLevel::from_name(spec_level);
+	// Attributes from presets come first, so that custom attributes
+	// take precedence over them.
+	let preset_attributes: Vec<(String, String)> = presets
+		.iter()
+		.flat_map(|preset| get_preset_attributes(preset))
+		.map(|(attribute, kind)| (attribute.to_string(), kind.to_string()))
+		.collect();
+
 	#[rustfmt::skip]
 	WHITELIST.get_or_init(|| {
-		let standard_attributes: [(String, String); 114] = [
+		let standard_attributes: [(&str, &str, SpecLevel); 132] = [
 			// Class
-			(String::from("class"), String::from("class")),
+			("class", "class", Html5),
+			// Part (shadow parts)
+			("part", "part", Living),
+			("exportparts", "exportparts", Living),
 			// ID
-			(String::from("id"), String::from("id")),
-			(String::from("aria-controls"), String::from("id")),
-			(String::from("aria-describedby"), String::from("id")),
-			(String::from("aria-labelledby"), String::from("id")),
-			(String::from("for"), String::from("id")),
-			(String::from("form"), String::from("id")),
-			(String::from("headers"), String::from("id")),
-			(String::from("itemref"), String::from("id")),
-			(String::from("list"), String::from("id")),
-			// Anchor
-			(String::from("href"), String::from("anchor")),
-			(String::from("xlink:href"), String::from("anchor")),
-			// Style
-			(String::from("fill"), String::from("style")),
-			(String::from("style"), String::from("style")),
+			("id", "id", Html5),
+			("aria-controls", "id", Html5),
+			("aria-describedby", "id", Html5),
+			("aria-labelledby", "id", Html5),
+			("for", "id", Html5),
+			("form", "id", Html5),
+			("headers", "id", Html5),
+			("itemref", "id", Html5),
+			("list", "id", Html5),
+			("aria-activedescendant", "id", Html5),
+			("aria-flowto", "id", Html5),
+			("aria-owns", "id", Html5),
+			("aria-details", "id", Living),
+			("aria-errormessage", "id", Living),
+			("commandfor", "id", Living),
+			("popovertarget", "id", Living),
+			("anchor", "id", Experimental),
+			("invoketarget", "id", Experimental),
+			// Anchor (including `href` on any SVG element, i.e. `<use href="#foo">`)
+			("href", "anchor", Html5),
+			("xlink:href", "anchor", Html5),
+			// Markup
+			("srcdoc", "markup", Html5),
+			// Style (including SVG presentation attributes that take `url()`)
+			("clip-path", "style", Html5),
+			("cursor", "style", Html5),
+			("fill", "style", Html5),
+			("filter", "style", Html5),
+			("marker-end", "style", Html5),
+			("marker-mid", "style", Html5),
+			("marker-start", "style", Html5),
+			("mask", "style", Html5),
+			("stroke", "style", Html5),
+			("style", "style", Html5),
 			// Script
-			(String::from("onabort"), String::from("script")),
-			(String::from("onactivate"), String::from("script")),
-			(String::from("onafterprint"), String::from("script")),
-			(String::from("onauxclick"), String::from("script")),
-			(String::from("onbeforeinput"), String::from("script")),
-			(String::from("onbeforematch"), String::from("script")),
-			(String::from("onbeforeprint"), String::from("script")),
-			(String::from("onbeforeunload"), String::from("script")),
-			(String::from("onbegin"), String::from("script")),
-			(String::from("onblur"), String::from("script")),
-			(String::from("oncancel"), String::from("script")),
-			(String::from("oncanplay"), String::from("script")),
-			(String::from("oncanplaythrough"), String::from("script")),
-			(String::from("onchange"), String::from("script")),
-			(String::from("onclick"), String::from("script")),
-			(String::from("onclose"), String::from("script")),
-			(String::from("oncontextextlost"), String::from("script")),
-			(String::from("oncontextextmenu"), String::from("script")),
-			(String::from("oncontextextrestored"), String::from("script")),
-			(String::from("oncopy"), String::from("script")),
-			(String::from("oncuechange"), String::from("script")),
-			(String::from("oncut"), String::from("script")),
-			(String::from("ondblclick"), String::from("script")),
-			(String::from("ondrag"), String::from("script")),
-			(String::from("ondragend"), String::from("script")),
-			(String::from("ondragenter"), String::from("script")),
-			(String::from("ondragexit"), String::from("script")),
-			(String::from("ondragleave"), String::from("script")),
-			(String::from("ondragover"), String::from("script")),
-			(String::from("ondragstart"), String::from("script")),
-			(String::from("ondrop"), String::from("script")),
-			(String::from("ondurationchange"), String::from("script")),
-			(String::from("onemptied"), String::from("script")),
-			(String::from("onend"), String::from("script")),
-			(String::from("onended"), String::from("script")),
-			(String::from("onerror"), String::from("script")),
-			(String::from("onfocus"), String::from("script")),
-			(String::from("onfocusin"), String::from("script")),
-			(String::from("onfocusout"), String::from("script")),
-			(String::from("onformdata"), String::from("script")),
-			(String::from("onhashchange"), String::from("script")),
-			(String::from("oninput"), String::from("script")),
-			(String::from("oninvalid"), String::from("script")),
-			(String::from("onkeydown"), String::from("script")),
-			(String::from("onkeypress"), String::from("script")),
-			(String::from("onkeyup"), String::from("script")),
-			(String::from("onlanguagechange"), String::from("script")),
-			(String::from("onload"), String::from("script")),
-			(String::from("onloadeddata"), String::from("script")),
-			(String::from("onloadedmetadata"), String::from("script")),
-			(String::from("onloadstart"), String::from("script")),
-			(String::from("onmessage"), String::from("script")),
-			(String::from("onmessageerror"), String::from("script")),
-			(String::from("onmousedown"), String::from("script")),
-			(String::from("onmouseenter"), String::from("script")),
-			(String::from("onmouseleave"), String::from("script")),
-			(String::from("onmousemove"), String::from("script")),
-			(String::from("onmouseout"), String::from("script")),
-			(String::from("onmouseover"), String::from("script")),
-			(String::from("onmouseup"), String::from("script")),
-			(String::from("onmousewheel"), String::from("script")),
-			(String::from("onoffline"), String::from("script")),
-			(String::from("ononline"), String::from("script")),
-			(String::from("onpagehide"), String::from("script")),
-			(String::from("onpageshow"), String::from("script")),
-			(String::from("onpaste"), String::from("script")),
-			(String::from("onpause"), String::from("script")),
-			(String::from("onplay"), String::from("script")),
-			(String::from("onplaying"), String::from("script")),
-			(String::from("onpopstate"), String::from("script")),
-			(String::from("onprogress"), String::from("script")),
-			(String::from("onratechange"), String::from("script")),
-			(String::from("onrejectionhandled"), String::from("script")),
-			(String::from("onrepeat"), String::from("script")),
-			(String::from("onresize"), String::from("script")),
-			(String::from("onresize"), String::from("script")),
-			(String::from("onscroll"), String::from("script")),
-			(String::from("onscrollend"), String::from("script")),
-			(String::from("onsecuritypolicyviolation"), String::from("script")),
-			(String::from("onsearch"), String::from("script")),
-			(String::from("onseeked"), String::from("script")),
-			(String::from("onseeking"), String::from("script")),
-			(String::from("onselect"), String::from("script")),
-			(String::from("onslotchange"), String::from("script")),
-			(String::from("onshow"), String::from("script")),
-			(String::from("onstalled"), String::from("script")),
-			(String::from("onstorage"), String::from("script")),
-			(String::from("onsubmit"), String::from("script")),
-			(String::from("onsuspend"), String::from("script")),
-			(String::from("ontimeupdate"), String::from("script")),
-			(String::from("ontoggle"), String::from("script")),
-			(String::from("onunhandledrejection"), String::from("script")),
-			(String::from("onunload"), String::from("script")),
-			(String::from("onvolumechange"), String::from("script")),
-			(String::from("onwaiting"), String::from("script")),
-			(String::from("onwebkitanimationend"), String::from("script")),
-			(String::from("onwebkitanimationiteration"), String::from("script")),
-			(String::from("onwebkitanimationstart"), String::from("script")),
-			(String::from("onwebkitanimationend"), String::from("script")),
-			(String::from("onwheel"), String::from("script")),
+			("onabort", "script", Html5),
+			("onactivate", "script", Html5),
+			("onafterprint", "script", Html5),
+			("onauxclick", "script", Html5),
+			("onbeforeinput", "script", Html5),
+			("onbeforematch", "script", Html5),
+			("onbeforeprint", "script", Html5),
+			("onbeforeunload", "script", Html5),
+			("onbegin", "script", Html5),
+			("onblur", "script", Html5),
+			("oncancel", "script", Html5),
+			("oncanplay", "script", Html5),
+			("oncanplaythrough", "script", Html5),
+			("onchange", "script", Html5),
+			("onclick", "script", Html5),
+			("onclose", "script", Html5),
+			("oncontextextlost", "script", Html5),
+			("oncontextextmenu", "script", Html5),
+			("oncontextextrestored", "script", Html5),
+			("oncopy", "script", Html5),
+			("oncuechange", "script", Html5),
+			("oncut", "script", Html5),
+			("ondblclick", "script", Html5),
+			("ondrag", "script", Html5),
+			("ondragend", "script", Html5),
+			("ondragenter", "script", Html5),
+			("ondragexit", "script", Html5),
+			("ondragleave", "script", Html5),
+			("ondragover", "script", Html5),
+			("ondragstart", "script", Html5),
+			("ondrop", "script", Html5),
+			("ondurationchange", "script", Html5),
+			("onemptied", "script", Html5),
+			("onend", "script", Html5),
+			("onended", "script", Html5),
+			("onerror", "script", Html5),
+			("onfocus", "script", Html5),
+			("onfocusin", "script", Html5),
+			("onfocusout", "script", Html5),
+			("onformdata", "script", Html5),
+			("onhashchange", "script", Html5),
+			("oninput", "script", Html5),
+			("oninvalid", "script", Html5),
+			("onkeydown", "script", Html5),
+			("onkeypress", "script", Html5),
+			("onkeyup", "script", Html5),
+			("onlanguagechange", "script", Html5),
+			("onload", "script", Html5),
+			("onloadeddata", "script", Html5),
+			("onloadedmetadata", "script", Html5),
+			("onloadstart", "script", Html5),
+			("onmessage", "script", Html5),
+			("onmessageerror", "script", Html5),
+			("onmousedown", "script", Html5),
+			("onmouseenter", "script", Html5),
+			("onmouseleave", "script", Html5),
+			("onmousemove", "script", Html5),
+			("onmouseout", "script", Html5),
+			("onmouseover", "script", Html5),
+			("onmouseup", "script", Html5),
+			("onmousewheel", "script", Html5),
+			("onoffline", "script", Html5),
+			("ononline", "script", Html5),
+			("onpagehide", "script", Html5),
+			("onpageshow", "script", Html5),
+			("onpaste", "script", Html5),
+			("onpause", "script", Html5),
+			("onplay", "script", Html5),
+			("onplaying", "script", Html5),
+			("onpopstate", "script", Html5),
+			("onprogress", "script", Html5),
+			("onratechange", "script", Html5),
+			("onrejectionhandled", "script", Html5),
+			("onrepeat", "script", Html5),
+			("onresize", "script", Html5),
+			("onscroll", "script", Html5),
+			("onscrollend", "script", Html5),
+			("onsecuritypolicyviolation", "script", Html5),
+			("onsearch", "script", Html5),
+			("onseeked", "script", Html5),
+			("onseeking", "script", Html5),
+			("onselect", "script", Html5),
+			("onslotchange", "script", Html5),
+			("onshow", "script", Html5),
+			("onstalled", "script", Html5),
+			("onstorage", "script", Html5),
+			("onsubmit", "script", Html5),
+			("onsuspend", "script", Html5),
+			("ontimeupdate", "script", Html5),
+			("ontoggle", "script", Html5),
+			("onunhandledrejection", "script", Html5),
+			("onunload", "script", Html5),
+			("onvolumechange", "script", Html5),
+			("onwaiting", "script", Html5),
+			("onwebkitanimationend", "script", Html5),
+			("onwebkitanimationiteration", "script", Html5),
+			("onwebkitanimationstart", "script", Html5),
+			("onwheel", "script", Html5),
 		];
 
-		let mut attributes = HashMap::from(standard_attributes);
+		let mut attributes: HashMap<String, String> = standard_attributes
+			.iter()
+			.filter(|(_, _, level)| *level <= spec_level)
+			.map(|(attribute, kind, _)| (attribute.to_string(), kind.to_string()))
+			.collect();
 
-		for (attribute, kind) in custom_attributes {
-			attributes.insert(attribute.to_string(), kind.to_string());
+		for (attribute, kind) in preset_attributes.iter().chain(custom_attributes) {
+			if !is_scoped_rule(attribute) {
+				attributes.insert(attribute.to_string(), kind.to_string());
+			}
 		}
 
 		attributes
 	});
+
+	SCOPED_RULES.get_or_init(|| {
+		custom_attributes
+			.iter()
+			.chain(&preset_attributes)
+			.map(|(attribute, kind)| (attribute.as_str(), kind.as_str()))
+			.chain(STANDARD_SCOPED_RULES)
+			.filter(|(attribute, _)| is_scoped_rule(attribute))
+			.map(|(attribute, kind)| {
+				let (element, name) = parse_attribute_rule(attribute);
+
+				AttributeRule {
+					element,
+					name,
+					kind: kind.to_string(),
+				}
+			})
+			.collect()
+	});
+}
+
+/// Get designation of an attribute (i.e. `class`, `id` or `selector`).
+///
+/// Notes:
+///  - Rules limited to certain elements only apply if the element is known, and
+///    take precedence over the rest.
+///  - Names with wildcards only apply if there is no rule for the exact name.
+pub fn get_attribute_designation(
+	name: &str,
+	element: Option<&Element>,
+) -> Option<&'static String> {
+	let find_rule = |limited: bool| {
+		SCOPED_RULES
+			.get()
+			.unwrap()
+			.iter()
+			.find(|rule| {
+				rule.element.is_empty() != limited
+					&& matches_wildcard(&rule.name, name)
+					&& element.is_none_or(|element| rule.element.matches(element))
+			})
+			.map(|rule| &rule.kind)
+	};
+
+	element
+		.and_then(|_| find_rule(true))
+		.or_else(|| WHITELIST.get().unwrap().get(name))
+		.or_else(|| find_rule(false))
+}
+
+impl ElementFilter {
+	pub fn is_empty(&self) -> bool {
+		self.tag.is_none()
+			&& self.id.is_none()
+			&& self.classes.is_empty()
+			&& self.attributes.is_empty()
+	}
+
+	pub fn matches(
+		&self,
+		element: &Element,
+	) -> bool {
+		self.tag
+			.as_ref()
+			.is_none_or(|tag| tag == "*" || tag.eq_ignore_ascii_case(element.tag))
+			&& self
+				.id
+				.as_ref()
+				.is_none_or(|id| element.get_attribute("id").as_deref() == Some(id.as_str()))
+			&& self.classes.iter().all(|class| {
+				element
+					.get_attribute("class")
+					.is_some_and(|classes| classes.split_whitespace().any(|name| name == class))
+			}) && self.attributes.iter().all(|(name, value)| {
+			match (element.get_attribute(name), value) {
+				(Some(actual), Some(value)) => actual == value.as_str(),
+				(actual, None) => actual.is_some(),
+				(None, _) => false,
+			}
+		})
+	}
+}
+
+// Check custom attribute is limited to certain elements or has a wildcard.
+fn is_scoped_rule(attribute: &str) -> bool {
+	attribute.contains(['*', '['])
+}
+
+// Split custom attribute rule into an element filter and attribute name, the
+// attribute name is in the last pair of square brackets, i.e.
+// `button.primary[data-target]`.
+pub(crate) fn parse_attribute_rule(rule: &str) -> (ElementFilter, String) {
+	let rule = rule.trim();
+
+	let (filter, name) = match rule
+		.strip_suffix(']')
+		.and_then(|rest| rest.rsplit_once('['))
+	{
+		Some((filter, name)) => (filter, name.trim()),
+		None => ("", rule),
+	};
+
+	(parse_element_filter(filter), name.to_ascii_lowercase())
+}
+
+// Parse element filter made up of a tag name, ID, classes and attributes.
+fn parse_element_filter(filter: &str) -> ElementFilter {
+	let mut element = ElementFilter::default();
+	let mut rest = filter.trim();
+
+	let tag_length = rest.find(['.', '#', '[']).unwrap_or(rest.len());
+
+	if tag_length > 0 {
+		element.tag = Some(rest[..tag_length].to_ascii_lowercase());
+	}
+	rest = &rest[tag_length..];
+
+	while !rest.is_empty() {
+		if let Some(attribute) = rest.strip_prefix('[') {
+			let end = attribute.find(']').unwrap_or(attribute.len());
+			let (name, value) = match attribute[..end].split_once('=') {
+				Some((name, value)) => {
+					(
+						name,
+						Some(value.trim().trim_matches(['"', '\'']).to_string()),
+					)
+				},
+				None => (&attribute[..end], None),
+			};
+
+			element
+				.attributes
+				.push((name.trim().to_ascii_lowercase(), value));
+			rest = attribute.get(end + 1..).unwrap_or("");
+			continue;
+		}
+
+		let end = rest[1..]
+			.find(['.', '#', '['])
+			.map_or(rest.len(), |index| index + 1);
+
+		match &rest[..1] {
+			"." => element.classes.push(rest[1..end].to_string()),
+			_ => element.id = Some(rest[1..end].to_string()),
+		}
+		rest = &rest[end..];
+	}
+
+	element
+}
+
+// Check attribute name matches name with wildcards, i.e. `data-*-target`.
+pub(crate) fn matches_wildcard(
+	pattern: &str,
+	name: &str,
+) -> bool {
+	let mut parts = pattern.split('*');
+	let first = parts.next().unwrap_or("");
+
+	let Some(mut rest) = name.strip_prefix(first) else {
+		return false;
+	};
+
+	let parts: Vec<&str> = parts.collect();
+
+	match parts.split_last() {
+		None => rest.is_empty(),
+		Some((last, middle)) => {
+			for part in middle {
+				match rest.find(part) {
+					Some(index) => rest = &rest[index + part.len()..],
+					None => return false,
+				}
+			}
+
+			rest.len() >= last.len() && rest.ends_with(last)
+		},
+	}
 }
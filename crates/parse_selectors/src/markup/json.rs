@@ -0,0 +1,411 @@
+use minify_selectors_utils::*;
+use once_cell::sync::OnceCell;
+
+use crate::markup::html_attributes::matches_wildcard;
+use crate::markup::html_attributes::parse_attribute_rule;
+use crate::markup::html_attributes::Element;
+use crate::markup::html_attributes::ElementFilter;
+use crate::Replacement;
+
+
+
+
+// Fields of custom attributes with JSON values that contain classes, IDs,
+// selectors, etc. (i.e. `/target` of `data-options` is a selector).
+pub static JSON_RULES: OnceCell<Vec<JsonRule>> = OnceCell::new();
+
+/// Path of a field in a JSON-valued attribute and what it contains.
+#[derive(Debug)]
+pub struct JsonRule {
+	pub element: ElementFilter,
+	pub attribute: String,
+	pub path: Vec<String>,
+	pub kind: String,
+}
+
+pub fn init(json_attributes: &[(String, String, String)]) {
+	JSON_RULES.get_or_init(|| {
+		json_attributes
+			.iter()
+			.map(|(attribute, path, kind)| {
+				let (element, attribute) = parse_attribute_rule(attribute);
+
+				JsonRule {
+					element,
+					attribute,
+					path: parse_json_path(path),
+					kind: kind.to_string(),
+				}
+			})
+			.collect()
+	});
+}
+
+/// Analyse JSON value of an attribute.
+///
+/// Notes:
+///  - Only string fields with a rule are processed, values that are not valid
+///    JSON are left as is.
+pub fn analyse_json_attribute(
+	value: &str,
+	attribute: &str,
+	element: Option<&Element>,
+	selectors: &mut Selectors,
+	config: &Config,
+	usage: Option<SelectorUsage>,
+) {
+	for (start, end, kind) in find_json_fields(value, attribute, element) {
+		let Some(mut field) = decode_json_string(&value[start..end]) else {
+			continue;
+		};
+
+		match kind {
+			"id" | "class" | "part" => {
+				crate::analyse_string_of_tokens(
+					&mut field,
+					selectors,
+					kind,
+					usage.or(Some(match kind {
+						"id" => SelectorUsage::MarkupId,
+						"part" => SelectorUsage::MarkupPart,
+						_ => SelectorUsage::MarkupClass,
+					})),
+				);
+			},
+
+			"selector" => {
+				crate::analyse_css(&mut field, selectors, config);
+			},
+
+			"style" => {
+				crate::analyse_css_functions(&mut field, selectors);
+			},
+
+			"anchor" => {
+				crate::analyse_anchor_links(&mut field, selectors);
+			},
+
+			_ => continue,
+		}
+	}
+}
+
+/// Rewrite JSON value of an attribute, fields are rewritten in place so the
+/// rest of the value (whitespace, order of keys, etc.) is left as is.
+pub fn rewrite_json_attribute(
+	value: &mut String,
+	attribute: &str,
+	element: Option<&Element>,
+	selectors: &Selectors,
+	config: &Config,
+) {
+	let mut replacements: Vec<Replacement> = vec![];
+
+	for (start, end, kind) in find_json_fields(value, attribute, element) {
+		let Some(original) = decode_json_string(&value[start..end]) else {
+			continue;
+		};
+		let mut field = original.clone();
+
+		match kind {
+			"id" | "class" | "part" => {
+				crate::rewrite_string_of_tokens(&mut field, selectors, kind);
+			},
+
+			"selector" => {
+				crate::rewrite_css(&mut field, selectors, config);
+			},
+
+			"style" => {
+				crate::rewrite_css_functions(&mut field, selectors);
+			},
+
+			"anchor" => {
+				crate::rewrite_anchor_links(&mut field, selectors);
+			},
+
+			_ => continue,
+		}
+
+		if field != original {
+			replacements.push(Replacement {
+				start,
+				end,
+				value: encode_json_string(&field),
+			});
+		}
+	}
+
+	crate::splice_replacements(value, replacements);
+}
+
+// Find ranges of string fields (including the quotes) in a JSON value which
+// have a rule, along with what the field contains.
+fn find_json_fields(
+	value: &str,
+	attribute: &str,
+	element: Option<&Element>,
+) -> Vec<(usize, usize, &'static str)> {
+	let rules: Vec<&JsonRule> = JSON_RULES
+		.get()
+		.unwrap()
+		.iter()
+		.filter(|rule| {
+			matches_wildcard(&rule.attribute, attribute)
+				&& match element {
+					Some(element) => rule.element.matches(element),
+					None => rule.element.is_empty(),
+				}
+		})
+		.collect();
+
+	if rules.is_empty() {
+		return vec![];
+	}
+
+	let mut strings: Vec<(usize, usize, Vec<String>)> = vec![];
+	let mut scanner = JsonScanner {
+		value: value.as_bytes(),
+		index: 0,
+	};
+
+	// Not valid JSON, leave it as is.
+	if scanner.scan_value(&mut vec![], &mut strings).is_none() || !scanner.is_at_end() {
+		return vec![];
+	}
+
+	strings
+		.into_iter()
+		.filter_map(|(start, end, path)| {
+			rules
+				.iter()
+				.find(|rule| {
+					rule.path.len() == path.len()
+						&& rule
+							.path
+							.iter()
+							.zip(&path)
+							.all(|(segment, key)| segment == "*" || segment == key)
+				})
+				.map(|rule| (start, end, rule.kind.as_str()))
+		})
+		.collect()
+}
+
+// Split path of a field into keys, either a JSON pointer (i.e.
+// `/panels/*/target`) or dot notation (i.e. `panels[*].target`).
+fn parse_json_path(path: &str) -> Vec<String> {
+	let path = path.trim();
+
+	if path.is_empty() || path == "/" {
+		return vec![];
+	}
+
+	match path.strip_prefix('/') {
+		Some(pointer) => {
+			pointer
+				.split('/')
+				.map(|key| key.replace("~1", "/").replace("~0", "~"))
+				.collect()
+		},
+		None => {
+			path.replace('[', ".")
+				.replace(']', "")
+				.split('.')
+				.filter(|key| !key.is_empty())
+				.map(|key| key.trim_matches(['"', '\'']).to_string())
+				.collect()
+		},
+	}
+}
+
+// Minimal JSON scanner that records the range and path of string values.
+struct JsonScanner<'a> {
+	value: &'a [u8],
+	index: usize,
+}
+
+impl JsonScanner<'_> {
+	fn skip_whitespace(&mut self) {
+		while self
+			.value
+			.get(self.index)
+			.is_some_and(|byte| matches!(byte, b' ' | b'\t' | b'\n' | b'\r'))
+		{
+			self.index += 1;
+		}
+	}
+
+	fn is_at_end(&mut self) -> bool {
+		self.skip_whitespace();
+		self.index == self.value.len()
+	}
+
+	fn expect(
+		&mut self,
+		byte: u8,
+	) -> Option<()> {
+		self.skip_whitespace();
+
+		if self.value.get(self.index) == Some(&byte) {
+			self.index += 1;
+			Some(())
+		} else {
+			None
+		}
+	}
+
+	fn scan_value(
+		&mut self,
+		path: &mut Vec<String>,
+		strings: &mut Vec<(usize, usize, Vec<String>)>,
+	) -> Option<()> {
+		self.skip_whitespace();
+
+		match self.value.get(self.index)? {
+			b'{' => {
+				self.index += 1;
+
+				if self.expect(b'}').is_some() {
+					return Some(());
+				}
+
+				loop {
+					self.skip_whitespace();
+					let (start, end) = self.scan_string()?;
+					let key = std::str::from_utf8(&self.value[start..end]).ok()?;
+					path.push(decode_json_string(key)?);
+					self.expect(b':')?;
+					self.scan_value(path, strings)?;
+					path.pop();
+
+					if self.expect(b',').is_none() {
+						return self.expect(b'}');
+					}
+				}
+			},
+			b'[' => {
+				self.index += 1;
+
+				if self.expect(b']').is_some() {
+					return Some(());
+				}
+
+				for index in 0.. {
+					path.push(index.to_string());
+					self.scan_value(path, strings)?;
+					path.pop();
+
+					if self.expect(b',').is_none() {
+						break;
+					}
+				}
+
+				self.expect(b']')
+			},
+			b'"' => {
+				let (start, end) = self.scan_string()?;
+				strings.push((start, end, path.clone()));
+				Some(())
+			},
+			// Numbers, booleans and null.
+			_ => {
+				let start = self.index;
+
+				while self.value.get(self.index).is_some_and(|byte| {
+					byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'+' | b'.')
+				}) {
+					self.index += 1;
+				}
+
+				(self.index > start).then_some(())
+			},
+		}
+	}
+
+	fn scan_string(&mut self) -> Option<(usize, usize)> {
+		let start = self.index;
+
+		if self.value.get(self.index) != Some(&b'"') {
+			return None;
+		}
+		self.index += 1;
+
+		loop {
+			match self.value.get(self.index)? {
+				b'\\' => self.index += 2,
+				b'"' => {
+					self.index += 1;
+					return Some((start, self.index));
+				},
+				_ => self.index += 1,
+			}
+		}
+	}
+}
+
+// Decode JSON string literal (including the quotes).
+fn decode_json_string(literal: &str) -> Option<String> {
+	let mut decoded = String::with_capacity(literal.len());
+	let mut characters = literal.strip_prefix('"')?.strip_suffix('"')?.chars();
+	let mut code_units: Vec<u16> = vec![];
+
+	while let Some(character) = characters.next() {
+		if character != '\\' {
+			decoded.push(character);
+			continue;
+		}
+
+		let escaped = characters.next()?;
+
+		if escaped == 'u' {
+			let code: String = characters.by_ref().take(4).collect();
+			code_units.push(u16::from_str_radix(&code, 16).ok()?);
+
+			// Surrogate pairs are made up of two escapes.
+			if (0xd800..0xdc00).contains(code_units.last().unwrap()) {
+				continue;
+			}
+
+			decoded.extend(
+				char::decode_utf16(code_units.drain(..))
+					.map(|character| character.unwrap_or(char::REPLACEMENT_CHARACTER)),
+			);
+			continue;
+		}
+
+		decoded.push(match escaped {
+			'b' => '\u{8}',
+			'f' => '\u{c}',
+			'n' => '\n',
+			'r' => '\r',
+			't' => '\t',
+			_ => escaped,
+		});
+	}
+
+	Some(decoded)
+}
+
+// Encode string as a JSON string literal.
+fn encode_json_string(value: &str) -> String {
+	let mut encoded = String::with_capacity(value.len() + 2);
+	encoded.push('"');
+
+	for character in value.chars() {
+		match character {
+			'"' => encoded.push_str("\\\""),
+			'\\' => encoded.push_str("\\\\"),
+			'\n' => encoded.push_str("\\n"),
+			'\r' => encoded.push_str("\\r"),
+			'\t' => encoded.push_str("\\t"),
+			character if (character as u32) < 0x20 => {
+				encoded.push_str(&format!("\\u{:04x}", character as u32));
+			},
+			_ => encoded.push(character),
+		}
+	}
+
+	encoded.push('"');
+	encoded
+}
@@ -1,13 +1,28 @@
 pub mod html_attributes;
+pub mod json;
 pub mod named_char_refs;
+pub mod presets;
 pub mod regexes;
+pub mod script_types;
+pub mod smil;
+pub mod tokenizer;
+
+use std::borrow::Cow;
 
 use minify_selectors_utils::*;
 use onig::*;
 
-use crate::markup::html_attributes::WHITELIST;
+use crate::markup::html_attributes::get_attribute_designation;
+use crate::markup::html_attributes::Element;
+use crate::markup::json::*;
 use crate::markup::named_char_refs::ENTITIES;
 use crate::markup::regexes as markup_regex;
+use crate::markup::smil::*;
+use crate::markup::tokenizer::Attribute;
+use crate::markup::tokenizer::Token;
+use crate::markup::tokenizer::TokenKind;
+use crate::pragmas::*;
+use crate::Replacement;
 
 
 
@@ -19,7 +34,11 @@ pub fn analyse_html(
 	config: &Config,
 	usage: Option<SelectorUsage>,
 ) {
-	analyse_html_attributes(file_string, selectors, config, usage);
+	if let Some((mut masked, _)) = mask_disabled_regions(file_string, CommentSyntax::Markup) {
+		return analyse_html(&mut masked, selectors, config, usage);
+	}
+
+	analyse_html_attributes(file_string, selectors, config, usage, false);
 	analyse_html_scripts(file_string, selectors, config);
 	analyse_html_styles(file_string, selectors, config);
 	super::analyse_prefixed_selectors(file_string, selectors);
@@ -31,80 +50,88 @@ pub fn rewrite_html(
 	selectors: &Selectors,
 	config: &Config,
 ) {
-	rewrite_html_attributes(file_string, selectors, config);
+	if let Some((mut masked, regions)) = mask_disabled_regions(file_string, CommentSyntax::Markup) {
+		rewrite_html(&mut masked, selectors, config);
+		*file_string = unmask_disabled_regions(&masked, &regions, CommentSyntax::Markup);
+		return;
+	}
+
+	rewrite_html_attributes(file_string, selectors, config, false);
 	rewrite_html_scripts(file_string, selectors, config);
 	rewrite_html_styles(file_string, selectors, config);
 	super::rewrite_prefixed_selectors(file_string, selectors);
 }
 
 /// Analyse HTML attributes.
+///
+/// Notes:
+///  - With `expressions`, attribute values in braces (i.e. `class={foo}` in
+///    component markup) are left for `analyse_component_directives`, as are
+///    classes and IDs mixed with them (i.e. `class="foo {bar}"`).
 pub fn analyse_html_attributes(
 	file_string: &mut str,
 	selectors: &mut Selectors,
 	config: &Config,
 	usage: Option<SelectorUsage>,
+	expressions: bool,
 ) {
-	for capture in markup_regex::HTML_ATTRIBUTES.captures_iter(file_string) {
-		// Matched string is a <code>/<script>/<style> element or a HTML comment.
-		if capture.at(1).is_none() && capture.at(0).unwrap().starts_with("<code") {
-			// Check for attributes to encode on the opening tag.
-			let code_element = capture
-				.at(0)
-				.unwrap()
-				.strip_prefix("<code")
-				.unwrap()
-				.split_once('>')
-				.unwrap();
-			let mut code_tag_attributes = code_element.0.to_string();
-			analyse_html_attributes(&mut code_tag_attributes, selectors, config, usage);
-			continue;
-		}
+	let tokens = tokenizer::tokenize(file_string, expressions);
+	let mut element = None;
 
-		// Matched string is a <script>/<style> element or HTML comment, leave as is.
-		if capture.at(1).is_none() && !capture.at(0).unwrap().starts_with("<code") {
+	for (tag, attribute) in get_processable_attributes(&tokens) {
+		// Expressions in component markup are handled separately.
+		if attribute.expression {
 			continue;
 		}
 
 		// Attribute does not contain classes and/or IDs.
 		// Leave it as is.
-		if !WHITELIST
-			.get()
-			.unwrap()
-			.contains_key(&capture.at(1).unwrap().to_ascii_lowercase())
-		{
+		let element = get_cached_element(&mut element, file_string, tag);
+		let (Some(value), Some(attribute_type_designation)) = (
+			attribute.value(file_string),
+			get_attribute_designation(&attribute.name, Some(element)),
+		) else {
+			continue;
+		};
+
+		// So are classes and IDs mixed with expressions in component markup,
+		// i.e. `class="btn {active ? 'on' : ''}"`.
+		if expressions && is_interpolated(value, attribute_type_designation) {
 			continue;
 		}
 
-		let attribute_name: &str = capture.at(1).unwrap();
-		let mut attribute_value: String = unescape_html_chars(capture.at(4).unwrap());
+		let mut attribute_value: String = match attribute_type_designation.as_str() {
+			"markup" | "json" => decode_html_chars(value),
+			_ => unescape_html_chars(value),
+		};
 
 		// Work out if value(s) are classes, IDs, selectors, etc.
-		let attribute_type_designation: &str = WHITELIST
-			.get()
-			.unwrap()
-			.get(&attribute_name.to_ascii_lowercase())
-			.unwrap();
-
-		match attribute_type_designation {
-			"id" | "class" => {
+		match attribute_type_designation.as_str() {
+			"id" | "class" | "part" => {
 				super::analyse_string_of_tokens(
 					&mut attribute_value,
 					selectors,
 					attribute_type_designation,
 					if usage.is_none() {
-						Some(
-							if attribute_type_designation == "id" {
-								SelectorUsage::MarkupId
-							} else {
-								SelectorUsage::MarkupClass
-							},
-						)
+						Some(match attribute_type_designation.as_str() {
+							"id" => SelectorUsage::MarkupId,
+							"part" => SelectorUsage::MarkupPart,
+							_ => SelectorUsage::MarkupClass,
+						})
 					} else {
 						usage
 					},
 				);
 			},
 
+			"exportparts" => {
+				analyse_export_parts(
+					&attribute_value,
+					selectors,
+					usage.or(Some(SelectorUsage::MarkupPart)),
+				);
+			},
+
 			"selector" => {
 				super::analyse_css(&mut attribute_value, selectors, config);
 			},
@@ -121,6 +148,29 @@ pub fn analyse_html_attributes(
 				super::analyse_anchor_links(&mut attribute_value, selectors);
 			},
 
+			"timing" => {
+				analyse_smil_timing(
+					&attribute_value,
+					selectors,
+					usage.or(Some(SelectorUsage::MarkupId)),
+				);
+			},
+
+			"json" => {
+				analyse_json_attribute(
+					&attribute_value,
+					&attribute.name,
+					Some(element),
+					selectors,
+					config,
+					usage,
+				);
+			},
+
+			"markup" => {
+				analyse_html(&mut attribute_value, selectors, config, usage);
+			},
+
 			_ => continue,
 		}
 	}
@@ -131,58 +181,43 @@ pub fn rewrite_html_attributes(
 	file_string: &mut String,
 	selectors: &Selectors,
 	config: &Config,
+	expressions: bool,
 ) {
-	*file_string = markup_regex::HTML_ATTRIBUTES.replace_all(file_string, |capture: &Captures| {
-		// Matched string is a <code>/<script>/<style> element or a HTML comment.
-		if capture.at(1).is_none() && capture.at(0).unwrap().starts_with("<code") {
-			// Check for attributes to encode on the opening tag.
-			let code_element = capture
-				.at(0)
-				.unwrap()
-				.strip_prefix("<code")
-				.unwrap()
-				.split_once('>')
-				.unwrap();
-
-			let mut code_tag_attributes = code_element.0.to_string();
-
-			rewrite_html_attributes(&mut code_tag_attributes, selectors, config);
-
-			return format!(
-				"<code{attributes}>{inner_html}",
-				attributes = code_tag_attributes,
-				inner_html = code_element.1,
-			);
-		}
+	let tokens = tokenizer::tokenize(file_string, expressions);
+	let mut replacements: Vec<Replacement> = vec![];
+	let mut element = None;
 
-		// Matched string is a <script>/<style> element or HTML comment, leave as is.
-		if capture.at(1).is_none() && !capture.at(0).unwrap().starts_with("<code") {
-			return capture.at(0).unwrap().to_string();
+	for (tag, attribute) in get_processable_attributes(&tokens) {
+		// Expressions in component markup are handled separately.
+		if attribute.expression {
+			continue;
 		}
 
 		// Attribute does not contain classes and/or IDs.
 		// Leave it as is.
-		if !WHITELIST
-			.get()
-			.unwrap()
-			.contains_key(&capture.at(1).unwrap().to_ascii_lowercase())
-		{
-			return capture.at(0).unwrap().to_string();
+		let element = get_cached_element(&mut element, file_string, tag);
+		let (Some(value), Some(attribute_type_designation)) = (
+			attribute.value(file_string),
+			get_attribute_designation(&attribute.name, Some(element)),
+		) else {
+			continue;
+		};
+
+		// So are classes and IDs mixed with expressions in component markup,
+		// i.e. `class="btn {active ? 'on' : ''}"`.
+		if expressions && is_interpolated(value, attribute_type_designation) {
+			continue;
 		}
 
-		let attribute_name: &str = capture.at(1).unwrap();
-		let attribute_quote: &str = capture.at(3).unwrap_or("");
-		let mut attribute_value: String = unescape_html_chars(capture.at(4).unwrap());
+		let original_value: String = match attribute_type_designation.as_str() {
+			"markup" | "json" => decode_html_chars(value),
+			_ => unescape_html_chars(value),
+		};
+		let mut attribute_value: String = original_value.clone();
 
 		// Work out if value(s) are classes, IDs, selectors, etc.
-		let attribute_type_designation: &str = WHITELIST
-			.get()
-			.unwrap()
-			.get(&attribute_name.to_ascii_lowercase())
-			.unwrap();
-
-		match attribute_type_designation {
-			"id" | "class" => {
+		match attribute_type_designation.as_str() {
+			"id" | "class" | "part" => {
 				super::rewrite_string_of_tokens(
 					&mut attribute_value,
 					selectors,
@@ -190,6 +225,10 @@ pub fn rewrite_html_attributes(
 				);
 			},
 
+			"exportparts" => {
+				rewrite_export_parts(&mut attribute_value, selectors);
+			},
+
 			"selector" => {
 				super::rewrite_css(&mut attribute_value, selectors, config);
 			},
@@ -206,19 +245,64 @@ pub fn rewrite_html_attributes(
 				super::rewrite_anchor_links(&mut attribute_value, selectors);
 			},
 
-			_ => {
-				return capture.at(0).unwrap().to_string();
+			"timing" => {
+				rewrite_smil_timing(&mut attribute_value, selectors);
+			},
+
+			"json" => {
+				rewrite_json_attribute(
+					&mut attribute_value,
+					&attribute.name,
+					Some(element),
+					selectors,
+					config,
+				);
+				// Decoded value needs to be escaped again.
+				if attribute_value != original_value {
+					attribute_value =
+						splice_html_attribute_value(value, &attribute_value, attribute.quote);
+				}
+			},
+
+			"markup" => {
+				rewrite_html(&mut attribute_value, selectors, config);
+				// Decoded value needs to be escaped again.
+				if attribute_value != original_value {
+					attribute_value =
+						splice_html_attribute_value(value, &attribute_value, attribute.quote);
+				}
 			},
+
+			_ => continue,
 		}
 
-		format!(
-			"{attribute}{join}{quote}{value}",
-			attribute = attribute_name,
-			join = capture.at(2).unwrap(),
-			value = attribute_value,
-			quote = attribute_quote,
-		)
-	});
+		// Unescaped quotes (i.e. `url(&quot;#foo&quot;)`) need to be escaped
+		// again, so the value does not end early.
+		if !matches!(attribute_type_designation.as_str(), "markup" | "json")
+			&& !attribute.quote.is_empty()
+			&& attribute_value.contains(attribute.quote)
+		{
+			attribute_value = attribute_value.replace(
+				attribute.quote,
+				if attribute.quote.ends_with('\'') {
+					"&#39;"
+				} else {
+					"&quot;"
+				},
+			);
+		}
+
+		if attribute_value != original_value {
+			let (start, end) = attribute.value.unwrap();
+			replacements.push(Replacement {
+				start,
+				end,
+				value: attribute_value,
+			});
+		}
+	}
+
+	super::splice_replacements(file_string, replacements);
 }
 
 // Analyse embedded scripts in HTML.
@@ -227,9 +311,15 @@ pub fn analyse_html_scripts(
 	selectors: &mut Selectors,
 	config: &Config,
 ) {
-	for capture in markup_regex::HTML_SCRIPT_ELEMENT.captures_iter(file_string) {
-		let mut embedded_script = capture.at(2).unwrap().to_string();
-		super::analyse_js(&mut embedded_script, selectors, config);
+	for (tag, token) in get_raw_text(&tokenizer::tokenize(file_string, false), "script") {
+		let mut embedded_script = token.text(file_string).to_string();
+
+		// Work out if script contents are JS, a HTML template, etc.
+		match get_script_designation(file_string, tag) {
+			"script" => super::analyse_js(&mut embedded_script, selectors, config),
+			"markup" => analyse_html(&mut embedded_script, selectors, config, None),
+			_ => continue,
+		}
 	}
 }
 
@@ -239,18 +329,26 @@ pub fn rewrite_html_scripts(
 	selectors: &Selectors,
 	config: &Config,
 ) {
-	*file_string =
-		markup_regex::HTML_SCRIPT_ELEMENT.replace_all(file_string, |capture: &Captures| {
-			let mut embedded_script = capture.at(2).unwrap().to_string();
-			super::rewrite_js(&mut embedded_script, selectors, config);
-
-			format!(
-				"{tag_open}{script}{tag_close}",
-				tag_open = capture.at(1).unwrap(),
-				script = embedded_script,
-				tag_close = capture.at(3).unwrap()
-			)
+	let mut replacements: Vec<Replacement> = vec![];
+
+	for (tag, token) in get_raw_text(&tokenizer::tokenize(file_string, false), "script") {
+		let mut embedded_script = token.text(file_string).to_string();
+
+		// Work out if script contents are JS, a HTML template, etc.
+		match get_script_designation(file_string, tag) {
+			"script" => super::rewrite_js(&mut embedded_script, selectors, config),
+			"markup" => rewrite_html(&mut embedded_script, selectors, config),
+			_ => continue,
+		}
+
+		replacements.push(Replacement {
+			start: token.start,
+			end: token.end,
+			value: embedded_script,
 		});
+	}
+
+	super::splice_replacements(file_string, replacements);
 }
 
 // Analyse embedded styles in HTML.
@@ -259,8 +357,9 @@ pub fn analyse_html_styles(
 	selectors: &mut Selectors,
 	config: &Config,
 ) {
-	for capture in markup_regex::HTML_STYLE_ELEMENT.captures_iter(file_string) {
-		let mut embedded_style = capture.at(2).unwrap().to_string();
+	for (_, token) in get_raw_text(&tokenizer::tokenize(file_string, false), "style") {
+		let (start, end) = get_cdata_content(file_string, token.start, token.end);
+		let mut embedded_style = file_string[start..end].to_string();
 		super::analyse_css(&mut embedded_style, selectors, config);
 	}
 }
@@ -271,18 +370,407 @@ pub fn rewrite_html_styles(
 	selectors: &Selectors,
 	config: &Config,
 ) {
-	*file_string =
-		markup_regex::HTML_STYLE_ELEMENT.replace_all(file_string, |capture: &Captures| {
-			let mut embedded_style = capture.at(2).unwrap().to_string();
-			super::rewrite_css(&mut embedded_style, selectors, config);
-
-			format!(
-				"{tag_open}{styles}{tag_close}",
-				tag_open = capture.at(1).unwrap(),
-				styles = embedded_style,
-				tag_close = capture.at(3).unwrap(),
-			)
+	let mut replacements: Vec<Replacement> = vec![];
+
+	for (_, token) in get_raw_text(&tokenizer::tokenize(file_string, false), "style") {
+		let (start, end) = get_cdata_content(file_string, token.start, token.end);
+		let mut embedded_style = file_string[start..end].to_string();
+		super::rewrite_css(&mut embedded_style, selectors, config);
+
+		replacements.push(Replacement {
+			start,
+			end,
+			value: embedded_style,
 		});
+	}
+
+	super::splice_replacements(file_string, replacements);
+}
+
+/// Analyse shadow part names in an `exportparts` attribute value, i.e.
+/// `foo, bar: baz` where `bar` is exported as `baz`.
+pub fn analyse_export_parts(
+	value: &str,
+	selectors: &mut Selectors,
+	usage: Option<SelectorUsage>,
+) {
+	for (start, end) in find_export_part_names(value) {
+		super::analyse_string_of_tokens(
+			&mut value[start..end].to_string(),
+			selectors,
+			"part",
+			usage,
+		);
+	}
+}
+
+/// Rewrite shadow part names in an `exportparts` attribute value.
+pub fn rewrite_export_parts(
+	value: &mut String,
+	selectors: &Selectors,
+) {
+	let mut replacements: Vec<Replacement> = vec![];
+
+	for (start, end) in find_export_part_names(value) {
+		let mut name = value[start..end].to_string();
+		super::rewrite_string_of_tokens(&mut name, selectors, "part");
+
+		if name != value[start..end] {
+			replacements.push(Replacement {
+				start,
+				end,
+				value: name,
+			});
+		}
+	}
+
+	super::splice_replacements(value, replacements);
+}
+
+// Find ranges of the part names in an `exportparts` attribute value,
+// mappings are separated by commas and the inner and outer names by a colon.
+fn find_export_part_names(value: &str) -> Vec<(usize, usize)> {
+	let mut names = vec![];
+	let mut offset: usize = 0;
+
+	for mapping in value.split(',') {
+		for name in mapping.split(':') {
+			let start = offset + name.len() - name.trim_start().len();
+			offset += name.len() + 1;
+
+			if !name.trim().is_empty() {
+				names.push((start, start + name.trim().len()));
+			}
+		}
+	}
+
+	names
+}
+
+// Get range of the content inside a CDATA section (as used in SVG), or
+// the given range if the text is not wrapped in one.
+fn get_cdata_content(
+	file_string: &str,
+	start: usize,
+	end: usize,
+) -> (usize, usize) {
+	let text = &file_string[start..end];
+	let trimmed = text.trim();
+
+	match (trimmed.starts_with("<![CDATA["), trimmed.ends_with("]]>")) {
+		(true, true) => {
+			let content_start = start + text.find("<![CDATA[").unwrap() + "<![CDATA[".len();
+			let content_end = start + text.rfind("]]>").unwrap();
+			(content_start, content_end.max(content_start))
+		},
+		_ => (start, end),
+	}
+}
+
+// Get attributes that may need processing, skipping over any tags inside
+// <code> elements.
+pub(crate) fn get_processable_attributes(tokens: &[Token]) -> Vec<(&Token, &Attribute)> {
+	let mut attributes = vec![];
+	let mut is_in_code = false;
+
+	for token in tokens {
+		if token.kind == TokenKind::EndTag && token.name == "code" {
+			is_in_code = false;
+		}
+
+		if token.kind != TokenKind::StartTag || is_in_code {
+			continue;
+		}
+
+		attributes.extend(token.attributes.iter().map(|attribute| (token, attribute)));
+
+		// Attributes on the <code> tag itself are still processed.
+		if token.name == "code" && !token.self_closing {
+			is_in_code = true;
+		}
+	}
+
+	attributes
+}
+
+// Get tag name and attribute values of an element, to match against
+// custom attribute rules that are limited to certain elements.
+pub(crate) fn get_element<'a>(
+	file_string: &'a str,
+	tag: &'a Token,
+) -> Element<'a> {
+	Element {
+		tag: &tag.name,
+		attributes: tag
+			.attributes
+			.iter()
+			.map(|attribute| {
+				(
+					attribute.name.as_str(),
+					Cow::Borrowed(attribute.value(file_string).unwrap_or("")),
+				)
+			})
+			.collect(),
+		is_escaped: true,
+	}
+}
+
+// Same as `get_element`, but the element is kept for the rest of the
+// attributes on the same tag, so that it is only built once per tag.
+pub(crate) fn get_cached_element<'a, 'b>(
+	cache: &'b mut Option<(usize, Element<'a>)>,
+	file_string: &'a str,
+	tag: &'a Token,
+) -> &'b Element<'a> {
+	if cache.as_ref().is_none_or(|(start, _)| *start != tag.start) {
+		*cache = Some((tag.start, get_element(file_string, tag)));
+	}
+
+	&cache.as_ref().unwrap().1
+}
+
+// Check if classes or IDs in a quoted attribute value are mixed with
+// expressions in braces, as in Svelte and Astro markup.
+pub(crate) fn is_interpolated(
+	value: &str,
+	attribute_type_designation: &str,
+) -> bool {
+	matches!(attribute_type_designation, "id" | "class" | "part")
+		&& !find_interpolations(value).is_empty()
+}
+
+// Find ranges of the expressions in braces in an attribute value (i.e.
+// `btn {active ? 'on' : ''}`), including the braces. Braces in strings
+// inside an expression are skipped over.
+pub(crate) fn find_interpolations(value: &str) -> Vec<(usize, usize)> {
+	let mut interpolations = vec![];
+	let mut start: Option<usize> = None;
+	let mut depth: usize = 0;
+	let mut quote: Option<char> = None;
+	let mut is_escaped = false;
+
+	for (index, character) in value.char_indices() {
+		if let Some(delimiter) = quote {
+			match character {
+				_ if is_escaped => is_escaped = false,
+				'\\' => is_escaped = true,
+				_ if character == delimiter => quote = None,
+				_ => {},
+			}
+			continue;
+		}
+
+		match character {
+			'{' => {
+				if depth == 0 {
+					start = Some(index);
+				}
+				depth += 1;
+			},
+			'}' if depth > 0 => {
+				depth -= 1;
+				if depth == 0 {
+					interpolations.push((start.take().unwrap(), index + 1));
+				}
+			},
+			'\'' | '"' | '`' if depth > 0 => quote = Some(character),
+			_ => {},
+		}
+	}
+
+	interpolations
+}
+
+// Get contents of the given raw text element, along with its start tag.
+fn get_raw_text<'a>(
+	tokens: &'a [Token],
+	name: &'a str,
+) -> impl Iterator<Item = (&'a Token, &'a Token)> {
+	tokens.windows(2).filter_map(move |pair| {
+		match pair[1].kind == TokenKind::RawText && pair[1].name == name {
+			true => Some((&pair[0], &pair[1])),
+			false => None,
+		}
+	})
+}
+
+// Get designation for a script element from its type attribute.
+fn get_script_designation(
+	file_string: &str,
+	tag: &Token,
+) -> &'static str {
+	let script_type = tag
+		.attributes
+		.iter()
+		.find(|attribute| attribute.name == "type")
+		.and_then(|attribute| attribute.value(file_string))
+		.map(unescape_html_chars);
+
+	script_types::get_designation(script_type.as_deref())
+}
+
+// Convert any character references in HTML substring to UTF8 char.
+// Unlike `unescape_html_chars`, unknown named character references
+// are left as is rather than being escaped for CSS.
+pub fn decode_html_chars(substring: &str) -> String {
+	if markup_regex::ESCAPED_HTML_CHARS.find(substring).is_none() {
+		return substring.to_string();
+	}
+
+	markup_regex::ESCAPED_HTML_CHARS.replace_all(substring, |capture: &Captures| {
+		let reference = capture.at(0).unwrap();
+
+		match capture.at(3) {
+			Some(named) => ENTITIES.get(named).cloned().unwrap_or(named.to_string()),
+			None => unescape_html_chars(reference),
+		}
+	})
+}
+
+// Escape HTML so that it can be placed back into an attribute value
+// delimited by the given quote.
+pub fn escape_html_attribute_value(
+	value: &str,
+	quote: &str,
+) -> String {
+	let mut escaped = String::with_capacity(value.len());
+
+	for character in value.chars() {
+		match character {
+			'&' => escaped.push_str("&amp;"),
+			'"' if !quote.ends_with('\'') => escaped.push_str("&quot;"),
+			'\'' if !quote.ends_with('"') => escaped.push_str("&#39;"),
+			// Unquoted values cannot contain any of the following either.
+			'<' if quote.is_empty() => escaped.push_str("&lt;"),
+			'>' if quote.is_empty() => escaped.push_str("&gt;"),
+			'=' if quote.is_empty() => escaped.push_str("&#61;"),
+			'`' if quote.is_empty() => escaped.push_str("&#96;"),
+			character if quote.is_empty() && character.is_ascii_whitespace() => {
+				escaped.push_str(&format!("&#{};", character as u32));
+			},
+			_ => escaped.push(character),
+		}
+	}
+
+	escaped
+}
+
+// Escape a rewritten (decoded) attribute value so that it can be placed back
+// into the attribute. Parts that are unchanged keep their original text from
+// the source, so only the rewritten parts are escaped again.
+pub fn splice_html_attribute_value(
+	source: &str,
+	rewritten: &str,
+	quote: &str,
+) -> String {
+	// Decoded and source ranges of each character reference.
+	let mut references: Vec<(usize, usize, usize, usize)> = vec![];
+	let mut decoded = String::with_capacity(source.len());
+	let mut last = 0;
+
+	for (start, end) in markup_regex::ESCAPED_HTML_CHARS.find_iter(source) {
+		let reference = decode_html_chars(&source[start..end]);
+		// Unknown named character references are left as is.
+		if reference == source[start..end] {
+			continue;
+		}
+		decoded.push_str(&source[last..start]);
+		references.push((decoded.len(), decoded.len() + reference.len(), start, end));
+		decoded.push_str(&reference);
+		last = end;
+	}
+	decoded.push_str(&source[last..]);
+
+	// Offset in the source for an offset in the decoded value, if it is
+	// not in the middle of a character reference.
+	let get_source_offset = |offset: usize| -> Option<usize> {
+		match references
+			.iter()
+			.rev()
+			.find(|reference| reference.0 < offset)
+		{
+			Some(&(_, decoded_end, _, source_end)) if offset >= decoded_end => {
+				Some(source_end + offset - decoded_end)
+			},
+			Some(_) => None,
+			None => Some(offset),
+		}
+	};
+
+	let original_segments = get_segments(&decoded);
+	let rewritten_segments = get_segments(rewritten);
+	let mut spliced = String::with_capacity(source.len());
+	let (mut i, mut j) = (0, 0);
+
+	while j < rewritten_segments.len() {
+		if let Some(&(start, segment)) = original_segments
+			.get(i)
+			.filter(|(_, segment)| *segment == rewritten_segments[j].1)
+		{
+			match (
+				get_source_offset(start),
+				get_source_offset(start + segment.len()),
+			) {
+				(Some(source_start), Some(source_end)) => {
+					spliced.push_str(&source[source_start..source_end]);
+				},
+				_ => spliced.push_str(&escape_html_attribute_value(segment, quote)),
+			}
+			i += 1;
+			j += 1;
+			continue;
+		}
+
+		// Find the nearest segments where both values line up again,
+		// everything in between was rewritten.
+		let next_match = (1..SPLICE_LOOKAHEAD)
+			.flat_map(|distance| (0..=distance).map(move |k| (k, distance - k)))
+			.find(|&(k, l)| {
+				original_segments
+					.get(i + k)
+					.zip(rewritten_segments.get(j + l))
+					.is_some_and(|(original, rewritten)| original.1 == rewritten.1)
+			});
+		let (skipped, inserted) =
+			next_match.unwrap_or((original_segments.len() - i, rewritten_segments.len() - j));
+
+		for (_, segment) in &rewritten_segments[j..j + inserted] {
+			spliced.push_str(&escape_html_attribute_value(segment, quote));
+		}
+		i += skipped;
+		j += inserted;
+	}
+
+	spliced
+}
+
+// How many segments ahead to look for where an original and rewritten
+// value line up again.
+const SPLICE_LOOKAHEAD: usize = 64;
+
+// Split value into runs of name characters, and single other characters.
+fn get_segments(value: &str) -> Vec<(usize, &str)> {
+	let is_name_char = |character: char| {
+		character.is_alphanumeric() || matches!(character, '-' | '_') || !character.is_ascii()
+	};
+	let mut segments: Vec<(usize, &str)> = vec![];
+	let mut run_start: Option<usize> = None;
+
+	for (offset, character) in value.char_indices() {
+		if is_name_char(character) {
+			run_start.get_or_insert(offset);
+			continue;
+		}
+		if let Some(start) = run_start.take() {
+			segments.push((start, &value[start..offset]));
+		}
+		segments.push((offset, &value[offset..offset + character.len_utf8()]));
+	}
+	if let Some(start) = run_start {
+		segments.push((start, &value[start..]));
+	}
+
+	segments
 }
 
 // Convert any escaped chars in HTML substring to UTF8 char.
@@ -309,7 +797,7 @@ pub fn unescape_html_chars(substring: &str) -> String {
 					)
 					.unwrap(),
 				)
-				.unwrap(),
+				.unwrap_or(char::REPLACEMENT_CHARACTER),
 			);
 		} else if capture.at(2).is_some() {
 			return String::from(
@@ -324,7 +812,7 @@ pub fn unescape_html_chars(substring: &str) -> String {
 						.parse::<u32>()
 						.unwrap(),
 				)
-				.unwrap(),
+				.unwrap_or(char::REPLACEMENT_CHARACTER),
 			);
 		} else if capture.at(3).is_some() {
 			if !ENTITIES.contains_key(capture.at(3).unwrap()) {
@@ -0,0 +1,66 @@
+/// Attributes of front-end frameworks and libraries, which can be enabled by
+/// name rather than listing each as a custom attribute.
+///
+/// Notes:
+///  - Bound attributes (i.e. `:class`, `x-bind:class` or `[class.active]`) are
+///    handled as directives, see `uses_directives`.
+///  - Attributes that only name a plugin or swap style (i.e. `data-bs-toggle`
+///    or `hx-swap`) are left as is.
+#[rustfmt::skip]
+pub fn get_preset_attributes(preset: &str) -> &'static [(&'static str, &'static str)] {
+	match preset {
+		"alpine" => &[
+			("x-data", "script"),
+			("x-init", "script"),
+			("x-show", "script"),
+			("x-effect", "script"),
+			("x-if", "script"),
+			("x-text", "script"),
+			("x-html", "script"),
+			("x-transition:*", "class"),
+		],
+		// Bootstrap 5, unprefixed attributes of earlier versions (i.e.
+		// `data-target`) are too common to include.
+		"bootstrap" => &[
+			("data-bs-target", "selector"),
+			("data-bs-parent", "selector"),
+			("data-bs-container", "selector"),
+		],
+		"htmx" => &[
+			("hx-target", "selector"),
+			("hx-select", "selector"),
+			("hx-select-oob", "selector"),
+			("hx-include", "selector"),
+			("hx-indicator", "selector"),
+			("hx-disabled-elt", "selector"),
+			("hx-on:*", "script"),
+			// Same as `hx-on:`, for templating languages that do not allow
+			// colons in attribute names (i.e. `hx-on--after-request`).
+			("hx-on-*", "script"),
+			("data-hx-target", "selector"),
+			("data-hx-select", "selector"),
+			("data-hx-select-oob", "selector"),
+			("data-hx-include", "selector"),
+			("data-hx-indicator", "selector"),
+			("data-hx-disabled-elt", "selector"),
+			("data-hx-on:*", "script"),
+			("data-hx-on-*", "script"),
+		],
+		"vue" => &[
+			("v-if", "script"),
+			("v-else-if", "script"),
+			("v-show", "script"),
+			("v-text", "script"),
+			("v-html", "script"),
+		],
+		_ => &[],
+	}
+}
+
+/// Check any of the presets use bound attributes (directives) in HTML, i.e.
+/// `x-bind:class` (Alpine), `:class` (Vue) or `[class.active]` (Angular).
+pub fn uses_directives(presets: &[String]) -> bool {
+	presets
+		.iter()
+		.any(|preset| matches!(preset.as_str(), "alpine" | "angular" | "vue"))
+}
@@ -0,0 +1,79 @@
+use std::collections::HashMap;
+
+use once_cell::sync::OnceCell;
+
+
+
+
+// Script element types and how their contents should be processed,
+// scripts with any other type are processed as JS.
+pub static SCRIPT_TYPES: OnceCell<HashMap<String, String>> = OnceCell::new();
+
+pub fn init(custom_script_types: &Vec<(String, String)>) {
+	#[rustfmt::skip]
+	SCRIPT_TYPES.get_or_init(|| {
+		let standard_script_types: [(String, String); 26] = [
+			// Script
+			(String::from(""), String::from("script")),
+			(String::from("module"), String::from("script")),
+			(String::from("text/javascript"), String::from("script")),
+			(String::from("text/ecmascript"), String::from("script")),
+			(String::from("application/javascript"), String::from("script")),
+			(String::from("application/ecmascript"), String::from("script")),
+			(String::from("application/x-javascript"), String::from("script")),
+			(String::from("application/x-ecmascript"), String::from("script")),
+			(String::from("text/x-javascript"), String::from("script")),
+			(String::from("text/x-ecmascript"), String::from("script")),
+			(String::from("text/jscript"), String::from("script")),
+			(String::from("text/livescript"), String::from("script")),
+			// Markup
+			(String::from("text/html"), String::from("markup")),
+			(String::from("text/template"), String::from("markup")),
+			(String::from("text/x-template"), String::from("markup")),
+			(String::from("text/x-handlebars"), String::from("markup")),
+			(String::from("text/x-handlebars-template"), String::from("markup")),
+			(String::from("text/x-mustache"), String::from("markup")),
+			(String::from("text/x-mustache-template"), String::from("markup")),
+			(String::from("text/x-underscore-template"), String::from("markup")),
+			(String::from("text/x-jquery-tmpl"), String::from("markup")),
+			(String::from("text/ng-template"), String::from("markup")),
+			// Ignore
+			(String::from("application/json"), String::from("ignore")),
+			(String::from("application/ld+json"), String::from("ignore")),
+			(String::from("importmap"), String::from("ignore")),
+			(String::from("speculationrules"), String::from("ignore")),
+		];
+
+		let mut script_types = HashMap::from(standard_script_types);
+
+		for (script_type, kind) in custom_script_types {
+			script_types.insert(script_type.to_ascii_lowercase(), kind.to_string());
+		}
+
+		script_types
+	});
+}
+
+/// Work out how the contents of a script element should be processed
+/// from its type attribute value: "script", "markup" or "ignore".
+pub fn get_designation(script_type: Option<&str>) -> &'static str {
+	// Parameters are ignored, i.e. "text/javascript; charset=utf-8"
+	let essence = script_type
+		.unwrap_or("")
+		.split(';')
+		.next()
+		.unwrap_or("")
+		.trim()
+		.to_ascii_lowercase();
+
+	match SCRIPT_TYPES
+		.get()
+		.unwrap()
+		.get(&essence)
+		.map(String::as_str)
+	{
+		Some("markup") => "markup",
+		Some("ignore") => "ignore",
+		_ => "script",
+	}
+}
@@ -0,0 +1,119 @@
+use minify_selectors_utils::*;
+
+use crate::Replacement;
+
+
+
+
+/// Analyse SMIL timing list of an SVG animation (i.e. `begin` or `end`).
+///
+/// Notes:
+///  - IDs are found in syncbase (`foo.end+1s`), event (`foo.click`) and repeat
+///    (`foo.repeat(2)`) values, any other values are left as is.
+///  - IDs marked with `__ignore--` (i.e. `__ignore--foo.click`) are handled
+///    with the rest of the prefixed selectors.
+pub fn analyse_smil_timing(
+	value: &str,
+	selectors: &mut Selectors,
+	usage: Option<SelectorUsage>,
+) {
+	for (start, end) in find_timing_ids(value) {
+		let id = &value[start..end];
+
+		if !crate::is_prefixed_selector(id) {
+			crate::add_selector_to_map(&format!("#{}", unescape_timing_id(id)), selectors, usage);
+		}
+	}
+}
+
+/// Rewrite SMIL timing list of an SVG animation.
+pub fn rewrite_smil_timing(
+	value: &mut String,
+	selectors: &Selectors,
+) {
+	let mut replacements: Vec<Replacement> = vec![];
+
+	for (start, end) in find_timing_ids(value) {
+		let id = &value[start..end];
+
+		if crate::is_prefixed_selector(id) {
+			continue;
+		}
+
+		if let Some(encoded) =
+			crate::get_encoded_selector(&format!("#{}", unescape_timing_id(id)), selectors)
+				.map(|encoded| escape_timing_id(&encoded))
+				.filter(|encoded| encoded != id)
+		{
+			replacements.push(Replacement {
+				start,
+				end,
+				value: encoded,
+			});
+		}
+	}
+
+	crate::splice_replacements(value, replacements);
+}
+
+// Find ranges of the IDs in a timing list, values are separated by
+// semicolons and the ID is the part before the first unescaped period.
+fn find_timing_ids(value: &str) -> Vec<(usize, usize)> {
+	let mut ids = vec![];
+	let mut offset: usize = 0;
+
+	for timing in value.split(';') {
+		let start = offset + timing.len() - timing.trim_start().len();
+		offset += timing.len() + 1;
+		let timing = timing.trim();
+
+		// Offset (i.e. `1s` or `-0.5s`), along with values that do not
+		// refer to an element.
+		if timing.is_empty()
+			|| timing.starts_with(|character: char| {
+				character.is_ascii_digit() || matches!(character, '+' | '-' | '.')
+			}) || timing == "indefinite"
+			|| timing.starts_with("accessKey(")
+			|| timing.starts_with("wallclock(")
+		{
+			continue;
+		}
+
+		let mut escaped = false;
+
+		for (index, character) in timing.char_indices() {
+			match character {
+				_ if escaped => escaped = false,
+				'\\' => escaped = true,
+				// Part after the period is the event or syncbase name, otherwise
+				// it is the fraction of an offset (i.e. `click-1.5s`).
+				'.' => {
+					if timing[index + 1..].starts_with(|character: char| character.is_alphabetic())
+					{
+						ids.push((start, start + index));
+					}
+					break;
+				},
+				// Event or repeat value without an ID followed by an offset
+				// (i.e. `click+1s`), or the argument of `repeat(2)`.
+				'+' | '(' => break,
+				character if character.is_whitespace() => break,
+				_ => {},
+			}
+		}
+	}
+
+	ids
+}
+
+// Remove escapes from ID, periods and hyphens in IDs need to be escaped in
+// timing values (i.e. `foo\.bar.click`).
+fn unescape_timing_id(id: &str) -> String {
+	id.replace('\\', "")
+}
+
+// Escape periods and hyphens in ID for a timing value, the reverse of
+// `unescape_timing_id`.
+fn escape_timing_id(id: &str) -> String {
+	id.replace('.', "\\.").replace('-', "\\-")
+}
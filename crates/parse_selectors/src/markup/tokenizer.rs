@@ -0,0 +1,428 @@
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TokenKind {
+	Text,
+	Comment,
+	// Doctype, processing instruction or any other bogus comment.
+	Declaration,
+	CData,
+	StartTag,
+	EndTag,
+	// Contents of a raw text or escapable raw text element, e.g. <script>.
+	RawText,
+}
+
+#[derive(Clone, Debug)]
+pub struct Token {
+	pub kind: TokenKind,
+	pub start: usize,
+	pub end: usize,
+	// Lowercased tag name for tags and raw text, otherwise empty.
+	pub name: String,
+	pub attributes: Vec<Attribute>,
+	pub self_closing: bool,
+}
+
+#[derive(Clone, Debug)]
+pub struct Attribute {
+	// Lowercased attribute name.
+	pub name: String,
+	pub name_start: usize,
+	pub name_end: usize,
+	// Byte range of value, excluding any quotes.
+	pub value: Option<(usize, usize)>,
+	// Quote around value, which may be escaped (e.g. \") if the
+	// markup is in a script string.
+	pub quote: &'static str,
+	// Value is an expression in braces (i.e. class={foo}) as used by
+	// component frameworks, the value range includes the braces.
+	pub expression: bool,
+}
+
+impl Token {
+	pub fn text<'a>(
+		&self,
+		source: &'a str,
+	) -> &'a str {
+		&source[self.start..self.end]
+	}
+}
+
+impl Attribute {
+	pub fn value<'a>(
+		&self,
+		source: &'a str,
+	) -> Option<&'a str> {
+		self.value.map(|(start, end)| &source[start..end])
+	}
+}
+
+// Elements which their contents are not parsed as markup.
+const RAW_TEXT_ELEMENTS: [&str; 9] = [
+	"iframe",
+	"noembed",
+	"noframes",
+	"plaintext",
+	"script",
+	"style",
+	"textarea",
+	"title",
+	"xmp",
+];
+
+/// Split markup into tokens, following the HTML5 tokenization rules for tags,
+/// attributes, comments and raw text elements.
+///
+/// Notes:
+///  - Tokens only note down their byte offsets in the source, so that any
+///    rewritten values can be spliced back in without touching the rest of the
+///    markup.
+///  - Character references are not decoded, see `unescape_html_chars`.
+///  - Attribute values may also be delimited by escaped quotes (i.e. \" or \')
+///    to handle markup that is embedded in a script string.
+///  - With `expressions`, unquoted attribute values that start with an opening
+///    brace run up until the matching closing brace, to handle expressions in
+///    component markup (e.g. Svelte and Astro).
+pub fn tokenize(
+	source: &str,
+	expressions: bool,
+) -> Vec<Token> {
+	let bytes = source.as_bytes();
+	let mut tokens: Vec<Token> = vec![];
+	let mut position: usize = 0;
+	let mut text_start: usize = 0;
+
+	while position < bytes.len() {
+		if bytes[position] != b'<' {
+			position += 1;
+			continue;
+		}
+
+		let Some(token) = scan_markup(source, position, expressions) else {
+			position += 1;
+			continue;
+		};
+
+		if text_start < position {
+			tokens.push(new_token(TokenKind::Text, text_start, position));
+		}
+
+		position = token.end;
+
+		// Contents of raw text elements run up until the matching end tag.
+		if token.kind == TokenKind::StartTag
+			&& !token.self_closing
+			&& RAW_TEXT_ELEMENTS.contains(&token.name.as_str())
+		{
+			let raw_text_end = match token.name.as_str() {
+				"plaintext" => bytes.len(),
+				name => find_end_tag(source, position, name),
+			};
+			let mut raw_text = new_token(TokenKind::RawText, position, raw_text_end);
+			raw_text.name = token.name.clone();
+
+			tokens.push(token);
+			tokens.push(raw_text);
+			position = raw_text_end;
+		} else {
+			tokens.push(token);
+		}
+
+		text_start = position;
+	}
+
+	if text_start < bytes.len() {
+		tokens.push(new_token(TokenKind::Text, text_start, bytes.len()));
+	}
+
+	tokens
+}
+
+fn new_token(
+	kind: TokenKind,
+	start: usize,
+	end: usize,
+) -> Token {
+	Token {
+		kind,
+		start,
+		end,
+		name: String::new(),
+		attributes: vec![],
+		self_closing: false,
+	}
+}
+
+// Scan the markup that begins with the less-than sign at the given
+// position. Returns None if it is just text.
+fn scan_markup(
+	source: &str,
+	start: usize,
+	expressions: bool,
+) -> Option<Token> {
+	let remaining = &source[start..];
+	let next = remaining.as_bytes().get(1).copied();
+
+	if let Some(comment) = remaining.strip_prefix("<!--") {
+		// Abruptly closed empty comments, i.e. <!--> and <!--->
+		let end = if comment.starts_with('>') {
+			start + 5
+		} else if comment.starts_with("->") {
+			start + 6
+		} else {
+			find_after(source, start + 4, "-->")
+		};
+		return Some(new_token(TokenKind::Comment, start, end));
+	}
+
+	if remaining.starts_with("<![CDATA[") {
+		let end = find_after(source, start + 9, "]]>");
+		return Some(new_token(TokenKind::CData, start, end));
+	}
+
+	match next {
+		Some(b'!') | Some(b'?') => {
+			let end = find_after(source, start + 2, ">");
+			Some(new_token(TokenKind::Declaration, start, end))
+		},
+		Some(b'/') => {
+			match remaining.as_bytes().get(2) {
+				Some(byte) if byte.is_ascii_alphabetic() => {
+					Some(scan_tag(source, start, TokenKind::EndTag, expressions))
+				},
+				// Missing end tag name, i.e. </>
+				Some(b'>') => Some(new_token(TokenKind::Declaration, start, start + 3)),
+				Some(_) => {
+					let end = find_after(source, start + 2, ">");
+					Some(new_token(TokenKind::Declaration, start, end))
+				},
+				None => None,
+			}
+		},
+		Some(byte) if byte.is_ascii_alphabetic() => {
+			Some(scan_tag(source, start, TokenKind::StartTag, expressions))
+		},
+		_ => None,
+	}
+}
+
+fn scan_tag(
+	source: &str,
+	start: usize,
+	kind: TokenKind,
+	expressions: bool,
+) -> Token {
+	let bytes = source.as_bytes();
+	let name_start = match kind {
+		TokenKind::EndTag => start + 2,
+		_ => start + 1,
+	};
+	let mut position = name_start;
+
+	while position < bytes.len() && !is_tag_name_end(bytes[position]) {
+		position += 1;
+	}
+
+	let mut token = new_token(kind, start, bytes.len());
+	token.name = source[name_start..position].to_ascii_lowercase();
+
+	loop {
+		// Before attribute name
+		while position < bytes.len()
+			&& (bytes[position].is_ascii_whitespace()
+				|| (bytes[position] == b'/' && bytes.get(position + 1) != Some(&b'>')))
+		{
+			position += 1;
+		}
+
+		if position >= bytes.len() {
+			token.end = bytes.len();
+			return token;
+		}
+
+		if bytes[position] == b'>' {
+			token.end = position + 1;
+			return token;
+		}
+
+		if source[position..].starts_with("/>") {
+			token.self_closing = true;
+			token.end = position + 2;
+			return token;
+		}
+
+		// Attribute name, an equals sign is allowed as the first character.
+		let attribute_name_start = position;
+		position += 1;
+		while position < bytes.len() && !is_attribute_name_end(bytes[position]) {
+			position += 1;
+		}
+		let attribute_name_end = position;
+
+		// After attribute name
+		let mut after_name = position;
+		while after_name < bytes.len() && bytes[after_name].is_ascii_whitespace() {
+			after_name += 1;
+		}
+
+		let mut attribute = Attribute {
+			name: source[attribute_name_start..attribute_name_end].to_ascii_lowercase(),
+			name_start: attribute_name_start,
+			name_end: attribute_name_end,
+			value: None,
+			quote: "",
+			expression: false,
+		};
+
+		if bytes.get(after_name) == Some(&b'=') {
+			position = after_name + 1;
+			while position < bytes.len() && bytes[position].is_ascii_whitespace() {
+				position += 1;
+			}
+			position = scan_attribute_value(source, position, &mut attribute, expressions);
+		}
+
+		token.attributes.push(attribute);
+	}
+}
+
+// Scan attribute value starting at the given position, returns
+// position after the value (and its closing quote).
+fn scan_attribute_value(
+	source: &str,
+	start: usize,
+	attribute: &mut Attribute,
+	expressions: bool,
+) -> usize {
+	let bytes = source.as_bytes();
+
+	let (quote, value_start) = match (bytes.get(start), bytes.get(start + 1)) {
+		(Some(b'"'), _) => ("\"", start + 1),
+		(Some(b'\''), _) => ("'", start + 1),
+		(Some(b'\\'), Some(b'"')) => ("\\\"", start + 2),
+		(Some(b'\\'), Some(b'\'')) => ("\\'", start + 2),
+		// Missing attribute value
+		(Some(b'>'), _) | (None, _) => {
+			attribute.value = Some((start, start));
+			return start;
+		},
+		_ => ("", start),
+	};
+
+	attribute.quote = quote;
+
+	if expressions && quote.is_empty() && bytes[start] == b'{' {
+		if let Some(end) = find_closing_brace(source, start) {
+			attribute.value = Some((start, end));
+			attribute.expression = true;
+			return end;
+		}
+	}
+
+	if quote.is_empty() {
+		let mut position = start;
+		while position < bytes.len()
+			&& !bytes[position].is_ascii_whitespace()
+			&& bytes[position] != b'>'
+		{
+			position += 1;
+		}
+		attribute.value = Some((start, position));
+		return position;
+	}
+
+	let delimiter = quote.as_bytes()[quote.len() - 1];
+	let mut position = value_start;
+
+	while position < bytes.len() && bytes[position] != delimiter {
+		position += 1;
+	}
+
+	let value_end = match quote.len() == 2 && position > value_start && bytes[position - 1] == b'\\'
+	{
+		true => position - 1,
+		false => position,
+	};
+	attribute.value = Some((value_start, value_end));
+
+	(position + 1).min(bytes.len())
+}
+
+// Find end of the brace-delimited expression starting at the given position,
+// skipping over any braces in strings.
+fn find_closing_brace(
+	source: &str,
+	start: usize,
+) -> Option<usize> {
+	let bytes = source.as_bytes();
+	let mut depth: usize = 0;
+	let mut quote: Option<u8> = None;
+	let mut position = start;
+
+	while position < bytes.len() {
+		match (quote, bytes[position]) {
+			(Some(_), b'\\') => position += 1,
+			(Some(delimiter), byte) if byte == delimiter => quote = None,
+			(Some(_), _) => {},
+			(None, byte @ (b'"' | b'\'' | b'`')) => quote = Some(byte),
+			(None, b'{') => depth += 1,
+			(None, b'}') => {
+				depth -= 1;
+				if depth == 0 {
+					return Some(position + 1);
+				}
+			},
+			(None, _) => {},
+		}
+		position += 1;
+	}
+
+	None
+}
+
+// Find end of the next occurrence of the pattern, or the end of source.
+fn find_after(
+	source: &str,
+	start: usize,
+	pattern: &str,
+) -> usize {
+	match source[start..].find(pattern) {
+		Some(offset) => start + offset + pattern.len(),
+		None => source.len(),
+	}
+}
+
+// Find start of the end tag for a raw text element, or the end of source.
+fn find_end_tag(
+	source: &str,
+	start: usize,
+	name: &str,
+) -> usize {
+	let bytes = source.as_bytes();
+	let mut position = start;
+
+	while let Some(offset) = source[position..].find("</") {
+		let tag_start = position + offset;
+		let name_end = tag_start + 2 + name.len();
+
+		if name_end <= bytes.len()
+			&& bytes[tag_start + 2..name_end].eq_ignore_ascii_case(name.as_bytes())
+			&& bytes
+				.get(name_end)
+				.is_none_or(|byte| is_tag_name_end(*byte))
+		{
+			return tag_start;
+		}
+
+		position = tag_start + 2;
+	}
+
+	bytes.len()
+}
+
+fn is_tag_name_end(byte: u8) -> bool {
+	byte.is_ascii_whitespace() || byte == b'/' || byte == b'>'
+}
+
+fn is_attribute_name_end(byte: u8) -> bool {
+	byte.is_ascii_whitespace() || byte == b'/' || byte == b'>' || byte == b'='
+}
@@ -0,0 +1,142 @@
+use onig::*;
+
+use crate::regexes;
+
+
+
+
+// Disabled regions are swapped out for a placeholder comment while the file
+// is being processed, so they are skipped like any other comment. The index
+// of the region is wrapped in characters from the private use area, so that
+// it does not clash with the rest of the file.
+const REGION_PLACEHOLDER: (char, char) = ('\u{E030}', '\u{E031}');
+
+/// Syntax of the file that pragma comments are written in.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CommentSyntax {
+	Markup,
+	Style,
+	Script,
+}
+
+/// Find byte ranges of the regions where processing has been disabled with
+/// pragma comments, i.e. `/* minify-selectors-disable */` up until
+/// `/* minify-selectors-enable */`.
+///
+/// Notes:
+///  - Regions include the pragma comments, and run up until the end of the file
+///    if processing is not enabled again.
+///  - `minify-selectors-disable-next-line` disables the rest of the line the
+///    comment is on, as well as the next line.
+pub fn find_disabled_regions(
+	file_string: &str,
+	syntax: CommentSyntax,
+) -> Vec<(usize, usize)> {
+	let regex: &Regex = match syntax {
+		CommentSyntax::Markup => &regexes::MARKUP_PRAGMAS,
+		CommentSyntax::Style => &regexes::STYLE_PRAGMAS,
+		CommentSyntax::Script => &regexes::SCRIPT_PRAGMAS,
+	};
+	let mut regions: Vec<(usize, usize)> = vec![];
+
+	if !file_string.contains("minify-selectors-") {
+		return regions;
+	}
+
+	let mut disabled_from: Option<usize> = None;
+
+	for capture in regex.captures_iter(file_string) {
+		let (start, end) = capture.pos(0).unwrap();
+
+		// Pragma is inside a region that has been disabled by the next
+		// line pragma.
+		if regions
+			.last()
+			.is_some_and(|(_, region_end)| start < *region_end)
+		{
+			continue;
+		}
+
+		match capture.at(1).or(capture.at(2)) {
+			Some("disable") if disabled_from.is_none() => disabled_from = Some(start),
+			Some("enable") => {
+				if let Some(from) = disabled_from.take() {
+					regions.push((from, end));
+				}
+			},
+			Some("disable-next-line") if disabled_from.is_none() => {
+				let next_line_start = file_string[end..]
+					.find('\n')
+					.map_or(file_string.len(), |offset| end + offset + 1);
+				let next_line_end = file_string[next_line_start..]
+					.find('\n')
+					.map_or(file_string.len(), |offset| next_line_start + offset);
+
+				regions.push((start, next_line_end));
+			},
+			_ => {},
+		}
+	}
+
+	if let Some(from) = disabled_from {
+		regions.push((from, file_string.len()));
+	}
+
+	regions
+}
+
+/// Swap out disabled regions for placeholder comments, returning the masked
+/// file and the original regions. Returns nothing if there are no regions.
+pub fn mask_disabled_regions(
+	file_string: &str,
+	syntax: CommentSyntax,
+) -> Option<(String, Vec<String>)> {
+	let regions = find_disabled_regions(file_string, syntax);
+
+	if regions.is_empty() {
+		return None;
+	}
+
+	let mut masked = String::with_capacity(file_string.len());
+	let mut originals: Vec<String> = vec![];
+	let mut position: usize = 0;
+
+	for (index, (start, end)) in regions.into_iter().enumerate() {
+		masked.push_str(&file_string[position..start]);
+		masked.push_str(&get_placeholder(index, syntax));
+		originals.push(file_string[start..end].to_string());
+		position = end;
+	}
+
+	masked.push_str(&file_string[position..]);
+
+	Some((masked, originals))
+}
+
+/// Swap placeholder comments back to the original disabled regions.
+pub fn unmask_disabled_regions(
+	masked: &str,
+	originals: &[String],
+	syntax: CommentSyntax,
+) -> String {
+	let mut unmasked = masked.to_string();
+
+	for (index, original) in originals.iter().enumerate() {
+		unmasked = unmasked.replacen(&get_placeholder(index, syntax), original, 1);
+	}
+
+	unmasked
+}
+
+// Placeholder comment for the disabled region at the given index.
+fn get_placeholder(
+	index: usize,
+	syntax: CommentSyntax,
+) -> String {
+	let (open, close) = REGION_PLACEHOLDER;
+
+	match syntax {
+		CommentSyntax::Markup => format!("<!--{open}{index}{close}-->"),
+		CommentSyntax::Style | CommentSyntax::Script => format!("/*{open}{index}{close}*/"),
+	}
+}
@@ -1,4 +1,5 @@
 use lazy_static::lazy_static;
+use once_cell::sync::OnceCell;
 use onig::*;
 
 
@@ -6,71 +7,16 @@ use onig::*;
 
 lazy_static! {
 
-	// Extracts specially marked selectors. Selectors have a
-	// minify-selectors specific prefix.
-	//
-	// Example usage and matches:
-	// -  __class--foo
-	// -  __id--foo
-	// -  #__ignore--bar, .__ignore--bar and __ignore--bar
-	// -  #__--baz and .__--baz
-	pub static ref PREFIXED_SELECTORS: Regex = Regex::new(
-		r"(?x)
-			(?:
-				(?<type>[\#\.]?)
-				__
-				(?<context>
-					(?:class | id | ignore)?
-				)
-				--
-			)
-			(?<name>
-				-?
-				(?>
-					[A-Za-z_]
-					| [^\0-\177]
-					| (?>
-						\\[0-9A-Fa-f]{1,6}(?>\r\n|[ \n\r\t\f])?
-						| \\[^\n\r\f0-9A-Fa-f]
-					)
-				)
-				(?>
-					(?!-->)
-					[\w\-]
-					| [^\0-\177]
-					| (?>
-						\\[0-9A-Fa-f]{1,6}(?>\r\n|[ \n\r\t\f])?
-						| \\[^\n\r\f0-9A-Fa-f]
-					)
-				)*
-			)
-		"
-	).unwrap();
-
-	// Extract ID from anchor links.
-	//
-	// Only URLs without the protocol ('http://', 'https://' or '//')
-	// or not prepended with minify-selector specific prefix
-	// will have the inner first and second named capture groups
-	// (url and target_id).
-	pub static ref INTERNAL_ANCHOR_TARGET_ID: Regex = Regex::new(
-		r"(?x)
-			^(?>http:|https:)?\/{2}.*$
-			| ^[^#]*[#]__(?:class | id | ignore)?--
-			| ^(?<url>[^#]*)
-			(?<target_id>\#[^#]*)$
-		"
-	).unwrap();
-
 	// Extract tokens (that are valid selector names) — seperated
-	// by whitespace(s).
+	// by whitespace(s) or template control flow tag placeholders
+	// (see template::mask_template_tags).
 	pub static ref STRING_DELIMITED_BY_SPACE: Regex = Regex::new(
 		r"(?x)
 			(?<token>
 				(?>
 					\\[0-9A-Fa-f]{1,6}(?>\r\n|[ \n\r\t\f])?
 					| \\[^\n\r\f0-9A-Fa-f]
-					| [^\s]
+					| [^\s\x{E002}\x{E003}\x{E020}-\x{E029}]
 				)+
 			)
 		"
@@ -151,4 +97,153 @@ lazy_static! {
 		"#
 	).unwrap();
 
+	// Pragma comments that disable processing in a region, or of the next
+	// line (see: pragmas::find_disabled_regions).
+	//
+	// Example usage and matches:
+	// -  <!-- minify-selectors-disable --> (markup)
+	// -  /* minify-selectors-enable */ (styles and scripts)
+	// -  // minify-selectors-disable-next-line (scripts)
+	pub static ref MARKUP_PRAGMAS: Regex = Regex::new(
+		r"(?x)
+			<!--\s*
+			minify-selectors-(?<pragma>disable-next-line | disable | enable)
+			\s*-->
+		"
+	).unwrap();
+
+	pub static ref STYLE_PRAGMAS: Regex = Regex::new(
+		r"(?x)
+			/\*\s*
+			minify-selectors-(?<pragma>disable-next-line | disable | enable)
+			\s*\*/
+		"
+	).unwrap();
+
+	pub static ref SCRIPT_PRAGMAS: Regex = Regex::new(
+		r"(?x)
+			/\*\s*
+			minify-selectors-(?<pragma>disable-next-line | disable | enable)
+			\s*\*/
+			| //[\ \t]*
+			minify-selectors-(?<line_pragma>disable-next-line | disable | enable)
+			[\ \t]*$
+		"
+	).unwrap();
+
+}
+
+
+
+
+// Extracts specially marked selectors. Selectors have a
+// minify-selectors specific prefix, made up of the start and end
+// markers set in config (`__` and `--` by default).
+//
+// Example usage and matches:
+// -  __class--foo
+// -  __id--foo
+// -  #__ignore--bar, .__ignore--bar and __ignore--bar
+// -  #__--baz and .__--baz
+pub static PREFIXED_SELECTORS: OnceCell<Regex> = OnceCell::new();
+
+// Extract ID from anchor links.
+//
+// Only URLs without the protocol ('http://', 'https://' or '//')
+// or not prepended with minify-selector specific prefix
+// will have the inner first and second named capture groups
+// (url and target_id).
+pub static INTERNAL_ANCHOR_TARGET_ID: OnceCell<Regex> = OnceCell::new();
+
+/// Build the regexes that depend on the markers of prefixed selectors.
+///
+/// Notes:
+///  - Regexes are only built on the first call, markers are then fixed for the
+///    rest of the process and any later markers are ignored.
+pub fn init(markers: &(String, String)) {
+	let (start, end) = (escape_pattern(&markers.0), escape_pattern(&markers.1));
+
+	PREFIXED_SELECTORS.get_or_init(|| {
+		Regex::new(
+			&[
+				r"(?x)
+				(?:
+					(?<type>[\#\.]?)
+					",
+				&start,
+				r"
+					(?<context>
+						(?:class | id | ignore)?
+					)
+					",
+				&end,
+				r"
+				)
+				(?<name>
+					-?
+					(?>
+						[A-Za-z_]
+						| [^\0-\177]
+						| (?>
+							\\[0-9A-Fa-f]{1,6}(?>\r\n|[ \n\r\t\f])?
+							| \\[^\n\r\f0-9A-Fa-f]
+						)
+					)
+					(?>
+						(?!-->)
+						[\w\-]
+						| [^\0-\177]
+						| (?>
+							\\[0-9A-Fa-f]{1,6}(?>\r\n|[ \n\r\t\f])?
+							| \\[^\n\r\f0-9A-Fa-f]
+						)
+					)*
+				)
+				",
+			]
+			.concat(),
+		)
+		.unwrap()
+	});
+
+	INTERNAL_ANCHOR_TARGET_ID.get_or_init(|| {
+		Regex::new(
+			&[
+				r"(?x)
+				^(?>http:|https:)?\/{2}.*$
+				| ^[^#]*[#]",
+				&get_marker_pattern(markers),
+				r"
+				| ^(?<url>[^#]*)
+				(?<target_id>\#[^#]*)$
+				",
+			]
+			.concat(),
+		)
+		.unwrap()
+	});
+}
+
+/// Pattern that matches the prefix of marked selectors without capturing
+/// it, i.e. `__class--`, `__id--`, `__ignore--` or `__--` by default.
+pub fn get_marker_pattern(markers: &(String, String)) -> String {
+	format!(
+		"{start}(?:class | id | ignore)?{end}",
+		start = escape_pattern(&markers.0),
+		end = escape_pattern(&markers.1),
+	)
+}
+
+// Escape characters of a marker so they are matched literally, including
+// whitespace and `#` as patterns are in extended mode.
+fn escape_pattern(string: &str) -> String {
+	string
+		.chars()
+		.map(|character| {
+			match character.is_ascii() && !character.is_ascii_alphanumeric() {
+				true => format!("\\{character}"),
+				false => character.to_string(),
+			}
+		})
+		.collect()
 }
@@ -0,0 +1,715 @@
+use std::collections::HashSet;
+
+
+
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TokenKind {
+	Comment,
+	Identifier,
+	Number,
+	Punctuator,
+	String,
+	Regex,
+	// Template literal with no substitutions, i.e. `foo`.
+	Template,
+	// Template literal pieces either side of substitutions,
+	// i.e. `foo${, }bar${ and }baz` respectively.
+	TemplateHead,
+	TemplateMiddle,
+	TemplateTail,
+	// Attribute name and text content of JSX elements.
+	JsxAttribute,
+	JsxText,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Token {
+	pub kind: TokenKind,
+	pub start: usize,
+	pub end: usize,
+}
+
+impl Token {
+	pub fn text<'a>(
+		&self,
+		source: &'a str,
+	) -> &'a str {
+		&source[self.start..self.end]
+	}
+
+	/// Check if token is a specific punctuator, e.g. `(` or `=`.
+	pub fn is_punctuator(
+		&self,
+		source: &str,
+		punctuator: &str,
+	) -> bool {
+		self.kind == TokenKind::Punctuator && self.text(source) == punctuator
+	}
+
+	/// Check if token is a string or a template literal (or piece of one).
+	pub fn is_string_like(&self) -> bool {
+		matches!(
+			self.kind,
+			TokenKind::String
+				| TokenKind::Template
+				| TokenKind::TemplateHead
+				| TokenKind::TemplateMiddle
+				| TokenKind::TemplateTail
+		)
+	}
+
+	/// Check if a string or template literal (or the last piece of one)
+	/// has its closing delimiter, unterminated ones end at the end of the
+	/// line or file instead.
+	pub fn is_closed(
+		&self,
+		source: &str,
+	) -> bool {
+		let text = self.text(source).as_bytes();
+		let delimiter = match self.kind {
+			TokenKind::String => text[0],
+			TokenKind::Template | TokenKind::TemplateTail => b'`',
+			_ => return true,
+		};
+
+		text.len() >= 2
+			&& text[text.len() - 1] == delimiter
+			// Delimiter is not escaped by an odd number of backslashes.
+			&& text[1..text.len() - 1]
+				.iter()
+				.rev()
+				.take_while(|byte| **byte == b'\\')
+				.count() % 2 == 0
+	}
+}
+
+// Keywords after which a forward slash starts a regular
+// expression literal rather than being a division operator.
+const KEYWORDS_BEFORE_EXPRESSION: [&str; 14] = [
+	"await",
+	"case",
+	"delete",
+	"do",
+	"else",
+	"in",
+	"instanceof",
+	"new",
+	"of",
+	"return",
+	"throw",
+	"typeof",
+	"void",
+	"yield",
+];
+
+// Punctuators, longest first so that they are matched greedily.
+const PUNCTUATORS: [&str; 54] = [
+	">>>=", "...", "===", "!==", "**=", "<<=", ">>=", ">>>", "&&=", "||=", "??=", "=>", "==", "!=",
+	"<=", ">=", "&&", "||", "??", "?.", "++", "--", "+=", "-=", "*=", "/=", "%=", "&=", "|=", "^=",
+	"**", "<<", ">>", "{", "}", "(", ")", "[", "]", ";", ",", "<", ">", "+", "-", "*", "/", "%",
+	"&", "|", "^", "!", "~", "?",
+];
+
+// How deep JSX elements can be nested, as unclosed type assertions
+// (i.e. `<T>a;` on every line) would otherwise each start a new level.
+const MAX_JSX_DEPTH: usize = 256;
+
+#[derive(PartialEq)]
+enum Brace {
+	Block,
+	Substitution,
+	// Expression container in a JSX element, i.e. {foo}
+	JsxExpression,
+}
+
+struct Lexer<'a> {
+	source: &'a str,
+	bytes: &'a [u8],
+	position: usize,
+	tokens: Vec<Token>,
+	braces: Vec<Brace>,
+	// Positions where a JSX element was tried but turned out not to be one,
+	// so that it is not scanned again.
+	failed_jsx: HashSet<usize>,
+	jsx_depth: usize,
+}
+
+/// Split JS source into tokens, so that strings, comments, regular expression
+/// literals and template literals can be told apart from code.
+///
+/// Notes:
+///  - Whitespace is not included. Tokens only note down their byte offsets in
+///    the source, any rewrites can be spliced back in without touching the rest
+///    of the script.
+///  - Lexing is lenient, unterminated strings, comments, etc. run to the end of
+///    the line or file instead of raising an error.
+///  - JSX elements are picked up where an expression may start. If the element
+///    is not closed, the less-than sign is treated as an operator instead (i.e.
+///    a TypeScript type assertion or generic arrow function).
+pub fn tokenize(source: &str) -> Vec<Token> {
+	let mut lexer = Lexer {
+		source,
+		bytes: source.as_bytes(),
+		position: 0,
+		tokens: vec![],
+		braces: vec![],
+		failed_jsx: HashSet::new(),
+		jsx_depth: 0,
+	};
+
+	lexer.run();
+	lexer.tokens
+}
+
+impl<'a> Lexer<'a> {
+	fn run(&mut self) {
+		// Hashbang comment
+		if self.source.starts_with("#!") {
+			let end = self.find_line_end(0);
+			self.push(TokenKind::Comment, 0, end);
+		}
+
+		self.scan_code();
+	}
+
+	// Scan code up until the end of source, or the closing brace of a JSX
+	// expression container (in which case returns true).
+	fn scan_code(&mut self) -> bool {
+		while self.position < self.bytes.len() {
+			let start = self.position;
+			let byte = self.bytes[start];
+
+			match byte {
+				b' ' | b'\t' | b'\n' | b'\r' | b'\x0b' | b'\x0c' => {
+					self.position += 1;
+				},
+
+				b'/' if self.peek(1) == Some(b'/') => {
+					let end = self.find_line_end(start);
+					self.push(TokenKind::Comment, start, end);
+				},
+
+				b'/' if self.peek(1) == Some(b'*') => {
+					let end = match self.source[start + 2..].find("*/") {
+						Some(offset) => start + 2 + offset + 2,
+						None => self.bytes.len(),
+					};
+					self.push(TokenKind::Comment, start, end);
+				},
+
+				b'/' if self.is_regex_allowed() => {
+					let end = self.scan_regex(start);
+					self.push(TokenKind::Regex, start, end);
+				},
+
+				b'\'' | b'"' => {
+					let end = self.scan_string(start, byte);
+					self.push(TokenKind::String, start, end);
+				},
+
+				b'`' => {
+					self.scan_template(start, true);
+				},
+
+				b'}' if self.braces.last() == Some(&Brace::Substitution) => {
+					self.braces.pop();
+					self.scan_template(start, false);
+				},
+
+				b'}' if self.braces.last() == Some(&Brace::JsxExpression) => {
+					self.braces.pop();
+					self.push(TokenKind::Punctuator, start, start + 1);
+					return true;
+				},
+
+				b'<' if self.is_regex_allowed() && self.scan_jsx_element(start) => {},
+
+				b'0'..=b'9' => {
+					let end = self.scan_number(start);
+					self.push(TokenKind::Number, start, end);
+				},
+
+				b'.' if matches!(self.peek(1), Some(b'0'..=b'9')) => {
+					let end = self.scan_number(start);
+					self.push(TokenKind::Number, start, end);
+				},
+
+				_ if is_identifier_start(self.char_at(start)) || byte == b'#' => {
+					let end = self.scan_identifier(start + self.char_at(start).len_utf8());
+					self.push(TokenKind::Identifier, start, end);
+				},
+
+				_ => {
+					let end = self.scan_punctuator(start);
+
+					match &self.source[start..end] {
+						"{" => self.braces.push(Brace::Block),
+						"}" => {
+							self.braces.pop();
+						},
+						_ => {},
+					}
+
+					self.push(TokenKind::Punctuator, start, end);
+				},
+			}
+		}
+
+		false
+	}
+
+	// Try to scan a JSX element, if it turns out not to be one then
+	// any tokens are discarded and returns false.
+	fn scan_jsx_element(
+		&mut self,
+		start: usize,
+	) -> bool {
+		// Element name or a fragment
+		match self.peek(1) {
+			Some(b'>') => {},
+			Some(_) if is_identifier_start(self.char_at(start + 1)) => {},
+			_ => return false,
+		}
+		if self.failed_jsx.contains(&start) || self.jsx_depth == MAX_JSX_DEPTH {
+			return false;
+		}
+
+		let tokens_length = self.tokens.len();
+		let braces_length = self.braces.len();
+
+		self.jsx_depth += 1;
+		let is_element = self.scan_jsx(start);
+		self.jsx_depth -= 1;
+
+		if is_element {
+			return true;
+		}
+
+		self.tokens.truncate(tokens_length);
+		self.braces.truncate(braces_length);
+		self.failed_jsx.insert(start);
+		self.position = start;
+		false
+	}
+
+	fn scan_jsx(
+		&mut self,
+		start: usize,
+	) -> bool {
+		self.push(TokenKind::Punctuator, start, start + 1);
+
+		let name_end = self.scan_jsx_name(self.position);
+		let name = &self.source[self.position..name_end];
+		if !name.is_empty() {
+			self.push(TokenKind::Identifier, self.position, name_end);
+		}
+
+		// Attributes
+		loop {
+			self.skip_whitespace_and_comments();
+
+			let position = self.position;
+
+			match self.bytes.get(position) {
+				None => return false,
+				Some(b'/') if self.peek(1) == Some(b'>') => {
+					self.push(TokenKind::Punctuator, position, position + 2);
+					return true;
+				},
+				Some(b'>') => {
+					self.push(TokenKind::Punctuator, position, position + 1);
+					break;
+				},
+				// Spread attributes
+				Some(b'{') => {
+					if !self.scan_jsx_expression(position) {
+						return false;
+					}
+				},
+				Some(_) if is_identifier_start(self.char_at(position)) => {
+					let attribute_end = self.scan_jsx_name(position);
+					self.push(TokenKind::JsxAttribute, position, attribute_end);
+					self.skip_whitespace_and_comments();
+
+					if self.peek(0) != Some(b'=') {
+						continue;
+					}
+
+					self.push(TokenKind::Punctuator, self.position, self.position + 1);
+					self.skip_whitespace_and_comments();
+
+					let value_start = self.position;
+
+					match self.peek(0) {
+						// Strings in JSX do not have escape sequences.
+						Some(quote @ b'"') | Some(quote @ b'\'') => {
+							let Some(offset) = self.bytes[value_start + 1..]
+								.iter()
+								.position(|byte| *byte == quote)
+							else {
+								return false;
+							};
+							self.push(TokenKind::String, value_start, value_start + offset + 2);
+						},
+						Some(b'{') => {
+							if !self.scan_jsx_expression(value_start) {
+								return false;
+							}
+						},
+						Some(b'<') => {
+							if !self.scan_jsx_element(value_start) {
+								return false;
+							}
+						},
+						_ => return false,
+					}
+				},
+				_ => return false,
+			}
+		}
+
+		// Children
+		loop {
+			let position = self.position;
+
+			match self.bytes.get(position) {
+				None => return false,
+				Some(b'<') if self.peek(1) == Some(b'/') => {
+					self.push(TokenKind::Punctuator, position, position + 2);
+					self.skip_whitespace_and_comments();
+
+					let closing_name_end = self.scan_jsx_name(self.position);
+					if self.source[self.position..closing_name_end] != *name {
+						return false;
+					}
+					if closing_name_end > self.position {
+						self.push(TokenKind::Identifier, self.position, closing_name_end);
+					}
+					self.skip_whitespace_and_comments();
+
+					if self.peek(0) != Some(b'>') {
+						return false;
+					}
+					self.push(TokenKind::Punctuator, self.position, self.position + 1);
+					return true;
+				},
+				Some(b'<') => {
+					if !self.scan_jsx_element(position) {
+						return false;
+					}
+				},
+				Some(b'{') => {
+					if !self.scan_jsx_expression(position) {
+						return false;
+					}
+				},
+				Some(_) => {
+					let text_end = match self.source[position..].find(['<', '{', '>', '}']) {
+						Some(offset) => position + offset,
+						None => self.bytes.len(),
+					};
+					// Text cannot contain a greater-than sign or closing brace,
+					// so it is code after a type assertion (i.e. `<T>a > b`).
+					if matches!(self.bytes.get(text_end), Some(b'>') | Some(b'}')) {
+						return false;
+					}
+					self.push(TokenKind::JsxText, position, text_end);
+				},
+			}
+		}
+	}
+
+	// Scan expression container, returns false if it is not closed.
+	fn scan_jsx_expression(
+		&mut self,
+		start: usize,
+	) -> bool {
+		self.braces.push(Brace::JsxExpression);
+		self.push(TokenKind::Punctuator, start, start + 1);
+		self.scan_code()
+	}
+
+	// Scan JSX element or attribute name, which may also contain hyphens,
+	// colons (namespaces) and periods (member expressions).
+	fn scan_jsx_name(
+		&self,
+		start: usize,
+	) -> usize {
+		let mut index = start;
+
+		while index < self.bytes.len() {
+			let character = self.char_at(index);
+
+			if !is_identifier_part(character) && !matches!(character, '-' | ':' | '.') {
+				break;
+			}
+			index += character.len_utf8();
+		}
+
+		index
+	}
+
+	fn skip_whitespace_and_comments(&mut self) {
+		loop {
+			while self.peek(0).is_some_and(|byte| byte.is_ascii_whitespace()) {
+				self.position += 1;
+			}
+
+			let start = self.position;
+
+			if self.source[start..].starts_with("//") {
+				let end = self.find_line_end(start);
+				self.push(TokenKind::Comment, start, end);
+			} else if self.source[start..].starts_with("/*") {
+				let end = match self.source[start + 2..].find("*/") {
+					Some(offset) => start + 2 + offset + 2,
+					None => self.bytes.len(),
+				};
+				self.push(TokenKind::Comment, start, end);
+			} else {
+				return;
+			}
+		}
+	}
+
+	fn push(
+		&mut self,
+		kind: TokenKind,
+		start: usize,
+		end: usize,
+	) {
+		self.tokens.push(Token { kind, start, end });
+		self.position = end;
+	}
+
+	fn peek(
+		&self,
+		offset: usize,
+	) -> Option<u8> {
+		self.bytes.get(self.position + offset).copied()
+	}
+
+	fn char_at(
+		&self,
+		index: usize,
+	) -> char {
+		self.source[index..].chars().next().unwrap_or('\0')
+	}
+
+	fn find_line_end(
+		&self,
+		start: usize,
+	) -> usize {
+		match self.source[start..].find(['\n', '\r', '\u{2028}', '\u{2029}']) {
+			Some(offset) => start + offset,
+			None => self.bytes.len(),
+		}
+	}
+
+	// Work out if a forward slash would begin a regular expression literal,
+	// based on what the previous (non-comment) token was.
+	fn is_regex_allowed(&self) -> bool {
+		let previous = self
+			.tokens
+			.iter()
+			.rev()
+			.find(|token| token.kind != TokenKind::Comment);
+
+		match previous {
+			None => true,
+			Some(token) => {
+				match token.kind {
+					TokenKind::Punctuator => !matches!(token.text(self.source), ")" | "]"),
+					TokenKind::Identifier => {
+						KEYWORDS_BEFORE_EXPRESSION.contains(&token.text(self.source))
+					},
+					_ => false,
+				}
+			},
+		}
+	}
+
+	fn scan_regex(
+		&self,
+		start: usize,
+	) -> usize {
+		let mut index = start + 1;
+		let mut in_class = false;
+
+		while index < self.bytes.len() {
+			match self.bytes[index] {
+				b'\\' => index += 1,
+				b'[' => in_class = true,
+				b']' => in_class = false,
+				b'/' if !in_class => {
+					index += 1;
+					break;
+				},
+				b'\n' | b'\r' => break,
+				_ => {},
+			}
+			index += 1;
+		}
+
+		// Flags
+		while index < self.bytes.len() && self.bytes[index].is_ascii_alphabetic() {
+			index += 1;
+		}
+
+		index.min(self.bytes.len())
+	}
+
+	fn scan_string(
+		&self,
+		start: usize,
+		quote: u8,
+	) -> usize {
+		let mut index = start + 1;
+
+		while index < self.bytes.len() {
+			match self.bytes[index] {
+				// Skips over escaped characters, including line continuations.
+				b'\\' => {
+					index += if self.source[index + 1..].starts_with("\r\n") {
+						3
+					} else {
+						2
+					};
+					continue;
+				},
+				byte if byte == quote => return index + 1,
+				b'\n' | b'\r' => return index,
+				_ => {},
+			}
+			index += 1;
+		}
+
+		self.bytes.len()
+	}
+
+	// Scan a template literal (or the remaining piece of one) up until its
+	// closing backtick or the next substitution.
+	fn scan_template(
+		&mut self,
+		start: usize,
+		is_opening: bool,
+	) {
+		let mut index = start + 1;
+
+		while index < self.bytes.len() {
+			match self.bytes[index] {
+				b'\\' => index += 1,
+				b'`' => {
+					let kind = match is_opening {
+						true => TokenKind::Template,
+						false => TokenKind::TemplateTail,
+					};
+					self.push(kind, start, index + 1);
+					return;
+				},
+				b'$' if self.bytes.get(index + 1) == Some(&b'{') => {
+					let kind = match is_opening {
+						true => TokenKind::TemplateHead,
+						false => TokenKind::TemplateMiddle,
+					};
+					self.braces.push(Brace::Substitution);
+					self.push(kind, start, index + 2);
+					return;
+				},
+				_ => {},
+			}
+			index += 1;
+		}
+
+		let kind = match is_opening {
+			true => TokenKind::Template,
+			false => TokenKind::TemplateTail,
+		};
+		self.push(kind, start, self.bytes.len());
+	}
+
+	fn scan_number(
+		&self,
+		start: usize,
+	) -> usize {
+		let mut index = start;
+
+		while index < self.bytes.len() {
+			match self.bytes[index] {
+				b'0'..=b'9' | b'a'..=b'z' | b'A'..=b'Z' | b'_' | b'.' => {},
+				// Exponent sign
+				b'+' | b'-'
+					if matches!(self.bytes[index - 1], b'e' | b'E')
+						&& !self.source[start..index].starts_with("0x")
+						&& !self.source[start..index].starts_with("0X") => {},
+				_ => break,
+			}
+			index += 1;
+		}
+
+		index
+	}
+
+	fn scan_identifier(
+		&self,
+		start: usize,
+	) -> usize {
+		let mut index = start;
+
+		while index < self.bytes.len() {
+			let character = self.char_at(index);
+
+			if !is_identifier_part(character) {
+				break;
+			}
+			index += character.len_utf8();
+		}
+
+		index
+	}
+
+	fn scan_punctuator(
+		&self,
+		start: usize,
+	) -> usize {
+		let remaining = &self.source[start..];
+
+		for punctuator in PUNCTUATORS {
+			// Optional chaining is not followed by a digit, otherwise it is a
+			// conditional operator followed by a decimal number (i.e. a?.5:b).
+			if punctuator == "?."
+				&& remaining.starts_with("?.")
+				&& matches!(remaining.as_bytes().get(2), Some(b'0'..=b'9'))
+			{
+				continue;
+			}
+			if remaining.starts_with(punctuator) {
+				return start + punctuator.len();
+			}
+		}
+
+		// Any other single character (., =, :, @, etc.)
+		start + self.char_at(start).len_utf8()
+	}
+}
+
+/// Check if the name is a valid identifier, i.e. can be used as an
+/// object key without quotes.
+pub fn is_identifier(name: &str) -> bool {
+	let mut characters = name.chars();
+
+	characters.next().is_some_and(is_identifier_start) && characters.all(is_identifier_part)
+}
+
+fn is_identifier_start(character: char) -> bool {
+	character == '$' || character == '_' || character.is_alphabetic()
+}
+
+fn is_identifier_part(character: char) -> bool {
+	character == '$'
+		|| character == '_'
+		|| character == '\u{200c}'
+		|| character == '\u{200d}'
+		|| character.is_alphanumeric()
+}
@@ -1,12 +1,217 @@
+pub mod lexer;
 pub mod regexes;
 
+use std::borrow::Cow;
+
 use minify_selectors_utils::*;
 use onig::*;
 
-use crate::markup::html_attributes::WHITELIST;
+use crate::markup::html_attributes::get_attribute_designation;
+use crate::markup::html_attributes::Element;
+use crate::markup::json::*;
+use crate::markup::smil::*;
 use crate::markup::*;
+use crate::pragmas::*;
+use crate::script::lexer::Token;
+use crate::script::lexer::TokenKind;
 use crate::script::regexes as script_regex;
 use crate::style::*;
+use crate::template::join_with_placeholders;
+use crate::template::split_at_placeholders;
+use crate::Replacement;
+
+
+
+
+// Functions that take classes, IDs, shadow part names, URL (which may have
+// a target ID), a CSS selector string, XPath expression, HTML or CSS.
+//
+// Names with a leading period are methods that can be called on any object,
+// otherwise the object needs to match as well (e.g. `window.open`).
+const FUNCTIONS: [&str; 35] = [
+	".insertAdjacentHTML",
+	".parseFromString",
+	".parseHTMLUnsafe",
+	".createContextualFragment",
+	".setHTMLUnsafe",
+	".setHTML",
+	"document.write",
+	"document.writeln",
+	".insertRule",
+	".replaceSync",
+	".evaluate",
+	".createExpression",
+	".querySelectorAll",
+	".querySelector",
+	".closest",
+	".matches",
+	".getElementById",
+	".getElementsByClassName",
+	".classList.add",
+	".classList.remove",
+	".classList.contains",
+	".classList.replace",
+	".classList.toggle",
+	".part.add",
+	".part.remove",
+	".part.contains",
+	".part.replace",
+	".part.toggle",
+	".setAttribute",
+	".setAttributeNS",
+	"history.pushState",
+	"history.replaceState",
+	"window.location.assign",
+	"window.location.replace",
+	"window.open",
+];
+
+// jQuery (and Zepto) functions that take classes, a CSS selector string
+// or HTML, only processed when enabled in config.
+const JQUERY_FUNCTIONS: [&str; 31] = [
+	"$",
+	"jQuery",
+	".find",
+	".filter",
+	".not",
+	".is",
+	".has",
+	".children",
+	".siblings",
+	".parent",
+	".parents",
+	".parentsUntil",
+	".next",
+	".nextAll",
+	".nextUntil",
+	".prev",
+	".prevAll",
+	".prevUntil",
+	".on",
+	".one",
+	".off",
+	".html",
+	".append",
+	".prepend",
+	".after",
+	".before",
+	".addClass",
+	".removeClass",
+	".toggleClass",
+	".hasClass",
+	".attr",
+];
+
+// Properties that are assigned or compared to a string of classes,
+// an ID, URL, HTML or CSS.
+const PROPERTIES: [&str; 13] = [
+	"window.location.hash",
+	"window.location.href",
+	"window.location",
+	".id",
+	".htmlFor",
+	".className",
+	".classList",
+	".classList.value",
+	".classList.item",
+	".part.value",
+	".innerHTML",
+	".outerHTML",
+	".textContent",
+];
+
+const PROPERTY_OPERATORS: [&str; 7] = ["=", "+=", "-=", "==", "===", "!=", "!=="];
+
+// Properties that are accessed by ID with bracket notation.
+const BRACKET_ACCESSORS: [&str; 1] = [".children"];
+
+// Comments that annotate the string literal after them, along with
+// what the string contains, i.e. `/* @class */ 'foo'`.
+const STRING_ANNOTATIONS: [(&str, &str); 3] = [
+	("@class", "class"),
+	("@id", "id"),
+	("@selector", "selector"),
+];
+
+// Tags of template literals that contain HTML or CSS, on top of any set
+// in config. Tags match member expressions that end with, or start with
+// the tag name, i.e. `lit.html` or `styled.div`, as well as calls to them
+// (i.e. `styled(Button)`).
+const TEMPLATE_TAGS: [(&str, &str); 6] = [
+	("html", "markup"),
+	("svg", "markup"),
+	("css", "css"),
+	("styled", "css"),
+	("createGlobalStyle", "css"),
+	("injectGlobal", "css"),
+];
+
+// JSX attributes that are named differently from their HTML counterparts,
+// any other attribute names are matched case-insensitively.
+const JSX_ATTRIBUTES: [(&str, &str); 3] = [
+	("className", "class"),
+	("htmlFor", "for"),
+	("xlinkHref", "xlink:href"),
+];
+
+/// Function argument or property value in a script.
+#[derive(Clone, Copy, Debug)]
+pub struct Argument {
+	pub start: usize,
+	pub end: usize,
+	pub kind: ArgumentKind,
+}
+
+/// JSX element, with its tag name and the attributes that have a string
+/// value (using the equivalent HTML attribute names).
+#[derive(Debug)]
+pub struct JsxElement<'a> {
+	pub tag: &'a str,
+	pub attributes: Vec<(String, Argument)>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ArgumentKind {
+	// String or template literal without any substitutions.
+	String,
+	// Template literal with substitutions.
+	Template,
+	// Variables, expressions, objects, etc.
+	Expression,
+	// Object literal key that is not quoted, i.e. `{ foo: true }`.
+	Key,
+}
+
+impl Argument {
+	/// Argument as it is written in the script.
+	pub fn text<'a>(
+		&self,
+		source: &'a str,
+	) -> &'a str {
+		&source[self.start..self.end]
+	}
+
+	/// String or template literal without its delimiters.
+	pub fn value<'a>(
+		&self,
+		source: &'a str,
+	) -> &'a str {
+		let text = self.text(source);
+
+		if self.kind == ArgumentKind::Key {
+			return text;
+		}
+
+		match text.len() >= 2 && text.ends_with(&text[..1]) {
+			true => &text[1..text.len() - 1],
+			false => &text[1..],
+		}
+	}
+
+	pub fn is_literal(&self) -> bool {
+		self.kind == ArgumentKind::String
+	}
+}
 
 
 
@@ -17,9 +222,18 @@ pub fn analyse_js(
 	selectors: &mut Selectors,
 	config: &Config,
 ) {
+	if let Some((mut masked, _)) = mask_disabled_regions(file_string, CommentSyntax::Script) {
+		return analyse_js(&mut masked, selectors, config);
+	}
+
 	analyse_js_arguments(file_string, selectors, config);
+	analyse_js_custom_functions(file_string, selectors, config);
+	analyse_js_jquery(file_string, selectors, config);
 	analyse_js_properties(file_string, selectors, config);
 	analyse_js_bracket_accessors(file_string, selectors);
+	analyse_js_annotated_strings(file_string, selectors, config);
+	analyse_jsx_attributes(file_string, selectors, config);
+	analyse_js_tagged_templates(file_string, selectors, config);
 	super::analyse_prefixed_selectors(file_string, selectors);
 }
 
@@ -29,9 +243,20 @@ pub fn rewrite_js(
 	selectors: &Selectors,
 	config: &Config,
 ) {
+	if let Some((mut masked, regions)) = mask_disabled_regions(file_string, CommentSyntax::Script) {
+		rewrite_js(&mut masked, selectors, config);
+		*file_string = unmask_disabled_regions(&masked, &regions, CommentSyntax::Script);
+		return;
+	}
+
 	rewrite_js_arguments(file_string, selectors, config);
+	rewrite_js_custom_functions(file_string, selectors, config);
+	rewrite_js_jquery(file_string, selectors, config);
 	rewrite_js_properties(file_string, selectors, config);
 	rewrite_js_bracket_accessors(file_string, selectors);
+	rewrite_js_annotated_strings(file_string, selectors, config);
+	rewrite_jsx_attributes(file_string, selectors, config);
+	rewrite_js_tagged_templates(file_string, selectors, config);
 	super::rewrite_prefixed_selectors(file_string, selectors);
 }
 
@@ -42,166 +267,163 @@ pub fn analyse_js_arguments(
 	selectors: &mut Selectors,
 	config: &Config,
 ) {
-	for capture in script_regex::JS_ARGUMENTS.captures_iter(file_string) {
-		// Matched string is a multiline or single line comment
-		// i.e. it does not have any further capture groups
-		if capture.at(1).is_none() {
-			continue;
-		}
-
-		let mut replacement_args: String = unescape_js_chars(capture.at(3).unwrap());
-		let mut function = capture.at(1).unwrap().to_owned();
-		function.retain(|c| !c.is_whitespace());
+	let tokens = tokenize_js(file_string);
 
+	for (index, function, arguments) in
+		find_indexed_function_calls(file_string, &tokens, &FUNCTIONS)
+	{
 		// Work out function call and its argument pattern:
-		match function.as_str() {
+		match function {
 			// Takes one argument, an CSS selector string.
 			".querySelector" | ".querySelectorAll" | ".closest" | ".matches" => {
-				// FIXME: rudimentary way to check if arg is a immediate
-				// string value rather than some expression.
-				let quote_type: &str = match replacement_args.chars().next() {
-					Some('\'') => "'",
-					Some('"') => "\"",
-					Some('`') => "`",
-					_ => "",
-				};
-
-				// Remove any additional backslash in JS selector strings.
-				replacement_args = replacement_args.replace("\\\\", "\\");
-
-				if !quote_type.is_empty() {
-					super::analyse_css(&mut replacement_args, selectors, config);
+				if let Some(argument) = arguments
+					.first()
+					.filter(|argument| argument.kind != ArgumentKind::Expression)
+				{
+					// Remove any additional backslash in JS selector strings.
+					let mut selector =
+						unescape_js_chars(argument.value(file_string)).replace("\\\\", "\\");
+					super::analyse_css(&mut selector, selectors, config);
 				}
 			},
 
 			// Takes one argument, a string of classes (no period prefixed)
 			// separated by spaces (if more than one) —
 			".getElementsByClassName" => {
-				// Checking that argument is a string
-				if capture.at(4).is_some() {
+				if let Some(argument) = arguments.first().filter(|argument| argument.is_literal()) {
 					super::analyse_string_of_tokens(
-						&mut replacement_args,
+						&mut unescape_js_chars(argument.text(file_string)),
 						selectors,
 						"class",
 						Some(SelectorUsage::Script),
 					);
 				}
-				// TODO: handle expressions?
 			},
 
 			// Takes one argument, an ID (no hash prefixed).
 			".getElementById" => {
-				// Checking that argument is a string
-				if capture.at(4).is_some() {
+				if let Some(argument) = arguments.first().filter(|argument| argument.is_literal()) {
 					super::analyse_string_of_tokens(
-						&mut replacement_args,
+						&mut unescape_js_chars(argument.text(file_string)),
 						selectors,
 						"id",
 						Some(SelectorUsage::Script),
 					);
-				};
-				// TODO: handle expressions?
+				}
 			},
 
 			// Takes two arguments: attribute name and value,
 			// process value if attribute is whitelisted.
 			".setAttribute" => {
-				// Go over the (two) function arguments
-				let mut function_args = super::get_function_arguments(&replacement_args);
-
-				// Check first arg in function, without the string delimiters
-				// and then trimming any whitespace off ends.
-				let attribute_name: &str = function_args.next().unwrap().at(3).unwrap_or("").trim();
-
-				// Check first argument is an known attribute which its value will have
-				// classses or an id. If it is not, leave value as is (second argument).
-				if WHITELIST.get().unwrap().contains_key(attribute_name) {
-					if let Some(attribute_value) = function_args.next() {
-						if attribute_value.at(3).is_some() {
-							let mut replacement_value = attribute_value.at(3).unwrap().to_string();
-							let attribute_type_designation: &str =
-								WHITELIST.get().unwrap().get(attribute_name).unwrap();
-
-
-							match attribute_type_designation {
-								"id" | "class" => {
-									super::analyse_string_of_tokens(
-										&mut replacement_value,
-										selectors,
-										attribute_type_designation,
-										Some(SelectorUsage::Script),
-									);
-								},
-
-								"selector" => {
-									super::analyse_css(&mut replacement_value, selectors, config);
-								},
-
-								"style" => {
-									analyse_css_functions(&mut replacement_value, selectors);
-								},
-
-								"script" => {
-									analyse_js(&mut replacement_value, selectors, config);
-								},
-
-								"anchor" => {
-									super::analyse_anchor_links(&mut replacement_value, selectors);
-								},
-
-								_ => continue,
-							};
-						}
-					}
+				if let (Some(name), Some(value)) = (arguments.first(), arguments.get(1)) {
+					analyse_attribute_arguments(file_string, name, value, selectors, config);
+				}
+			},
+
+			// Takes three arguments: namespace, attribute name and value,
+			// i.e. `xlink:href` on SVG elements.
+			".setAttributeNS" => {
+				if let (Some(name), Some(value)) = (arguments.get(1), arguments.get(2)) {
+					analyse_attribute_arguments(file_string, name, value, selectors, config);
 				}
 			},
 
 			// Takes two arguments: position and html,
 			// we are only interested in the latter argument.
 			".insertAdjacentHTML" => {
-				if let Some(html) = super::get_function_arguments(&replacement_args).nth(1) {
-					// Third capture group, which should be just the string (without the
-					// delimeters).
-					if html.at(3).is_some() {
-						let mut replacement_html = html.at(3).unwrap().to_string();
-
-						match html.at(3).unwrap().contains("</body>") {
-							true => {
-								super::analyse_html(
-									&mut replacement_html,
-									selectors,
-									config,
-									Some(SelectorUsage::Script),
-								)
-							},
-							false => {
-								analyse_html_attributes(
-									&mut replacement_html,
-									selectors,
-									config,
-									Some(SelectorUsage::Script),
-								)
-							},
-						};
-					}
+				if let Some(argument) = arguments
+					.get(1)
+					.filter(|argument| argument.kind != ArgumentKind::Expression)
+				{
+					analyse_html_string(
+						&mut get_embedded_value(file_string, &tokens, argument),
+						selectors,
+						config,
+					);
+				}
+			},
+
+			// Takes one argument, HTML (or a HTML document).
+			".parseFromString"
+			| ".parseHTMLUnsafe"
+			| ".createContextualFragment"
+			| ".setHTMLUnsafe"
+			| ".setHTML" => {
+				if let Some(argument) = arguments
+					.first()
+					.filter(|argument| argument.kind != ArgumentKind::Expression)
+				{
+					analyse_html_string(
+						&mut get_embedded_value(file_string, &tokens, argument),
+						selectors,
+						config,
+					);
+				}
+			},
+
+			// Takes one or more arguments, each of which is HTML.
+			"document.write" | "document.writeln" => {
+				for argument in arguments
+					.iter()
+					.filter(|argument| argument.kind != ArgumentKind::Expression)
+				{
+					analyse_html_string(
+						&mut get_embedded_value(file_string, &tokens, argument),
+						selectors,
+						config,
+					);
+				}
+			},
+
+			// Takes an XPath expression (and the context node, etc.), we are
+			// only interested in argument number 1.
+			// Only on documents or XPath evaluators, as other libraries have
+			// `evaluate` methods too (e.g. `page.evaluate()` of Playwright).
+			".evaluate" | ".createExpression"
+				if is_xpath_evaluator(file_string, &tokens, index, &arguments) =>
+			{
+				if let Some(argument) = arguments
+					.first()
+					.filter(|argument| argument.kind != ArgumentKind::Expression)
+				{
+					super::analyse_xpath(&get_xpath(file_string, &tokens, argument), selectors);
+				}
+			},
+
+			// Takes a CSS rule (and an optional index) or a stylesheet, we are
+			// only interested in argument number 1.
+			".insertRule" | ".replaceSync" => {
+				if let Some(argument) = arguments
+					.first()
+					.filter(|argument| argument.kind != ArgumentKind::Expression)
+				{
+					super::analyse_css(
+						&mut get_embedded_value(file_string, &tokens, argument),
+						selectors,
+						config,
+					);
 				}
 			},
 
 			// Takes either only one argument or up to two arguments:
 			// we are only ever interested in argument number 1.
 			"window.open" | "window.location.assign" | "window.location.replace" => {
-				if let Some(link) = super::get_function_arguments(&replacement_args).next() {
-					let mut replacement_link = link.at(0).unwrap().to_string();
-					super::analyse_anchor_links(&mut replacement_link, selectors);
+				if let Some(argument) = arguments.first().filter(|argument| argument.is_literal()) {
+					super::analyse_anchor_links(
+						&mut unescape_js_chars(argument.text(file_string)),
+						selectors,
+					);
 				}
 			},
 
 			// Takes two or three arguments, the final argument which
 			// is an optional URL is the one that we are interested in.
 			"history.pushState" | "history.replaceState" => {
-				if let Some(link) = super::get_function_arguments(&replacement_args).nth(2) {
-					let mut replacement_link = link.at(0).unwrap().to_string();
-					super::analyse_anchor_links(&mut replacement_link, selectors);
+				if let Some(argument) = arguments.get(2).filter(|argument| argument.is_literal()) {
+					super::analyse_anchor_links(
+						&mut unescape_js_chars(argument.text(file_string)),
+						selectors,
+					);
 				}
 			},
 
@@ -212,12 +434,26 @@ pub fn analyse_js_arguments(
 			| ".classList.remove"
 			| ".classList.replace"
 			| ".classList.toggle" => {
-				super::analyse_string_of_arguments(
-					&mut replacement_args,
-					selectors,
-					"class",
-					Some(SelectorUsage::Script),
-				);
+				for argument in arguments.iter().filter(|argument| argument.is_literal()) {
+					super::analyse_string_of_arguments(
+						&mut unescape_js_chars(argument.text(file_string)),
+						selectors,
+						"class",
+						Some(SelectorUsage::Script),
+					);
+				}
+			},
+
+			// Same as `classList`, but for shadow part names.
+			".part.add" | ".part.contains" | ".part.remove" | ".part.replace" | ".part.toggle" => {
+				for argument in arguments.iter().filter(|argument| argument.is_literal()) {
+					super::analyse_string_of_arguments(
+						&mut unescape_js_chars(argument.text(file_string)),
+						selectors,
+						"part",
+						Some(SelectorUsage::Script),
+					);
+				}
 			},
 
 			_ => {},
@@ -231,153 +467,223 @@ pub fn rewrite_js_arguments(
 	selectors: &Selectors,
 	config: &Config,
 ) {
-	*file_string = script_regex::JS_ARGUMENTS.replace_all(file_string, |capture: &Captures| {
-		// Matched string is a multiline or single line comment
-		// i.e. it does not have any further capture groups
-		if capture.at(1).is_none() {
-			return capture.at(0).unwrap().to_string();
-		}
-
-		let mut replacement_args: String = unescape_js_chars(capture.at(3).unwrap());
-		let mut function = capture.at(1).unwrap().to_owned();
-		function.retain(|c| !c.is_whitespace());
+	let tokens = tokenize_js(file_string);
+	let mut replacements: Vec<Replacement> = vec![];
 
+	for (index, function, arguments) in
+		find_indexed_function_calls(file_string, &tokens, &FUNCTIONS)
+	{
 		// Work out function call and its argument pattern:
-		match function.as_str() {
+		match function {
 			// Takes one argument, an CSS selector string.
 			".querySelector" | ".querySelectorAll" | ".closest" | ".matches" => {
-				// FIXME: rudimentary way to check if arg is a immediate
-				// string value rather than some expression.
-				let quote_type: &str = match replacement_args.chars().next() {
-					Some('\'') => "'",
-					Some('"') => "\"",
-					Some('`') => "`",
-					_ => "",
-				};
-
-				// Remove any additional backslash in JS selector strings.
-				replacement_args = replacement_args.replace("\\\\", "\\");
-
-				if !quote_type.is_empty() {
-					super::rewrite_css(&mut replacement_args, selectors, config);
+				if let Some(argument) = arguments
+					.first()
+					.filter(|argument| argument.kind != ArgumentKind::Expression)
+				{
+					// Remove any additional backslash in JS selector strings.
+					let mut selector =
+						unescape_js_chars(argument.value(file_string)).replace("\\\\", "\\");
+					let original = selector.clone();
+					super::rewrite_css(&mut selector, selectors, config);
+					replace_value(
+						&mut replacements,
+						file_string,
+						argument,
+						&original,
+						selector,
+					);
 				}
 			},
 
 			// Takes one argument, a string of classes (no period prefixed)
 			// separated by spaces (if more than one) —
 			".getElementsByClassName" => {
-				// Checking that argument is a string
-				if capture.at(4).is_some() {
-					super::rewrite_string_of_tokens(&mut replacement_args, selectors, "class");
+				if let Some(argument) = arguments.first().filter(|argument| argument.is_literal()) {
+					let mut classes = unescape_js_chars(argument.text(file_string));
+					let original = classes.clone();
+					super::rewrite_string_of_tokens(&mut classes, selectors, "class");
+					replace_text(&mut replacements, argument, &original, classes);
 				}
-				// TODO: handle expressions?
 			},
 
 			// Takes one argument, an ID (no hash prefixed).
 			".getElementById" => {
-				// Checking that argument is a string
-				if capture.at(4).is_some() {
-					super::rewrite_string_of_tokens(&mut replacement_args, selectors, "id");
-				};
-				// TODO: handle expressions?
+				if let Some(argument) = arguments.first().filter(|argument| argument.is_literal()) {
+					let mut id = unescape_js_chars(argument.text(file_string));
+					let original = id.clone();
+					super::rewrite_string_of_tokens(&mut id, selectors, "id");
+					replace_text(&mut replacements, argument, &original, id);
+				}
 			},
 
 			// Takes two arguments: attribute name and value,
 			// process value if attribute is whitelisted.
 			".setAttribute" => {
-				// Go over the (two) function arguments
-				let mut function_args = super::get_function_arguments(&replacement_args);
-
-				// Check first arg in function, without the string delimiters
-				// and then trimming any whitespace off ends.
-				let attribute_name: &str = function_args.next().unwrap().at(3).unwrap_or("").trim();
-
-				// Check first argument is an known attribute which its value will have
-				// classses or an id. If it is not, leave value as is (second argument).
-				if WHITELIST.get().unwrap().contains_key(attribute_name) {
-					if let Some(attribute_value) = function_args.next() {
-						if attribute_value.at(3).is_some() {
-							let mut replacement_value = attribute_value.at(3).unwrap().to_string();
-							let attribute_type_designation: &str =
-								WHITELIST.get().unwrap().get(attribute_name).unwrap();
-
-
-							match attribute_type_designation {
-								"id" | "class" => {
-									super::rewrite_string_of_tokens(
-										&mut replacement_value,
-										selectors,
-										attribute_type_designation,
-									);
-								},
-
-								"selector" => {
-									super::rewrite_css(&mut replacement_value, selectors, config);
-								},
-
-								"style" => {
-									rewrite_css_functions(&mut replacement_value, selectors);
-								},
-
-								"script" => {
-									rewrite_js(&mut replacement_value, selectors, config);
-								},
-
-								"anchor" => {
-									super::rewrite_anchor_links(&mut replacement_value, selectors);
-								},
-
-								_ => return replacement_value,
-							};
-
-							replacement_args = replacement_args
-								.replace(attribute_value.at(3).unwrap(), &replacement_value);
-						}
-					}
+				if let (Some(name), Some(value)) = (arguments.first(), arguments.get(1)) {
+					rewrite_attribute_arguments(
+						&mut replacements,
+						file_string,
+						name,
+						value,
+						selectors,
+						config,
+					);
+				}
+			},
+
+			// Takes three arguments: namespace, attribute name and value.
+			".setAttributeNS" => {
+				if let (Some(name), Some(value)) = (arguments.get(1), arguments.get(2)) {
+					rewrite_attribute_arguments(
+						&mut replacements,
+						file_string,
+						name,
+						value,
+						selectors,
+						config,
+					);
 				}
 			},
 
 			// Takes two arguments: position and html,
 			// we are only interested in the latter argument.
 			".insertAdjacentHTML" => {
-				if let Some(html) = super::get_function_arguments(&replacement_args).nth(1) {
-					// Third capture group, which should be just the string (without the
-					// delimeters).
-					if html.at(3).is_some() {
-						let mut replacement_html = html.at(3).unwrap().to_string();
-
-						match html.at(3).unwrap().contains("</body>") {
-							true => super::rewrite_html(&mut replacement_html, selectors, config),
-							false => {
-								rewrite_html_attributes(&mut replacement_html, selectors, config)
-							},
-						};
-
-						replacement_args =
-							replacement_args.replace(html.at(3).unwrap(), &replacement_html);
+				if let Some(argument) = arguments
+					.get(1)
+					.filter(|argument| argument.kind != ArgumentKind::Expression)
+				{
+					let mut html = get_embedded_value(file_string, &tokens, argument);
+					let original = html.clone();
+					rewrite_html_string(&mut html, selectors, config);
+					replace_embedded_value(
+						&mut replacements,
+						file_string,
+						&tokens,
+						argument,
+						&original,
+						html,
+					);
+				}
+			},
+
+			".parseFromString"
+			| ".parseHTMLUnsafe"
+			| ".createContextualFragment"
+			| ".setHTMLUnsafe"
+			| ".setHTML" => {
+				if let Some(argument) = arguments
+					.first()
+					.filter(|argument| argument.kind != ArgumentKind::Expression)
+				{
+					let mut html = get_embedded_value(file_string, &tokens, argument);
+					let original = html.clone();
+					rewrite_html_string(&mut html, selectors, config);
+					replace_embedded_value(
+						&mut replacements,
+						file_string,
+						&tokens,
+						argument,
+						&original,
+						html,
+					);
+				}
+			},
+
+			"document.write" | "document.writeln" => {
+				for argument in arguments
+					.iter()
+					.filter(|argument| argument.kind != ArgumentKind::Expression)
+				{
+					let mut html = get_embedded_value(file_string, &tokens, argument);
+					let original = html.clone();
+					rewrite_html_string(&mut html, selectors, config);
+					replace_embedded_value(
+						&mut replacements,
+						file_string,
+						&tokens,
+						argument,
+						&original,
+						html,
+					);
+				}
+			},
+
+			// Only on documents or XPath evaluators, as other libraries have
+			// `evaluate` methods too (e.g. `page.evaluate()` of Playwright).
+			".evaluate" | ".createExpression"
+				if is_xpath_evaluator(file_string, &tokens, index, &arguments) =>
+			{
+				if let Some(argument) = arguments
+					.first()
+					.filter(|argument| argument.kind != ArgumentKind::Expression)
+				{
+					let original = get_xpath(file_string, &tokens, argument);
+					let mut xpath = original.clone();
+					super::rewrite_xpath(&mut xpath, selectors);
+
+					if argument.kind == ArgumentKind::Template {
+						replace_embedded_value(
+							&mut replacements,
+							file_string,
+							&tokens,
+							argument,
+							&original,
+							xpath,
+						);
+					} else if xpath != original {
+						// XPath literals may use the same quotes as the JS string.
+						let quote = argument.text(file_string).chars().next().unwrap();
+						replacements.push(Replacement {
+							start: argument.start,
+							end: argument.end,
+							value: format!(
+								"{quote}{xpath}{quote}",
+								xpath = escape_js_chars(&xpath, quote),
+							),
+						});
 					}
 				}
 			},
 
+			".insertRule" | ".replaceSync" => {
+				if let Some(argument) = arguments
+					.first()
+					.filter(|argument| argument.kind != ArgumentKind::Expression)
+				{
+					let mut css = get_embedded_value(file_string, &tokens, argument);
+					let original = css.clone();
+					super::rewrite_css(&mut css, selectors, config);
+					replace_embedded_value(
+						&mut replacements,
+						file_string,
+						&tokens,
+						argument,
+						&original,
+						css,
+					);
+				}
+			},
+
 			// Takes either only one argument or up to two arguments:
 			// we are only ever interested in argument number 1.
 			"window.open" | "window.location.assign" | "window.location.replace" => {
-				if let Some(link) = super::get_function_arguments(&replacement_args).next() {
-					let mut replacement_link = link.at(0).unwrap().to_string();
-					super::rewrite_anchor_links(&mut replacement_link, selectors);
-					replacement_args =
-						replacement_args.replace(link.at(0).unwrap(), &replacement_link);
+				if let Some(argument) = arguments.first().filter(|argument| argument.is_literal()) {
+					let mut link = unescape_js_chars(argument.text(file_string));
+					let original = link.clone();
+					super::rewrite_anchor_links(&mut link, selectors);
+					replace_text(&mut replacements, argument, &original, link);
 				}
 			},
 
 			// Takes two or three arguments, the final argument which
 			// is an optional URL is the one that we are interested in.
 			"history.pushState" | "history.replaceState" => {
-				if let Some(link) = super::get_function_arguments(&replacement_args).nth(2) {
-					let mut replacement_link = link.at(0).unwrap().to_string();
-					super::rewrite_anchor_links(&mut replacement_link, selectors);
-					replacement_args =
-						replacement_args.replace(link.at(0).unwrap(), &replacement_link);
+				if let Some(argument) = arguments.get(2).filter(|argument| argument.is_literal()) {
+					let mut link = unescape_js_chars(argument.text(file_string));
+					let original = link.clone();
+					super::rewrite_anchor_links(&mut link, selectors);
+					replace_text(&mut replacements, argument, &original, link);
 				}
 			},
 
@@ -388,196 +694,2199 @@ pub fn rewrite_js_arguments(
 			| ".classList.remove"
 			| ".classList.replace"
 			| ".classList.toggle" => {
-				super::rewrite_string_of_arguments(&mut replacement_args, selectors, "class");
+				for argument in arguments.iter().filter(|argument| argument.is_literal()) {
+					let mut class = unescape_js_chars(argument.text(file_string));
+					let original = class.clone();
+					super::rewrite_string_of_arguments(&mut class, selectors, "class");
+					replace_text(&mut replacements, argument, &original, class);
+				}
+			},
+
+			".part.add" | ".part.contains" | ".part.remove" | ".part.replace" | ".part.toggle" => {
+				for argument in arguments.iter().filter(|argument| argument.is_literal()) {
+					let mut part = unescape_js_chars(argument.text(file_string));
+					let original = part.clone();
+					super::rewrite_string_of_arguments(&mut part, selectors, "part");
+					replace_text(&mut replacements, argument, &original, part);
+				}
 			},
 
 			_ => {},
 		}
+	}
 
-		format!(
-			"{function}{join}{arguments}",
-			function = capture.at(1).unwrap(),
-			join = capture.at(2).unwrap(),
-			arguments = replacement_args
-		)
-	});
+	super::splice_replacements(file_string, replacements);
 }
 
 
-/// Analyse JS property operation values.
-pub fn analyse_js_properties(
+/// Analyse arguments of custom functions (e.g. `clsx`).
+pub fn analyse_js_custom_functions(
 	file_string: &mut str,
 	selectors: &mut Selectors,
 	config: &Config,
 ) {
-	for capture in script_regex::JS_PROPERTIES.captures_iter(file_string) {
-		// Matched string is a multi-line or single-line comment
-		// i.e. it does not have any further capture groups
-		if capture.at(1).is_none() {
-			continue;
-		}
+	let tokens = tokenize_js(file_string);
+	let custom_functions = get_custom_functions(config);
+	let names: Vec<&str> = custom_functions.iter().map(|function| function.0).collect();
+
+	for (function, arguments) in find_function_calls(file_string, &tokens, &names) {
+		let (_, context, positions) = custom_functions
+			.iter()
+			.find(|custom_function| custom_function.0 == function)
+			.unwrap();
+
+		for (position, argument) in arguments.iter().enumerate() {
+			if positions
+				.as_ref()
+				.is_some_and(|positions| !positions.contains(&position))
+			{
+				continue;
+			}
 
-		let mut property_value: String = unescape_js_chars(capture.at(4).unwrap());
-		let property_name: &str = capture.at(1).unwrap();
+			match *context {
+				"class" | "id" => {
+					analyse_class_names(
+						file_string,
+						get_argument_tokens(&tokens, argument),
+						selectors,
+						context,
+						Some(SelectorUsage::Script),
+					);
+				},
 
-		if property_name == ".innerHTML" || property_name == ".outerHTML" {
-			if property_value.contains("</body>") {
-				super::analyse_html(
-					&mut property_value,
-					selectors,
-					config,
-					Some(SelectorUsage::Script),
-				);
-			} else {
-				analyse_html_attributes(
-					&mut property_value,
-					selectors,
-					config,
-					Some(SelectorUsage::Script),
-				);
+				"selector" if argument.is_literal() => {
+					let mut selector =
+						unescape_js_chars(argument.value(file_string)).replace("\\\\", "\\");
+					super::analyse_css(&mut selector, selectors, config);
+				},
+
+				_ => {},
 			}
-		} else if property_name == "window.location"
-			|| property_name == "window.location.href"
-			|| property_name == "window.location.hash"
-		{
-			super::analyse_anchor_links(&mut property_value, selectors);
-		} else if property_name == ".id" {
-			super::analyse_string_of_tokens(
-				&mut property_value,
-				selectors,
-				"id",
-				Some(SelectorUsage::Script),
-			);
-		} else if property_name == ".className" || property_name.starts_with(".classList") {
-			super::analyse_string_of_tokens(
-				&mut property_value,
-				selectors,
-				"class",
-				Some(SelectorUsage::Script),
-			);
 		}
 	}
 }
 
-/// Rewrite JS property operation values.
-pub fn rewrite_js_properties(
+/// Rewrite arguments of custom functions (e.g. `clsx`).
+pub fn rewrite_js_custom_functions(
 	file_string: &mut String,
 	selectors: &Selectors,
 	config: &Config,
 ) {
-	*file_string = script_regex::JS_PROPERTIES.replace_all(file_string, |capture: &Captures| {
-		// Matched string is a multi-line or single-line comment
-		// i.e. it does not have any further capture groups
-		if capture.at(1).is_none() {
-			return capture.at(0).unwrap().to_string();
-		}
+	let tokens = tokenize_js(file_string);
+	let custom_functions = get_custom_functions(config);
+	let names: Vec<&str> = custom_functions.iter().map(|function| function.0).collect();
+	let mut replacements: Vec<Replacement> = vec![];
+
+	for (function, arguments) in find_function_calls(file_string, &tokens, &names) {
+		let (_, context, positions) = custom_functions
+			.iter()
+			.find(|custom_function| custom_function.0 == function)
+			.unwrap();
+
+		for (position, argument) in arguments.iter().enumerate() {
+			if positions
+				.as_ref()
+				.is_some_and(|positions| !positions.contains(&position))
+			{
+				continue;
+			}
 
-		let mut property_value: String = unescape_js_chars(capture.at(4).unwrap());
-		let property_name: &str = capture.at(1).unwrap();
+			match *context {
+				"class" | "id" => {
+					replacements.extend(rewrite_class_names(
+						file_string,
+						get_argument_tokens(&tokens, argument),
+						selectors,
+						context,
+					));
+				},
+
+				"selector" if argument.is_literal() => {
+					let mut selector =
+						unescape_js_chars(argument.value(file_string)).replace("\\\\", "\\");
+					let original = selector.clone();
+					super::rewrite_css(&mut selector, selectors, config);
+					replace_value(
+						&mut replacements,
+						file_string,
+						argument,
+						&original,
+						selector,
+					);
+				},
 
-		if property_name == ".innerHTML" || property_name == ".outerHTML" {
-			if property_value.contains("</body>") {
-				super::rewrite_html(&mut property_value, selectors, config);
-			} else {
-				rewrite_html_attributes(&mut property_value, selectors, config);
+				_ => {},
 			}
-		} else if property_name == "window.location"
-			|| property_name == "window.location.href"
-			|| property_name == "window.location.hash"
-		{
-			super::rewrite_anchor_links(&mut property_value, selectors);
-		} else if property_name == ".id" {
-			super::rewrite_string_of_tokens(&mut property_value, selectors, "id");
-		} else if property_name == ".className" || property_name.starts_with(".classList") {
-			super::rewrite_string_of_tokens(&mut property_value, selectors, "class");
 		}
+	}
 
-		format!(
-			"{name}{operator}{value}",
-			name = property_name,
-			operator = capture.at(3).unwrap(),
-			value = property_value,
-		)
-	});
+	super::splice_replacements(file_string, replacements);
 }
 
-
-/// Analyse JS property accessors.
-pub fn analyse_js_bracket_accessors(
+/// Analyse arguments of jQuery (and Zepto) function calls.
+pub fn analyse_js_jquery(
 	file_string: &mut str,
 	selectors: &mut Selectors,
+	config: &Config,
 ) {
-	for capture in script_regex::JS_BRACKET_ACCESSORS.captures_iter(file_string) {
-		// Matched string is a multi-line or single-line comment
-		// i.e. it does not have any further capture groups
-		if capture.at(1).is_none() {
-			continue;
-		}
+	if !config.jquery {
+		return;
+	}
+
+	let tokens = tokenize_js(file_string);
+
+	for (function, arguments) in find_jquery_calls(file_string, &tokens) {
+		match function {
+			// Takes a CSS selector string or HTML, i.e. `$('.foo')`
+			// or `$('<div class="foo">')`.
+			"$" | "jQuery" => {
+				if let Some(argument) = arguments
+					.first()
+					.filter(|argument| argument.kind != ArgumentKind::Expression)
+				{
+					let mut value = get_embedded_value(file_string, &tokens, argument);
+
+					match value.trim_start().starts_with('<') {
+						true => analyse_html_string(&mut value, selectors, config),
+						false => {
+							// Remove any additional backslash in JS selector strings.
+							value = value.replace("\\\\", "\\");
+							super::analyse_css(&mut value, selectors, config)
+						},
+					}
+				}
+			},
 
-		let mut property_value: String = unescape_js_chars(capture.at(3).unwrap());
-		let property_name: &str = capture.at(1).unwrap();
+			// Takes HTML, only processed if it looks like it starts with a tag
+			// as it can also be text.
+			".html" | ".append" | ".prepend" | ".after" | ".before" => {
+				if let Some(argument) = arguments
+					.first()
+					.filter(|argument| argument.kind != ArgumentKind::Expression)
+				{
+					let mut html = get_embedded_value(file_string, &tokens, argument);
+
+					if html.trim_start().starts_with('<') {
+						analyse_html_string(&mut html, selectors, config);
+					}
+				}
+			},
 
-		if property_name == ".children" {
-			super::analyse_string_of_tokens(
-				&mut property_value,
-				selectors,
-				"id",
-				Some(SelectorUsage::Script),
-			);
+			// Takes event names and an optional CSS selector string for
+			// delegated events, which is the second argument.
+			".on" | ".one" | ".off" => {
+				if let Some(argument) = arguments
+					.get(1)
+					.filter(|argument| argument.kind != ArgumentKind::Expression)
+				{
+					let mut selector =
+						get_embedded_value(file_string, &tokens, argument).replace("\\\\", "\\");
+					super::analyse_css(&mut selector, selectors, config);
+				}
+			},
+
+			// Takes a string of classes separated by spaces (if more than one).
+			".addClass" | ".removeClass" | ".toggleClass" | ".hasClass" => {
+				if let Some(argument) = arguments.first().filter(|argument| argument.is_literal()) {
+					super::analyse_string_of_tokens(
+						&mut unescape_js_chars(argument.text(file_string)),
+						selectors,
+						"class",
+						Some(SelectorUsage::Script),
+					);
+				}
+			},
+
+			// Takes two arguments: attribute name and value.
+			".attr" => {
+				if let (Some(name), Some(value)) = (arguments.first(), arguments.get(1)) {
+					analyse_attribute_arguments(file_string, name, value, selectors, config);
+				}
+			},
+
+			// Rest take one argument, a CSS selector string.
+			_ => {
+				if let Some(argument) = arguments
+					.first()
+					.filter(|argument| argument.kind != ArgumentKind::Expression)
+				{
+					let mut selector =
+						get_embedded_value(file_string, &tokens, argument).replace("\\\\", "\\");
+					super::analyse_css(&mut selector, selectors, config);
+				}
+			},
 		}
 	}
 }
 
-/// Rewrite JS property accessors.
-pub fn rewrite_js_bracket_accessors(
+/// Rewrite arguments of jQuery (and Zepto) function calls.
+pub fn rewrite_js_jquery(
 	file_string: &mut String,
 	selectors: &Selectors,
+	config: &Config,
 ) {
-	*file_string =
-		script_regex::JS_BRACKET_ACCESSORS.replace_all(file_string, |capture: &Captures| {
-			// Matched string is a multi-line or single-line comment
-			// i.e. it does not have any further capture groups
-			if capture.at(1).is_none() {
-				return capture.at(0).unwrap().to_string();
-			}
-
-			let mut property_value: String = unescape_js_chars(capture.at(3).unwrap());
-			let property_name: &str = capture.at(1).unwrap();
+	if !config.jquery {
+		return;
+	}
 
-			if property_name == ".children" {
-				super::rewrite_string_of_tokens(&mut property_value, selectors, "id");
-			}
+	let tokens = tokenize_js(file_string);
+	let mut replacements: Vec<Replacement> = vec![];
+
+	for (function, arguments) in find_jquery_calls(file_string, &tokens) {
+		match function {
+			"$" | "jQuery" | ".html" | ".append" | ".prepend" | ".after" | ".before" => {
+				let Some(argument) = arguments
+					.first()
+					.filter(|argument| argument.kind != ArgumentKind::Expression)
+				else {
+					continue;
+				};
+				let mut value = get_embedded_value(file_string, &tokens, argument);
+
+				if value.trim_start().starts_with('<') {
+					let original = value.clone();
+					rewrite_html_string(&mut value, selectors, config);
+					replace_embedded_value(
+						&mut replacements,
+						file_string,
+						&tokens,
+						argument,
+						&original,
+						value,
+					);
+				} else if matches!(function, "$" | "jQuery") {
+					// Remove any additional backslash in JS selector strings.
+					let mut selector = value.replace("\\\\", "\\");
+					let original = selector.clone();
+					super::rewrite_css(&mut selector, selectors, config);
+					replace_embedded_value(
+						&mut replacements,
+						file_string,
+						&tokens,
+						argument,
+						&original,
+						selector,
+					);
+				}
+			},
 
-			format!(
-				"{name}{open_bracket}{value}",
-				name = property_name,
-				open_bracket = capture.at(2).unwrap(),
-				value = property_value,
-			)
-		});
-}
+			".addClass" | ".removeClass" | ".toggleClass" | ".hasClass" => {
+				if let Some(argument) = arguments.first().filter(|argument| argument.is_literal()) {
+					let mut classes = unescape_js_chars(argument.text(file_string));
+					let original = classes.clone();
+					super::rewrite_string_of_tokens(&mut classes, selectors, "class");
+					replace_text(&mut replacements, argument, &original, classes);
+				}
+			},
 
+			".attr" => {
+				if let (Some(name), Some(value)) = (arguments.first(), arguments.get(1)) {
+					rewrite_attribute_arguments(
+						&mut replacements,
+						file_string,
+						name,
+						value,
+						selectors,
+						config,
+					);
+				}
+			},
 
-// Converts any escaped chars in JS substring to UTF8 char.
-pub fn unescape_js_chars(js_string: &str) -> String {
-	let mut unescaped = js_string.to_string();
+			// Rest take a CSS selector string, as the first argument or the
+			// second for delegated events.
+			_ => {
+				let position = match function {
+					".on" | ".one" | ".off" => 1,
+					_ => 0,
+				};
 
-	if script_regex::ESCAPED_JS_CHARS.find(js_string).is_none() {
-		return unescaped;
+				if let Some(argument) = arguments
+					.get(position)
+					.filter(|argument| argument.kind != ArgumentKind::Expression)
+				{
+					let mut selector =
+						get_embedded_value(file_string, &tokens, argument).replace("\\\\", "\\");
+					let original = selector.clone();
+					super::rewrite_css(&mut selector, selectors, config);
+					replace_embedded_value(
+						&mut replacements,
+						file_string,
+						&tokens,
+						argument,
+						&original,
+						selector,
+					);
+				}
+			},
+		}
 	}
 
-	unescaped = script_regex::ESCAPED_JS_CHARS.replace_all(&unescaped, |capture: &Captures| {
-		String::from(
-			if capture.at(1).is_some() {
-				char::from_u32(
-					u32::from_str_radix(capture.at(1).unwrap().strip_prefix('%').unwrap(), 16)
-						.unwrap(),
-				)
-				.unwrap()
-			} else if capture.at(2).is_some() {
-				char::from_u32(
-					u32::from_str_radix(capture.at(2).unwrap().strip_prefix("\\x").unwrap(), 16)
-						.unwrap(),
+	super::splice_replacements(file_string, replacements);
+}
+
+/// Analyse tagged template literals that contain HTML or CSS, i.e.
+/// `` html`<div class="foo">` `` or `` css`.foo { … }` ``.
+pub fn analyse_js_tagged_templates(
+	file_string: &mut str,
+	selectors: &mut Selectors,
+	config: &Config,
+) {
+	let tokens = tokenize_js(file_string);
+
+	for (kind, parts) in find_tagged_templates(file_string, &tokens, &get_template_tags(config)) {
+		// Substitutions are swapped out for placeholders, any selectors
+		// joined to them are left as is.
+		let mut template = join_with_placeholders(
+			&parts
+				.iter()
+				.map(|(start, end)| &file_string[*start..*end])
+				.collect::<Vec<&str>>(),
+		);
+
+		match kind {
+			"markup" => {
+				super::analyse_html(
+					&mut template,
+					selectors,
+					config,
+					Some(SelectorUsage::Script),
+				)
+			},
+			"css" => super::analyse_css(&mut template, selectors, config),
+			_ => {},
+		}
+	}
+}
+
+/// Rewrite tagged template literals that contain HTML or CSS, only the
+/// static parts are rewritten with substitutions left intact.
+pub fn rewrite_js_tagged_templates(
+	file_string: &mut String,
+	selectors: &Selectors,
+	config: &Config,
+) {
+	let tokens = tokenize_js(file_string);
+	let mut replacements: Vec<Replacement> = vec![];
+
+	for (kind, parts) in find_tagged_templates(file_string, &tokens, &get_template_tags(config)) {
+		let originals: Vec<&str> = parts
+			.iter()
+			.map(|(start, end)| &file_string[*start..*end])
+			.collect();
+		let mut template = join_with_placeholders(&originals);
+
+		match kind {
+			"markup" => super::rewrite_html(&mut template, selectors, config),
+			"css" => super::rewrite_css(&mut template, selectors, config),
+			_ => {},
+		}
+
+		let rewritten = split_at_placeholders(&template);
+
+		// Substitutions have been moved or removed, leave it as is.
+		if rewritten.len() != parts.len() {
+			continue;
+		}
+
+		for ((start, end), value) in parts.into_iter().zip(rewritten) {
+			if value != file_string[start..end] {
+				replacements.push(Replacement { start, end, value });
+			}
+		}
+	}
+
+	super::splice_replacements(file_string, replacements);
+}
+
+/// Analyse class names (or IDs and part names) in an expression, see
+/// `find_class_names`.
+pub fn analyse_class_names(
+	file_string: &str,
+	tokens: &[Token],
+	selectors: &mut Selectors,
+	context: &str,
+	usage: Option<SelectorUsage>,
+) {
+	for name in find_class_names(file_string, tokens) {
+		// Object keys only toggle classes.
+		if context != "class" && name.kind == ArgumentKind::Key {
+			continue;
+		}
+
+		super::analyse_string_of_tokens(
+			&mut unescape_js_chars(name.value(file_string)),
+			selectors,
+			context,
+			usage,
+		);
+	}
+}
+
+/// Rewrite class names (or IDs and part names) in an expression, see
+/// `find_class_names`.
+/// Returns replacements for any names that have changed.
+pub fn rewrite_class_names(
+	file_string: &str,
+	tokens: &[Token],
+	selectors: &Selectors,
+	context: &str,
+) -> Vec<Replacement> {
+	let mut replacements: Vec<Replacement> = vec![];
+
+	for name in find_class_names(file_string, tokens) {
+		if context != "class" && name.kind == ArgumentKind::Key {
+			continue;
+		}
+
+		let original = unescape_js_chars(name.value(file_string));
+		let mut value = original.clone();
+		super::rewrite_string_of_tokens(&mut value, selectors, context);
+
+		if value == original {
+			continue;
+		}
+
+		let value = match name.kind {
+			// Encoded name may not be a valid identifier (e.g. with
+			// a custom alphabet), in which case it needs quotes.
+			ArgumentKind::Key if !lexer::is_identifier(&value) => format!("'{value}'"),
+			ArgumentKind::Key => value,
+			_ => {
+				format!(
+					"{quote}{value}{quote}",
+					quote = &name.text(file_string)[..1],
+				)
+			},
+		};
+
+		replacements.push(Replacement {
+			start: name.start,
+			end: name.end,
+			value,
+		});
+	}
+
+	replacements
+}
+
+
+/// Analyse JS property operation values.
+pub fn analyse_js_properties(
+	file_string: &mut str,
+	selectors: &mut Selectors,
+	config: &Config,
+) {
+	let tokens = tokenize_js(file_string);
+
+	for (property_name, value) in find_property_values(file_string, &tokens, &PROPERTIES) {
+		let mut property_value: String = unescape_js_chars(value.text(file_string));
+
+		if property_name == ".innerHTML" || property_name == ".outerHTML" {
+			// Substitutions of a template literal are left as is.
+			if value.kind == ArgumentKind::Template {
+				property_value = get_embedded_value(file_string, &tokens, &value);
+			}
+			analyse_html_string(&mut property_value, selectors, config);
+		} else if property_name == ".textContent" {
+			// Only the text of style elements is of interest.
+			if value.kind != ArgumentKind::Expression && is_stylesheet(value.value(file_string)) {
+				super::analyse_css(
+					&mut get_embedded_value(file_string, &tokens, &value),
+					selectors,
+					config,
+				);
+			}
+			continue;
+		}
+
+		// Rest of the properties only take literal values.
+		if !value.is_literal() {
+			continue;
+		}
+
+		if property_name.starts_with("window.location") {
+			super::analyse_anchor_links(&mut property_value, selectors);
+		} else if property_name == ".id" || property_name == ".htmlFor" {
+			super::analyse_string_of_tokens(
+				&mut property_value,
+				selectors,
+				"id",
+				Some(SelectorUsage::Script),
+			);
+		} else if property_name == ".className" || property_name.starts_with(".classList") {
+			super::analyse_string_of_tokens(
+				&mut property_value,
+				selectors,
+				"class",
+				Some(SelectorUsage::Script),
+			);
+		} else if property_name == ".part.value" {
+			super::analyse_string_of_tokens(
+				&mut property_value,
+				selectors,
+				"part",
+				Some(SelectorUsage::Script),
+			);
+		}
+	}
+}
+
+/// Rewrite JS property operation values.
+pub fn rewrite_js_properties(
+	file_string: &mut String,
+	selectors: &Selectors,
+	config: &Config,
+) {
+	let tokens = tokenize_js(file_string);
+	let mut replacements: Vec<Replacement> = vec![];
+
+	for (property_name, value) in find_property_values(file_string, &tokens, &PROPERTIES) {
+		let mut property_value: String = unescape_js_chars(value.text(file_string));
+		let original = property_value.clone();
+
+		if (property_name == ".innerHTML" || property_name == ".outerHTML")
+			&& value.kind == ArgumentKind::Template
+		{
+			let mut html = get_embedded_value(file_string, &tokens, &value);
+			let original = html.clone();
+			rewrite_html_string(&mut html, selectors, config);
+			replace_embedded_value(
+				&mut replacements,
+				file_string,
+				&tokens,
+				&value,
+				&original,
+				html,
+			);
+			continue;
+		} else if property_name == ".innerHTML" || property_name == ".outerHTML" {
+			rewrite_html_string(&mut property_value, selectors, config);
+		} else if property_name == ".textContent" {
+			if value.kind == ArgumentKind::Expression || !is_stylesheet(value.value(file_string)) {
+				continue;
+			}
+
+			let mut css = get_embedded_value(file_string, &tokens, &value);
+			let original = css.clone();
+			super::rewrite_css(&mut css, selectors, config);
+			replace_embedded_value(
+				&mut replacements,
+				file_string,
+				&tokens,
+				&value,
+				&original,
+				css,
+			);
+			continue;
+		} else if !value.is_literal() {
+			// Rest of the properties only take literal values.
+			continue;
+		} else if property_name.starts_with("window.location") {
+			super::rewrite_anchor_links(&mut property_value, selectors);
+		} else if property_name == ".id" || property_name == ".htmlFor" {
+			super::rewrite_string_of_tokens(&mut property_value, selectors, "id");
+		} else if property_name == ".className" || property_name.starts_with(".classList") {
+			super::rewrite_string_of_tokens(&mut property_value, selectors, "class");
+		} else if property_name == ".part.value" {
+			super::rewrite_string_of_tokens(&mut property_value, selectors, "part");
+		}
+
+		replace_text(&mut replacements, &value, &original, property_value);
+	}
+
+	super::splice_replacements(file_string, replacements);
+}
+
+
+/// Analyse JS property accessors.
+pub fn analyse_js_bracket_accessors(
+	file_string: &mut str,
+	selectors: &mut Selectors,
+) {
+	let tokens = tokenize_js(file_string);
+
+	for (property_name, value) in find_bracket_accessors(file_string, &tokens, &BRACKET_ACCESSORS) {
+		let mut property_value: String = unescape_js_chars(value.text(file_string));
+
+		if property_name == ".children" {
+			super::analyse_string_of_tokens(
+				&mut property_value,
+				selectors,
+				"id",
+				Some(SelectorUsage::Script),
+			);
+		}
+	}
+}
+
+/// Rewrite JS property accessors.
+pub fn rewrite_js_bracket_accessors(
+	file_string: &mut String,
+	selectors: &Selectors,
+) {
+	let tokens = tokenize_js(file_string);
+	let mut replacements: Vec<Replacement> = vec![];
+
+	for (property_name, value) in find_bracket_accessors(file_string, &tokens, &BRACKET_ACCESSORS) {
+		let mut property_value: String = unescape_js_chars(value.text(file_string));
+		let original = property_value.clone();
+
+		if property_name == ".children" {
+			super::rewrite_string_of_tokens(&mut property_value, selectors, "id");
+		}
+
+		replace_text(&mut replacements, &value, &original, property_value);
+	}
+
+	super::splice_replacements(file_string, replacements);
+}
+
+
+/// Analyse string literals annotated with what they contain, i.e.
+/// `/* @class */ 'foo'`, `/* @id */ 'bar'` or `/* @selector */ '.foo > #bar'`.
+pub fn analyse_js_annotated_strings(
+	file_string: &mut str,
+	selectors: &mut Selectors,
+	config: &Config,
+) {
+	let tokens = tokenize_js(file_string);
+
+	for (kind, value) in find_annotated_strings(file_string, &tokens, config) {
+		match kind {
+			// Names joined to a substitution of a template literal (i.e.
+			// `` `tab-${index}` ``) are left as is.
+			"class" | "id" => {
+				super::analyse_string_of_tokens(
+					&mut get_embedded_value(file_string, &tokens, &value),
+					selectors,
+					kind,
+					Some(SelectorUsage::Script),
+				);
+			},
+
+			"selector" => {
+				// Remove any additional backslash in JS selector strings.
+				let mut selector =
+					unescape_js_chars(value.value(file_string)).replace("\\\\", "\\");
+				super::analyse_css(&mut selector, selectors, config);
+			},
+
+			_ => {},
+		}
+	}
+}
+
+/// Rewrite annotated string literals.
+pub fn rewrite_js_annotated_strings(
+	file_string: &mut String,
+	selectors: &Selectors,
+	config: &Config,
+) {
+	let tokens = tokenize_js(file_string);
+	let mut replacements: Vec<Replacement> = vec![];
+
+	for (kind, value) in find_annotated_strings(file_string, &tokens, config) {
+		match kind {
+			"class" | "id" => {
+				let mut names = get_embedded_value(file_string, &tokens, &value);
+				let original = names.clone();
+				super::rewrite_string_of_tokens(&mut names, selectors, kind);
+				replace_embedded_value(
+					&mut replacements,
+					file_string,
+					&tokens,
+					&value,
+					&original,
+					names,
+				);
+			},
+
+			"selector" => {
+				// Remove any additional backslash in JS selector strings.
+				let mut selector =
+					unescape_js_chars(value.value(file_string)).replace("\\\\", "\\");
+				let original = selector.clone();
+				super::rewrite_css(&mut selector, selectors, config);
+				replace_value(&mut replacements, file_string, &value, &original, selector);
+			},
+
+			_ => {},
+		}
+	}
+
+	super::splice_replacements(file_string, replacements);
+}
+
+
+/// Analyse JSX element attributes.
+pub fn analyse_jsx_attributes(
+	file_string: &mut str,
+	selectors: &mut Selectors,
+	config: &Config,
+) {
+	let tokens = tokenize_js(file_string);
+
+	for jsx_element in find_jsx_elements(file_string, &tokens) {
+		let element = get_jsx_element(file_string, &jsx_element);
+
+		for (attribute_name, original) in &element.attributes {
+			let Some(attribute_type_designation) =
+				get_attribute_designation(attribute_name, Some(&element))
+			else {
+				continue;
+			};
+			let mut attribute_value = original.to_string();
+
+			match attribute_type_designation.as_str() {
+				"id" | "class" | "part" => {
+					super::analyse_string_of_tokens(
+						&mut attribute_value,
+						selectors,
+						attribute_type_designation,
+						Some(match attribute_type_designation.as_str() {
+							"id" => SelectorUsage::MarkupId,
+							"part" => SelectorUsage::MarkupPart,
+							_ => SelectorUsage::MarkupClass,
+						}),
+					);
+				},
+
+				"exportparts" => {
+					analyse_export_parts(
+						&attribute_value,
+						selectors,
+						Some(SelectorUsage::MarkupPart),
+					);
+				},
+
+				"selector" => {
+					super::analyse_css(&mut attribute_value, selectors, config);
+				},
+
+				"style" => {
+					super::analyse_css_functions(&mut attribute_value, selectors);
+				},
+
+				"anchor" => {
+					super::analyse_anchor_links(&mut attribute_value, selectors);
+				},
+
+				"timing" => {
+					analyse_smil_timing(&attribute_value, selectors, Some(SelectorUsage::MarkupId));
+				},
+
+				"json" => {
+					analyse_json_attribute(
+						&attribute_value,
+						attribute_name,
+						Some(&element),
+						selectors,
+						config,
+						None,
+					);
+				},
+
+				"markup" => {
+					super::analyse_html(&mut attribute_value, selectors, config, None);
+				},
+
+				// Event handlers in JSX are expressions rather than strings,
+				// so are already processed as part of the script.
+				_ => continue,
+			}
+		}
+	}
+}
+
+/// Rewrite JSX element attributes.
+pub fn rewrite_jsx_attributes(
+	file_string: &mut String,
+	selectors: &Selectors,
+	config: &Config,
+) {
+	let tokens = tokenize_js(file_string);
+	let mut replacements: Vec<Replacement> = vec![];
+
+	for jsx_element in find_jsx_elements(file_string, &tokens) {
+		let element = get_jsx_element(file_string, &jsx_element);
+
+		for ((_, value), (attribute_name, original)) in
+			jsx_element.attributes.iter().zip(&element.attributes)
+		{
+			let Some(attribute_type_designation) =
+				get_attribute_designation(attribute_name, Some(&element))
+			else {
+				continue;
+			};
+			let mut attribute_value = original.to_string();
+
+			match attribute_type_designation.as_str() {
+				"id" | "class" | "part" => {
+					super::rewrite_string_of_tokens(
+						&mut attribute_value,
+						selectors,
+						attribute_type_designation,
+					);
+				},
+
+				"exportparts" => {
+					rewrite_export_parts(&mut attribute_value, selectors);
+				},
+
+				"selector" => {
+					super::rewrite_css(&mut attribute_value, selectors, config);
+				},
+
+				"style" => {
+					super::rewrite_css_functions(&mut attribute_value, selectors);
+				},
+
+				"anchor" => {
+					super::rewrite_anchor_links(&mut attribute_value, selectors);
+				},
+
+				"timing" => {
+					rewrite_smil_timing(&mut attribute_value, selectors);
+				},
+
+				"json" => {
+					rewrite_json_attribute(
+						&mut attribute_value,
+						attribute_name,
+						Some(&element),
+						selectors,
+						config,
+					);
+				},
+
+				"markup" => {
+					super::rewrite_html(&mut attribute_value, selectors, config);
+				},
+
+				_ => continue,
+			}
+
+			replace_value(
+				&mut replacements,
+				file_string,
+				value,
+				original,
+				attribute_value,
+			);
+		}
+	}
+
+	super::splice_replacements(file_string, replacements);
+}
+
+
+/// Tokenize JS, leaving out any comments.
+pub fn tokenize_js(file_string: &str) -> Vec<Token> {
+	lexer::tokenize(file_string)
+		.into_iter()
+		.filter(|token| token.kind != TokenKind::Comment)
+		.collect()
+}
+
+/// Find calls to any of the given functions, returning the matched
+/// function name and the arguments for each call.
+pub fn find_function_calls<'a>(
+	file_string: &str,
+	tokens: &[Token],
+	functions: &[&'a str],
+) -> Vec<(&'a str, Vec<Argument>)> {
+	find_indexed_function_calls(file_string, tokens, functions)
+		.into_iter()
+		.map(|(_, function, arguments)| (function, arguments))
+		.collect()
+}
+
+/// Find calls to jQuery (and Zepto) functions, see `find_function_calls`.
+///
+/// Notes:
+///  - Methods (i.e. `.find()` or `.has()`) are only picked up when the chain
+///    they are called on starts with `$()` or `jQuery()`, or has a variable
+///    that is prefixed with `$` (i.e. `$menu.find()` or `this.$menu.find()`).
+pub fn find_jquery_calls(
+	file_string: &str,
+	tokens: &[Token],
+) -> Vec<(&'static str, Vec<Argument>)> {
+	find_indexed_function_calls(file_string, tokens, &JQUERY_FUNCTIONS)
+		.into_iter()
+		.filter(|(index, function, _)| {
+			!function.starts_with('.')
+				|| get_call_chain(file_string, tokens, *index)
+					.iter()
+					.any(|name| name.starts_with('$') || *name == "jQuery")
+		})
+		.map(|(_, function, arguments)| (function, arguments))
+		.collect()
+}
+
+/// Same as `find_function_calls`, along with the index of the token for
+/// the function name.
+pub fn find_indexed_function_calls<'a>(
+	file_string: &str,
+	tokens: &[Token],
+	functions: &[&'a str],
+) -> Vec<(usize, &'a str, Vec<Argument>)> {
+	let mut calls = vec![];
+
+	for index in 0..tokens.len() {
+		if tokens[index].kind != TokenKind::Identifier {
+			continue;
+		}
+
+		// TypeScript type arguments, i.e. `querySelector<HTMLElement>()`
+		let open = skip_type_arguments(file_string, tokens, index + 1);
+
+		if !tokens
+			.get(open)
+			.is_some_and(|token| token.is_punctuator(file_string, "("))
+		{
+			continue;
+		}
+
+		let expression = get_member_expression(file_string, tokens, index);
+		let Some(function) = functions
+			.iter()
+			.find(|function| is_member(&expression, function))
+		else {
+			continue;
+		};
+		let Some(close) = find_closing_bracket(file_string, tokens, open) else {
+			continue;
+		};
+
+		calls.push((
+			index,
+			*function,
+			get_arguments(file_string, tokens, open, close),
+		));
+	}
+
+	calls
+}
+
+/// Find JSX elements with attributes that have a string value, returning the
+/// tag name along with the equivalent HTML attribute name and value (either a
+/// string, or a string or template literal in an expression container, i.e.
+/// `className={'foo'}`) for each attribute.
+pub fn find_jsx_elements<'a>(
+	file_string: &'a str,
+	tokens: &[Token],
+) -> Vec<JsxElement<'a>> {
+	let mut elements = vec![];
+
+	for (index, token) in tokens.iter().enumerate() {
+		// Opening tag, with the name right after the angle bracket (fragments
+		// and closing tags have no attributes).
+		if !token.is_punctuator(file_string, "<")
+			|| !tokens
+				.get(index + 1)
+				.is_some_and(|name| name.kind == TokenKind::Identifier && name.start == token.end)
+			|| !tokens
+				.get(index + 2)
+				.is_some_and(|token| token.kind == TokenKind::JsxAttribute)
+		{
+			continue;
+		}
+
+		let mut element = JsxElement {
+			tag: tokens[index + 1].text(file_string),
+			attributes: vec![],
+		};
+		let mut index = index + 2;
+
+		while let Some(token) = tokens.get(index) {
+			// Spread attributes, i.e. `{...props}`
+			if token.is_punctuator(file_string, "{") {
+				let Some(close) = find_closing_bracket(file_string, tokens, index) else {
+					break;
+				};
+				index = close + 1;
+				continue;
+			}
+
+			if token.kind != TokenKind::JsxAttribute {
+				break;
+			}
+
+			if !tokens
+				.get(index + 1)
+				.is_some_and(|token| token.is_punctuator(file_string, "="))
+			{
+				index += 1;
+				continue;
+			}
+
+			let name = token.text(file_string);
+			let name = match JSX_ATTRIBUTES
+				.iter()
+				.find(|(jsx_name, _)| *jsx_name == name)
+			{
+				Some((_, html_name)) => html_name.to_string(),
+				None => name.to_ascii_lowercase(),
+			};
+
+			match tokens.get(index + 2) {
+				Some(value) if value.kind == TokenKind::String => {
+					element
+						.attributes
+						.push((name, get_argument(&tokens[index + 2..=index + 2])));
+					index += 3;
+				},
+				Some(value) if value.is_punctuator(file_string, "{") => {
+					let Some(close) = find_closing_bracket(file_string, tokens, index + 2) else {
+						break;
+					};
+
+					if let Some(literal) = get_literal(tokens, index + 3).filter(|literal| {
+						literal.kind == ArgumentKind::String
+							&& tokens[index + 3..=close]
+								.iter()
+								.find(|token| token.start >= literal.end)
+								.is_some_and(|token| token.start == tokens[close].start)
+					}) {
+						element.attributes.push((name, literal));
+					}
+					index = close + 1;
+				},
+				// Elements as attribute values, i.e. `icon=<Icon />`, are
+				// picked up on their own.
+				_ => break,
+			}
+		}
+
+		if !element.attributes.is_empty() {
+			elements.push(element);
+		}
+	}
+
+	elements
+}
+
+/// Element that the attributes of a JSX element are on, with the values of
+/// its string attributes (see `find_jsx_elements`).
+pub fn get_jsx_element<'a>(
+	file_string: &str,
+	jsx_element: &'a JsxElement,
+) -> Element<'a> {
+	Element {
+		tag: jsx_element.tag,
+		attributes: jsx_element
+			.attributes
+			.iter()
+			.map(|(name, value)| {
+				(
+					name.as_str(),
+					Cow::Owned(get_jsx_attribute_value(file_string, value)),
+				)
+			})
+			.collect(),
+		is_escaped: false,
+	}
+}
+
+/// Find class names in an expression that evaluates to a string of classes
+/// (e.g. a `:class` binding), which are any string literals as well as the
+/// keys of object literals (i.e. `{ foo: isFoo }`).
+///
+/// Notes:
+///  - Strings that are compared against (i.e. `type === 'foo'`) and values of
+///    object literal properties are left out.
+///  - Only strings in arrays, parentheses, ternaries and logical expressions
+///    are looked at, any in function calls (i.e. `t('label.key')`) or member
+///    access (i.e. `styles['btn']`) are left out.
+pub fn find_class_names(
+	file_string: &str,
+	tokens: &[Token],
+) -> Vec<Argument> {
+	let mut names = vec![];
+	// Brackets that the current token is in.
+	let mut brackets: Vec<Bracket> = vec![];
+
+	for (index, token) in tokens.iter().enumerate() {
+		let is_punctuator = |offset: isize, punctuators: &[&str]| {
+			index
+				.checked_add_signed(offset)
+				.and_then(|index| tokens.get(index))
+				.is_some_and(|token| {
+					token.kind == TokenKind::Punctuator
+						&& punctuators.contains(&token.text(file_string))
+				})
+		};
+		// Brackets after an operand are calls or member access.
+		let is_after_operand = index > 0
+			&& (matches!(
+				tokens[index - 1].kind,
+				TokenKind::Identifier
+					| TokenKind::String
+					| TokenKind::Template
+					| TokenKind::TemplateTail
+					| TokenKind::Number
+			) || is_punctuator(-1, &[")", "]", "?."]));
+		let is_included = brackets.iter().all(|bracket| {
+			matches!(
+				bracket,
+				Bracket::Group | Bracket::Array | Bracket::Object { is_value: false }
+			)
+		});
+
+		match token.kind {
+			TokenKind::Punctuator => {
+				match token.text(file_string) {
+					"(" | "[" if is_after_operand => brackets.push(Bracket::Other),
+					"(" => brackets.push(Bracket::Group),
+					"[" => brackets.push(Bracket::Array),
+					"{" => brackets.push(Bracket::Object { is_value: false }),
+					")" | "]" | "}" => {
+						brackets.pop();
+					},
+					punctuator @ (":" | ",") => {
+						if let Some(Bracket::Object { is_value }) = brackets.last_mut() {
+							*is_value = punctuator == ":";
+						}
+					},
+					_ => {},
+				}
+			},
+			TokenKind::TemplateHead => brackets.push(Bracket::Other),
+			TokenKind::TemplateTail => {
+				brackets.pop();
+			},
+			TokenKind::String | TokenKind::Template if is_included => {
+				let is_compared = is_punctuator(-1, &["==", "===", "!=", "!=="])
+					|| is_punctuator(1, &["==", "===", "!=", "!=="]);
+
+				if !is_compared {
+					names.push(get_argument(&tokens[index..=index]));
+				}
+			},
+			TokenKind::Identifier
+				if is_included
+					&& matches!(brackets.last(), Some(Bracket::Object { .. }))
+					&& is_punctuator(1, &[":"]) =>
+			{
+				names.push(Argument {
+					start: token.start,
+					end: token.end,
+					kind: ArgumentKind::Key,
+				});
+			},
+			_ => {},
+		}
+	}
+
+	names
+}
+
+// Kind of bracket in an expression of class names.
+enum Bracket {
+	// Parentheses for grouping, i.e. `(foo || bar)`.
+	Group,
+	// Array literal, or computed key of an object literal.
+	Array,
+	Object { is_value: bool },
+	// Function call, member access or template substitution.
+	Other,
+}
+
+/// Find string values that are assigned to (or compared with) any of the
+/// given properties, returning the matched property name and value.
+pub fn find_property_values<'a>(
+	file_string: &str,
+	tokens: &[Token],
+	properties: &[&'a str],
+) -> Vec<(&'a str, Argument)> {
+	let mut values = vec![];
+
+	for index in 0..tokens.len() {
+		if tokens[index].kind != TokenKind::Identifier {
+			continue;
+		}
+
+		let expression = get_member_expression(file_string, tokens, index);
+		let Some(property) = properties
+			.iter()
+			.find(|property| is_member(&expression, property))
+		else {
+			continue;
+		};
+		let mut next = index + 1;
+
+		// Skip over item index, i.e. `.classList[0]` or `.classList.item(0)`.
+		if *property == ".classList.item" {
+			match find_closing_bracket(file_string, tokens, next) {
+				Some(close) if tokens[next].is_punctuator(file_string, "(") => next = close + 1,
+				_ => continue,
+			}
+		} else if *property == ".classList"
+			&& tokens
+				.get(next)
+				.is_some_and(|token| token.is_punctuator(file_string, "["))
+			&& tokens
+				.get(next + 1)
+				.is_some_and(|token| token.kind == TokenKind::Number)
+			&& tokens
+				.get(next + 2)
+				.is_some_and(|token| token.is_punctuator(file_string, "]"))
+		{
+			next += 3;
+		}
+
+		if !tokens.get(next).is_some_and(|token| {
+			PROPERTY_OPERATORS
+				.iter()
+				.any(|operator| token.is_punctuator(file_string, operator))
+		}) {
+			continue;
+		}
+
+		if let Some(value) = get_literal(tokens, next + 1) {
+			values.push((*property, value));
+		}
+	}
+
+	values
+}
+
+/// Find template literals with any of the given tags, returning what the
+/// tag contains (i.e. `markup` or `css`) and the ranges of the static parts
+/// of each template literal (either side of any substitutions).
+pub fn find_tagged_templates<'a>(
+	file_string: &str,
+	tokens: &[Token],
+	tags: &[(&'a str, &'a str)],
+) -> Vec<(&'a str, Vec<(usize, usize)>)> {
+	let mut templates = vec![];
+
+	for index in 1..tokens.len() {
+		if !matches!(
+			tokens[index].kind,
+			TokenKind::Template | TokenKind::TemplateHead
+		) {
+			continue;
+		}
+
+		let Some(expression) = get_tag_expression(file_string, tokens, index - 1) else {
+			continue;
+		};
+		let Some((_, kind)) = tags.iter().find(|(name, _)| {
+			is_member(&expression, name) || expression.starts_with(&format!("{name}."))
+		}) else {
+			continue;
+		};
+
+		templates.push((*kind, get_template_parts(file_string, tokens, index)));
+	}
+
+	templates
+}
+
+/// Get ranges of the static parts of a template literal starting at the
+/// given token index, made up of its head, middles and tail, skipping over
+/// any templates nested in substitutions.
+pub fn get_template_parts(
+	file_string: &str,
+	tokens: &[Token],
+	index: usize,
+) -> Vec<(usize, usize)> {
+	// Piece of a template up until its closing backtick, which is missing
+	// if the template is unterminated.
+	let get_last_part = |token: &Token| {
+		match token.is_closed(file_string) {
+			true => (token.start + 1, token.end - 1),
+			false => (token.start + 1, token.end),
+		}
+	};
+
+	let token = &tokens[index];
+	if token.kind == TokenKind::Template {
+		return vec![get_last_part(token)];
+	}
+
+	let mut parts = vec![(token.start + 1, token.end - 2)];
+	let mut depth: usize = 0;
+
+	for token in &tokens[index + 1..] {
+		match token.kind {
+			TokenKind::TemplateHead => depth += 1,
+			TokenKind::TemplateMiddle if depth == 0 => {
+				parts.push((token.start + 1, token.end - 2));
+			},
+			TokenKind::TemplateTail if depth == 0 => {
+				parts.push(get_last_part(token));
+				break;
+			},
+			TokenKind::TemplateTail => depth -= 1,
+			_ => {},
+		}
+	}
+
+	parts
+}
+
+/// Find string keys used to access any of the given properties with
+/// bracket notation, returning the matched property name and key.
+pub fn find_bracket_accessors<'a>(
+	file_string: &str,
+	tokens: &[Token],
+	properties: &[&'a str],
+) -> Vec<(&'a str, Argument)> {
+	let mut keys = vec![];
+
+	for index in 0..tokens.len() {
+		if tokens[index].kind != TokenKind::Identifier
+			|| !tokens
+				.get(index + 1)
+				.is_some_and(|token| token.is_punctuator(file_string, "["))
+		{
+			continue;
+		}
+
+		let expression = get_member_expression(file_string, tokens, index);
+		let Some(property) = properties
+			.iter()
+			.find(|property| is_member(&expression, property))
+		else {
+			continue;
+		};
+
+		if let Some(key) = get_literal(tokens, index + 2).filter(|key| key.is_literal()) {
+			keys.push((*property, key));
+		}
+	}
+
+	keys
+}
+
+/// Find string or template literals that follow an annotation comment,
+/// returning what the annotation says the literal contains.
+///
+/// Notes:
+///  - Annotated literals that are already processed as the argument of a
+///    function or the value of a property are skipped, so that they are not
+///    processed twice.
+pub fn find_annotated_strings(
+	file_string: &str,
+	tokens: &[Token],
+	config: &Config,
+) -> Vec<(&'static str, Argument)> {
+	let mut strings = vec![];
+	let processed = get_processed_literals(file_string, tokens, config);
+
+	for comment in lexer::tokenize(file_string) {
+		if comment.kind != TokenKind::Comment {
+			continue;
+		}
+
+		let Some(annotation) = comment
+			.text(file_string)
+			.strip_prefix("/*")
+			.and_then(|text| text.strip_suffix("*/"))
+			.map(|text| {
+				text.trim_matches(|character: char| character == '*' || character.is_whitespace())
+			})
+		else {
+			continue;
+		};
+		let Some((_, kind)) = STRING_ANNOTATIONS
+			.iter()
+			.find(|(name, _)| *name == annotation)
+		else {
+			continue;
+		};
+
+		// Literal needs to be the next token after the comment.
+		let index = tokens.partition_point(|token| token.start < comment.end);
+
+		if let Some(value) = get_literal(tokens, index).filter(|value| {
+			value.kind != ArgumentKind::Expression && !processed.contains(&value.start)
+		}) {
+			strings.push((*kind, value));
+		}
+	}
+
+	strings
+}
+
+// Get the member expression that ends with the identifier at the given
+// index, e.g. `document.querySelector`. Expression starts with a period if
+// the object is not an identifier, e.g. `.classList` in `foo().classList`.
+fn get_member_expression(
+	file_string: &str,
+	tokens: &[Token],
+	index: usize,
+) -> String {
+	let mut expression = tokens[index].text(file_string).to_string();
+	let mut index = index;
+
+	while index > 0
+		&& (tokens[index - 1].is_punctuator(file_string, ".")
+			|| tokens[index - 1].is_punctuator(file_string, "?."))
+	{
+		if index < 2 || tokens[index - 2].kind != TokenKind::Identifier {
+			return format!(".{expression}");
+		}
+
+		expression = format!(
+			"{object}.{expression}",
+			object = tokens[index - 2].text(file_string),
+		);
+		index -= 2;
+	}
+
+	expression
+}
+
+// Analyse arguments that set an attribute (name and value), the value is
+// processed if the attribute is whitelisted.
+fn analyse_attribute_arguments(
+	file_string: &str,
+	name: &Argument,
+	value: &Argument,
+	selectors: &mut Selectors,
+	config: &Config,
+) {
+	if !name.is_literal() || !value.is_literal() {
+		return;
+	}
+
+	// Check first argument is an known attribute which its value will have
+	// classses or an id. If it is not, leave value as is (second argument).
+	// Attribute names are lowercased, as they are by `setAttribute()` on
+	// HTML elements (i.e. `popoverTarget`).
+	let attribute_name: String = unescape_js_chars(name.value(file_string)).to_ascii_lowercase();
+	let Some(attribute_type_designation) = get_attribute_designation(attribute_name.trim(), None)
+	else {
+		return;
+	};
+	let mut attribute_value: String = unescape_js_chars(value.value(file_string));
+
+	match attribute_type_designation.as_str() {
+		"id" | "class" | "part" => {
+			super::analyse_string_of_tokens(
+				&mut attribute_value,
+				selectors,
+				attribute_type_designation,
+				Some(SelectorUsage::Script),
+			);
+		},
+
+		"exportparts" => {
+			analyse_export_parts(&attribute_value, selectors, Some(SelectorUsage::Script));
+		},
+
+		"selector" => {
+			super::analyse_css(&mut attribute_value, selectors, config);
+		},
+
+		"style" => {
+			analyse_css_functions(&mut attribute_value, selectors);
+		},
+
+		"script" => {
+			analyse_js(&mut attribute_value, selectors, config);
+		},
+
+		"anchor" => {
+			super::analyse_anchor_links(&mut attribute_value, selectors);
+		},
+
+		"timing" => {
+			analyse_smil_timing(&attribute_value, selectors, Some(SelectorUsage::Script));
+		},
+
+		"json" => {
+			analyse_json_attribute(
+				&attribute_value,
+				attribute_name.trim(),
+				None,
+				selectors,
+				config,
+				Some(SelectorUsage::Script),
+			);
+		},
+
+		"markup" => {
+			super::analyse_html(
+				&mut attribute_value,
+				selectors,
+				config,
+				Some(SelectorUsage::Script),
+			);
+		},
+
+		_ => {},
+	}
+}
+
+// Rewrite arguments that set an attribute (name and value).
+fn rewrite_attribute_arguments(
+	replacements: &mut Vec<Replacement>,
+	file_string: &str,
+	name: &Argument,
+	value: &Argument,
+	selectors: &Selectors,
+	config: &Config,
+) {
+	if !name.is_literal() || !value.is_literal() {
+		return;
+	}
+
+	// Check first argument is an known attribute which its value will have
+	// classses or an id. If it is not, leave value as is (second argument).
+	// Attribute names are lowercased, as they are by `setAttribute()` on
+	// HTML elements (i.e. `popoverTarget`).
+	let attribute_name: String = unescape_js_chars(name.value(file_string)).to_ascii_lowercase();
+	let Some(attribute_type_designation) = get_attribute_designation(attribute_name.trim(), None)
+	else {
+		return;
+	};
+	let mut attribute_value: String = unescape_js_chars(value.value(file_string));
+	let original = attribute_value.clone();
+
+	match attribute_type_designation.as_str() {
+		"id" | "class" | "part" => {
+			super::rewrite_string_of_tokens(
+				&mut attribute_value,
+				selectors,
+				attribute_type_designation,
+			);
+		},
+
+		"exportparts" => {
+			rewrite_export_parts(&mut attribute_value, selectors);
+		},
+
+		"selector" => {
+			super::rewrite_css(&mut attribute_value, selectors, config);
+		},
+
+		"style" => {
+			rewrite_css_functions(&mut attribute_value, selectors);
+		},
+
+		"script" => {
+			rewrite_js(&mut attribute_value, selectors, config);
+		},
+
+		"anchor" => {
+			super::rewrite_anchor_links(&mut attribute_value, selectors);
+		},
+
+		"timing" => {
+			rewrite_smil_timing(&mut attribute_value, selectors);
+		},
+
+		"json" => {
+			rewrite_json_attribute(
+				&mut attribute_value,
+				attribute_name.trim(),
+				None,
+				selectors,
+				config,
+			);
+		},
+
+		"markup" => {
+			super::rewrite_html(&mut attribute_value, selectors, config);
+		},
+
+		_ => return,
+	};
+
+	replace_value(replacements, file_string, value, &original, attribute_value);
+}
+
+// Analyse HTML passed to a DOM API, whole documents and HTML with
+// embedded styles or scripts (i.e. the contents of a `<template>`) are
+// processed in full, otherwise only attributes are.
+fn analyse_html_string(
+	html: &mut str,
+	selectors: &mut Selectors,
+	config: &Config,
+) {
+	match is_html_document(html) {
+		true => super::analyse_html(html, selectors, config, Some(SelectorUsage::Script)),
+		false => {
+			analyse_html_attributes(html, selectors, config, Some(SelectorUsage::Script), false)
+		},
+	}
+}
+
+// Rewrite HTML passed to a DOM API.
+fn rewrite_html_string(
+	html: &mut String,
+	selectors: &Selectors,
+	config: &Config,
+) {
+	match is_html_document(html) {
+		true => super::rewrite_html(html, selectors, config),
+		false => rewrite_html_attributes(html, selectors, config, false),
+	}
+}
+
+// Check if HTML is a whole document or has embedded styles or scripts.
+fn is_html_document(html: &str) -> bool {
+	html.contains("</body>") || html.contains("</style>") || html.contains("</script>")
+}
+
+// Check if text assigned to `.textContent` looks like a stylesheet,
+// as it could be the text of any element.
+fn is_stylesheet(text: &str) -> bool {
+	let text = text.trim();
+	text.contains('{') && text.ends_with('}') && !text.starts_with('{')
+}
+
+// Get the tag of a template literal that ends at the given token index,
+// either a member expression (i.e. `styled.div`) or a call to one (i.e.
+// `styled(Button).attrs(…)`), leaving out the arguments of any calls.
+// TypeScript type arguments are skipped over as well.
+fn get_tag_expression(
+	file_string: &str,
+	tokens: &[Token],
+	index: usize,
+) -> Option<String> {
+	let mut index = index;
+	let mut expression = String::new();
+
+	loop {
+		if let Some((open, close)) = [("(", ")"), ("<", ">")]
+			.into_iter()
+			.find(|(_, close)| tokens[index].is_punctuator(file_string, close))
+		{
+			let mut depth: usize = 0;
+
+			loop {
+				if tokens[index].is_punctuator(file_string, close) {
+					depth += 1;
+				} else if tokens[index].is_punctuator(file_string, open) {
+					depth -= 1;
+				}
+
+				if depth == 0 {
+					break;
+				}
+
+				index = index.checked_sub(1)?;
+			}
+
+			index = index.checked_sub(1)?;
+			continue;
+		}
+
+		if tokens[index].kind != TokenKind::Identifier {
+			return None;
+		}
+
+		let member = get_member_expression(file_string, tokens, index);
+		expression = format!("{member}{expression}");
+
+		// Object is not an identifier, i.e. `.attrs` in `styled(Button).attrs`.
+		match member.strip_prefix('.') {
+			Some(members) => index = index.checked_sub(members.split('.').count() * 2)?,
+			None => return Some(expression),
+		}
+	}
+}
+
+// Get template tags from config, along with the built-in ones.
+fn get_template_tags(config: &Config) -> Vec<(&str, &str)> {
+	TEMPLATE_TAGS
+		.into_iter()
+		.chain(
+			config
+				.template_tags
+				.iter()
+				.map(|(tag, kind)| (tag.as_str(), kind.as_str())),
+		)
+		.collect()
+}
+
+// Get custom functions from config, along with what their arguments
+// contain and which arguments (if only some of them) to process.
+//
+// Function names may be followed by the positions of arguments, starting
+// from zero, i.e. `cva(0)`.
+fn get_custom_functions(config: &Config) -> Vec<(&str, &str, Option<Vec<usize>>)> {
+	config
+		.custom_functions
+		.iter()
+		.map(|(signature, context)| {
+			match signature.split_once('(') {
+				Some((name, positions)) => {
+					(
+						name.trim(),
+						context.as_str(),
+						Some(
+							positions
+								.trim_end_matches(')')
+								.split(',')
+								.filter_map(|position| position.trim().parse::<usize>().ok())
+								.collect(),
+						),
+					)
+				},
+				None => (signature.trim(), context.as_str(), None),
+			}
+		})
+		.collect()
+}
+
+// Get start of the literals that are processed as the argument of a function
+// or the value of a property.
+fn get_processed_literals(
+	file_string: &str,
+	tokens: &[Token],
+	config: &Config,
+) -> Vec<usize> {
+	let custom_functions = get_custom_functions(config);
+	let mut functions: Vec<&str> = FUNCTIONS.to_vec();
+
+	functions.extend(custom_functions.iter().map(|function| function.0));
+
+	find_function_calls(file_string, tokens, &functions)
+		.into_iter()
+		.chain(match config.jquery {
+			true => find_jquery_calls(file_string, tokens),
+			false => vec![],
+		})
+		.flat_map(|(_, arguments)| arguments)
+		.chain(
+			find_property_values(file_string, tokens, &PROPERTIES)
+				.into_iter()
+				.map(|(_, value)| value),
+		)
+		.map(|argument| argument.start)
+		.collect()
+}
+
+// Get tokens that make up an argument.
+fn get_argument_tokens<'a>(
+	tokens: &'a [Token],
+	argument: &Argument,
+) -> &'a [Token] {
+	let start = tokens
+		.iter()
+		.position(|token| token.start >= argument.start)
+		.unwrap_or(tokens.len());
+	let end = tokens
+		.iter()
+		.position(|token| token.start >= argument.end)
+		.unwrap_or(tokens.len());
+
+	&tokens[start..end]
+}
+
+// Skip over TypeScript type arguments starting at the given token index,
+// returning the index of the token after them.
+fn skip_type_arguments(
+	file_string: &str,
+	tokens: &[Token],
+	index: usize,
+) -> usize {
+	if !tokens
+		.get(index)
+		.is_some_and(|token| token.is_punctuator(file_string, "<"))
+	{
+		return index;
+	}
+
+	let mut depth: usize = 0;
+
+	for (offset, token) in tokens[index..].iter().enumerate() {
+		match (token.kind, token.text(file_string)) {
+			(TokenKind::Punctuator, "<") => depth += 1,
+			(TokenKind::Punctuator, closing @ (">" | ">>" | ">>>")) => {
+				match depth.checked_sub(closing.len()) {
+					Some(0) => return index + offset + 1,
+					Some(remaining) => depth = remaining,
+					None => return index,
+				}
+			},
+			(TokenKind::Punctuator, "." | "," | "|" | "&" | "[" | "]" | "?" | ":" | "=>")
+			| (TokenKind::Punctuator, "(" | ")" | "{" | "}" | ";")
+			| (TokenKind::Identifier, _)
+			| (TokenKind::String, _)
+			| (TokenKind::Number, _) => {},
+			_ => return index,
+		}
+	}
+
+	index
+}
+
+// Get string value of a JSX attribute, strings in JSX have HTML character
+// references rather than escape sequences.
+fn get_jsx_attribute_value(
+	file_string: &str,
+	value: &Argument,
+) -> String {
+	match file_string[..value.start].trim_end().ends_with('=') {
+		true => unescape_html_chars(value.value(file_string)),
+		false => unescape_js_chars(value.value(file_string)),
+	}
+}
+
+// Check member expression ends with the given member name.
+fn is_member(
+	expression: &str,
+	name: &str,
+) -> bool {
+	match expression.strip_suffix(name) {
+		Some(object) => name.starts_with('.') || object.is_empty() || object.ends_with('.'),
+		None => false,
+	}
+}
+
+// Get XPath expression from a string argument, XPath does not have escape
+// sequences so any escape sequences are only part of the JS string.
+// Substitutions of a template literal are swapped out for placeholders,
+// see `get_embedded_value`.
+fn get_xpath(
+	file_string: &str,
+	tokens: &[Token],
+	argument: &Argument,
+) -> String {
+	if argument.kind == ArgumentKind::Template {
+		return get_embedded_value(file_string, tokens, argument);
+	}
+
+	unescape_js_string(argument.value(file_string))
+}
+
+// Check that `evaluate` or `createExpression` at the given index is called
+// on a document or an `XPathEvaluator`, or otherwise has the same arguments
+// as `document.evaluate` (expression, context node, resolver, type and
+// result).
+fn is_xpath_evaluator(
+	file_string: &str,
+	tokens: &[Token],
+	index: usize,
+	arguments: &[Argument],
+) -> bool {
+	let is_evaluator = matches!(
+		get_call_chain(file_string, tokens, index).first(),
+		Some(&"document" | &"ownerDocument" | &"contentDocument" | &"XPathEvaluator")
+	);
+
+	is_evaluator || (tokens[index].text(file_string) == "evaluate" && arguments.len() == 5)
+}
+
+// Find index of the closing bracket token for the opening bracket
+// token at the given index.
+fn find_closing_bracket(
+	file_string: &str,
+	tokens: &[Token],
+	open: usize,
+) -> Option<usize> {
+	let mut depth: usize = 0;
+
+	for (index, token) in tokens.iter().enumerate().skip(open) {
+		match token.kind {
+			TokenKind::Punctuator => {
+				match token.text(file_string) {
+					"(" | "[" | "{" => depth += 1,
+					")" | "]" | "}" => {
+						depth = depth.saturating_sub(1);
+						if depth == 0 {
+							return Some(index);
+						}
+					},
+					_ => {},
+				}
+			},
+			TokenKind::TemplateHead => depth += 1,
+			TokenKind::TemplateTail => depth = depth.saturating_sub(1),
+			_ => {},
+		}
+	}
+
+	None
+}
+
+// Find the opening bracket that is closed by the bracket at the given index.
+fn find_opening_bracket(
+	file_string: &str,
+	tokens: &[Token],
+	close: usize,
+) -> Option<usize> {
+	let mut depth: usize = 0;
+
+	for index in (0..=close).rev() {
+		match tokens[index].kind {
+			TokenKind::Punctuator => {
+				match tokens[index].text(file_string) {
+					")" | "]" | "}" => depth += 1,
+					"(" | "[" | "{" => {
+						depth = depth.saturating_sub(1);
+						if depth == 0 {
+							return Some(index);
+						}
+					},
+					_ => {},
+				}
+			},
+			TokenKind::TemplateTail => depth += 1,
+			TokenKind::TemplateHead => depth = depth.saturating_sub(1),
+			_ => {},
+		}
+	}
+
+	None
+}
+
+/// Get names of the objects and methods that a method (or property) at the
+/// given index is accessed on, nearest first. I.e. for `.find` in
+/// `$('.foo').children().find()` it is `children` then `$`.
+pub fn get_call_chain<'a>(
+	file_string: &'a str,
+	tokens: &[Token],
+	index: usize,
+) -> Vec<&'a str> {
+	let mut chain = vec![];
+	let mut index = index;
+
+	while index >= 2
+		&& (tokens[index - 1].is_punctuator(file_string, ".")
+			|| tokens[index - 1].is_punctuator(file_string, "?."))
+	{
+		let mut object = index - 2;
+
+		// Skip over calls and index access, i.e. `$('.foo').find()`.
+		while tokens[object].is_punctuator(file_string, ")")
+			|| tokens[object].is_punctuator(file_string, "]")
+		{
+			match find_opening_bracket(file_string, tokens, object) {
+				Some(open) if open > 0 => object = open - 1,
+				_ => return chain,
+			}
+		}
+
+		if tokens[object].kind != TokenKind::Identifier {
+			break;
+		}
+
+		chain.push(tokens[object].text(file_string));
+		index = object;
+	}
+
+	chain
+}
+
+// Split tokens between a pair of parens into comma separated arguments.
+fn get_arguments(
+	file_string: &str,
+	tokens: &[Token],
+	open: usize,
+	close: usize,
+) -> Vec<Argument> {
+	let mut arguments = vec![];
+	let mut depth: usize = 0;
+	let mut start = open + 1;
+
+	for index in open + 1..=close {
+		let token = &tokens[index];
+
+		match token.kind {
+			TokenKind::Punctuator => {
+				match token.text(file_string) {
+					"(" | "[" | "{" => depth += 1,
+					")" | "]" | "}" if index < close => depth = depth.saturating_sub(1),
+					"," | ")" if depth == 0 => {
+						if start < index {
+							arguments.push(get_argument(&tokens[start..index]));
+						}
+						start = index + 1;
+					},
+					_ => {},
+				}
+			},
+			TokenKind::TemplateHead => depth += 1,
+			TokenKind::TemplateTail => depth = depth.saturating_sub(1),
+			_ => {},
+		}
+	}
+
+	arguments
+}
+
+// Work out if argument tokens are a string, template literal or expression.
+fn get_argument(tokens: &[Token]) -> Argument {
+	let start = tokens.first().unwrap().start;
+	let end = tokens.last().unwrap().end;
+
+	let kind = match (tokens.first().unwrap().kind, tokens.last().unwrap().kind) {
+		(TokenKind::String, _) | (TokenKind::Template, _) if tokens.len() == 1 => {
+			ArgumentKind::String
+		},
+		(TokenKind::TemplateHead, TokenKind::TemplateTail) if is_single_template(tokens) => {
+			ArgumentKind::Template
+		},
+		_ => ArgumentKind::Expression,
+	};
+
+	Argument { start, end, kind }
+}
+
+// Get string or template literal starting at the given token index.
+fn get_literal(
+	tokens: &[Token],
+	index: usize,
+) -> Option<Argument> {
+	match tokens.get(index)?.kind {
+		TokenKind::String | TokenKind::Template => Some(get_argument(&tokens[index..=index])),
+		TokenKind::TemplateHead => {
+			let mut depth: usize = 0;
+
+			for (offset, token) in tokens[index..].iter().enumerate() {
+				match token.kind {
+					TokenKind::TemplateHead => depth += 1,
+					TokenKind::TemplateTail => depth -= 1,
+					_ => {},
+				}
+				if depth == 0 {
+					return Some(get_argument(&tokens[index..=index + offset]));
+				}
+			}
+
+			None
+		},
+		_ => None,
+	}
+}
+
+// Check that the template head and tail tokens belong to the same
+// template literal, i.e. not `${a}` + `${b}`.
+fn is_single_template(tokens: &[Token]) -> bool {
+	let mut depth: usize = 0;
+
+	for (index, token) in tokens.iter().enumerate() {
+		match token.kind {
+			TokenKind::TemplateHead => depth += 1,
+			TokenKind::TemplateTail => depth -= 1,
+			_ => {},
+		}
+		if depth == 0 {
+			return index == tokens.len() - 1;
+		}
+	}
+
+	false
+}
+
+// Get the value of a string or template literal argument that contains
+// HTML or CSS. Substitutions of a template are swapped out for
+// placeholders, so that any selectors joined to them are left as is.
+fn get_embedded_value(
+	file_string: &str,
+	tokens: &[Token],
+	argument: &Argument,
+) -> String {
+	if argument.kind != ArgumentKind::Template {
+		return unescape_js_chars(argument.value(file_string));
+	}
+
+	let index = tokens.partition_point(|token| token.start < argument.start);
+
+	join_with_placeholders(
+		&get_template_parts(file_string, tokens, index)
+			.iter()
+			.map(|(start, end)| &file_string[*start..*end])
+			.collect::<Vec<&str>>(),
+	)
+}
+
+// Note down replacement for an argument's value from `get_embedded_value`,
+// only the static parts of a template are replaced.
+fn replace_embedded_value(
+	replacements: &mut Vec<Replacement>,
+	file_string: &str,
+	tokens: &[Token],
+	argument: &Argument,
+	original: &str,
+	value: String,
+) {
+	if argument.kind != ArgumentKind::Template {
+		return replace_value(replacements, file_string, argument, original, value);
+	}
+
+	let index = tokens.partition_point(|token| token.start < argument.start);
+	let parts = get_template_parts(file_string, tokens, index);
+	let rewritten = split_at_placeholders(&value);
+
+	// Substitutions have been moved or removed, leave it as is.
+	if rewritten.len() != parts.len() {
+		return;
+	}
+
+	for ((start, end), value) in parts.into_iter().zip(rewritten) {
+		if value != file_string[start..end] {
+			replacements.push(Replacement { start, end, value });
+		}
+	}
+}
+
+// Note down replacement for an argument's literal value (i.e. without
+// its quotes), as long as it has been changed.
+pub(crate) fn replace_value(
+	replacements: &mut Vec<Replacement>,
+	file_string: &str,
+	argument: &Argument,
+	original: &str,
+	value: String,
+) {
+	let quote = &argument.text(file_string)[..1];
+
+	replace_text(
+		replacements,
+		argument,
+		&format!("{quote}{original}{quote}"),
+		format!("{quote}{value}{quote}"),
+	);
+}
+
+// Note down replacement for an argument, as long as it has been changed.
+pub(crate) fn replace_text(
+	replacements: &mut Vec<Replacement>,
+	argument: &Argument,
+	original: &str,
+	text: String,
+) {
+	if text == original {
+		return;
+	}
+
+	replacements.push(Replacement {
+		start: argument.start,
+		end: argument.end,
+		value: text,
+	});
+}
+
+
+// Converts any escaped chars in JS substring to UTF8 char.
+pub fn unescape_js_chars(js_string: &str) -> String {
+	let mut unescaped = js_string.to_string();
+
+	if script_regex::ESCAPED_JS_CHARS.find(js_string).is_none() {
+		return unescaped;
+	}
+
+	unescaped = script_regex::ESCAPED_JS_CHARS.replace_all(&unescaped, |capture: &Captures| {
+		String::from(
+			if capture.at(1).is_some() {
+				char::from_u32(
+					u32::from_str_radix(capture.at(1).unwrap().strip_prefix('%').unwrap(), 16)
+						.unwrap(),
+				)
+				.unwrap()
+			} else if capture.at(2).is_some() {
+				char::from_u32(
+					u32::from_str_radix(capture.at(2).unwrap().strip_prefix("\\x").unwrap(), 16)
+						.unwrap(),
 				)
 				.unwrap()
 			} else if capture.at(3).is_some() {
@@ -585,7 +2894,7 @@ pub fn unescape_js_chars(js_string: &str) -> String {
 					u32::from_str_radix(capture.at(3).unwrap().strip_prefix("\\u").unwrap(), 16)
 						.unwrap(),
 				)
-				.unwrap()
+				.unwrap_or(char::REPLACEMENT_CHARACTER)
 			} else if capture.at(4).is_some() {
 				char::from_u32(
 					u32::from_str_radix(
@@ -600,7 +2909,7 @@ pub fn unescape_js_chars(js_string: &str) -> String {
 					)
 					.unwrap(),
 				)
-				.unwrap()
+				.unwrap_or(char::REPLACEMENT_CHARACTER)
 			} else {
 				panic!("Not any of the known capture groups");
 			},
@@ -609,3 +2918,99 @@ pub fn unescape_js_chars(js_string: &str) -> String {
 
 	unescaped
 }
+
+// Converts escape sequences in the value of a JS string literal (including
+// escaped quotes and backslashes) to the chars they stand for, the reverse
+// of `escape_js_chars`.
+pub(crate) fn unescape_js_string(js_string: &str) -> String {
+	let mut unescaped = String::with_capacity(js_string.len());
+	let mut chars = js_string.chars().peekable();
+
+	while let Some(character) = chars.next() {
+		if character != '\\' {
+			unescaped.push(character);
+			continue;
+		}
+
+		let Some(escaped) = chars.next() else {
+			unescaped.push(character);
+			break;
+		};
+
+		let hex_digits: String = match escaped {
+			'x' => chars.clone().take(2).collect(),
+			'u' if chars.peek() == Some(&'{') => {
+				chars
+					.clone()
+					.skip(1)
+					.take_while(|digit| *digit != '}')
+					.collect()
+			},
+			'u' => chars.clone().take(4).collect(),
+			_ => String::new(),
+		};
+		let code_point = match hex_digits.is_empty() {
+			true => None,
+			false => {
+				u32::from_str_radix(&hex_digits, 16)
+					.ok()
+					.and_then(char::from_u32)
+			},
+		};
+
+		match (escaped, code_point) {
+			('x' | 'u', Some(code_point)) => {
+				let length = match chars.peek() {
+					Some('{') => hex_digits.len() + 2,
+					_ => hex_digits.len(),
+				};
+				chars.nth(length - 1);
+				unescaped.push(code_point);
+			},
+			('n', _) => unescaped.push('\n'),
+			('r', _) => unescaped.push('\r'),
+			('t', _) => unescaped.push('\t'),
+			('b', _) => unescaped.push('\u{8}'),
+			('f', _) => unescaped.push('\u{c}'),
+			('v', _) => unescaped.push('\u{b}'),
+			('0', _) => unescaped.push('\0'),
+			// Line continuation.
+			('\n' | '\u{2028}' | '\u{2029}', _) => {},
+			('\r', _) => {
+				chars.next_if_eq(&'\n');
+			},
+			(escaped, _) => unescaped.push(escaped),
+		}
+	}
+
+	unescaped
+}
+
+// Escapes chars that cannot be used as is in a JS string literal with the
+// given quote.
+pub(crate) fn escape_js_chars(
+	string: &str,
+	quote: char,
+) -> String {
+	let mut escaped = String::with_capacity(string.len());
+	let mut chars = string.chars().peekable();
+
+	while let Some(character) = chars.next() {
+		match character {
+			'\\' => escaped.push_str("\\\\"),
+			'\n' => escaped.push_str("\\n"),
+			'\r' => escaped.push_str("\\r"),
+			'\u{2028}' => escaped.push_str("\\u2028"),
+			'\u{2029}' => escaped.push_str("\\u2029"),
+			// Substitution in a template literal.
+			'$' if quote == '`' && chars.peek() == Some(&'{') => escaped.push_str("\\$"),
+			character if character == quote => {
+				escaped.push('\\');
+				escaped.push(character);
+			},
+			character => escaped.push(character),
+		}
+	}
+
+	escaped
+}
@@ -4,6 +4,7 @@ use minify_selectors_utils::*;
 use onig::*;
 
 use crate::markup::html_attributes::*;
+use crate::pragmas::*;
 use crate::style::regexes as style_regex;
 
 
@@ -14,7 +15,12 @@ pub fn analyse_css(
 	selectors: &mut Selectors,
 	config: &Config,
 ) {
+	if let Some((mut masked, _)) = mask_disabled_regions(file_string, CommentSyntax::Style) {
+		return analyse_css(&mut masked, selectors, config);
+	}
+
 	analyse_css_selectors(file_string, selectors);
+	analyse_css_parts(file_string, selectors);
 	analyse_css_attributes(file_string, selectors, config);
 	analyse_css_functions(file_string, selectors);
 	super::analyse_prefixed_selectors(file_string, selectors);
@@ -25,7 +31,14 @@ pub fn rewrite_css(
 	selectors: &Selectors,
 	config: &Config,
 ) {
+	if let Some((mut masked, regions)) = mask_disabled_regions(file_string, CommentSyntax::Style) {
+		rewrite_css(&mut masked, selectors, config);
+		*file_string = unmask_disabled_regions(&masked, &regions, CommentSyntax::Style);
+		return;
+	}
+
 	rewrite_css_selectors(file_string, selectors);
+	rewrite_css_parts(file_string, selectors);
 	rewrite_css_attributes(file_string, selectors, config);
 	rewrite_css_functions(file_string, selectors);
 	super::rewrite_prefixed_selectors(file_string, selectors);
@@ -37,10 +50,13 @@ pub fn analyse_css_selectors(
 	file_string: &mut str,
 	selectors: &mut Selectors,
 ) {
-	for capture in style_regex::CSS_SELECTORS.captures_iter(file_string) {
+	let css_selectors = style_regex::CSS_SELECTORS.get().unwrap();
+
+	for capture in css_selectors.captures_iter(file_string) {
 		// Check that capture group 2 exists,
 		// i.e. matched to a class/id name — and not an attribute selector,
-		// rule block, @import, or comment — which does not have this group.
+		// rule block, declaration, @import, or comment — which does not
+		// have this group.
 		if capture.at(2).is_some() {
 			super::add_selector_to_map(
 				&format!(
@@ -61,27 +77,68 @@ pub fn rewrite_css_selectors(
 	file_string: &mut String,
 	selectors: &Selectors,
 ) {
-	*file_string = style_regex::CSS_SELECTORS.replace_all(file_string, |capture: &Captures| {
+	let css_selectors = style_regex::CSS_SELECTORS.get().unwrap();
+
+	*file_string = css_selectors.replace_all(file_string, |capture: &Captures| {
 		// Check that capture group 2 exists,
 		// i.e. matched to a class/id name — and not an attribute selector,
-		// rule block, @import, or comment — which does not have this group.
+		// rule block, declaration, @import, or comment — which does not
+		// have this group.
 		if capture.at(2).is_some() {
-			return format!(
-				"{prefix}{identifier}",
-				prefix = &capture.at(1).unwrap(),
-				identifier = super::get_encoded_selector(
-					&unescape_css_chars(capture.at(0).unwrap()),
-					selectors,
-				)
-				.unwrap_or_else(|| capture.at(0).unwrap().to_string()),
-			);
+			return match super::get_encoded_selector(
+				&unescape_css_chars(capture.at(0).unwrap()),
+				selectors,
+			) {
+				Some(identifier) => {
+					format!("{prefix}{identifier}", prefix = &capture.at(1).unwrap())
+				},
+				None => capture.at(0).unwrap().to_string(),
+			};
 		}
-		// Matched to an attribute selector, rule block, @import or comment.
-		// Leave it as is.
+		// Matched to an attribute selector, rule block, declaration, @import or
+		// comment. Leave it as is.
 		capture.at(0).unwrap().to_owned()
 	});
 }
 
+/// Analyse shadow part names in `::part()` pseudo-elements.
+pub fn analyse_css_parts(
+	file_string: &mut str,
+	selectors: &mut Selectors,
+) {
+	for capture in style_regex::CSS_PARTS.captures_iter(file_string) {
+		// Matched to a rule block or comment.
+		if capture.at(2).is_none() {
+			continue;
+		}
+
+		super::analyse_string_of_tokens(
+			&mut capture.at(2).unwrap().to_string(),
+			selectors,
+			"part",
+			Some(SelectorUsage::Style),
+		);
+	}
+}
+
+/// Rewrite shadow part names in `::part()` pseudo-elements.
+pub fn rewrite_css_parts(
+	file_string: &mut String,
+	selectors: &Selectors,
+) {
+	*file_string = style_regex::CSS_PARTS.replace_all(file_string, |capture: &Captures| {
+		// Matched to a rule block or comment. Leave it as is.
+		if capture.at(2).is_none() {
+			return capture.at(0).unwrap().to_owned();
+		}
+
+		let mut names = capture.at(2).unwrap().to_string();
+		super::rewrite_string_of_tokens(&mut names, selectors, "part");
+
+		format!("{pseudo}{names})", pseudo = capture.at(1).unwrap())
+	});
+}
+
 // Analyse CSS attribute selectors.
 pub fn analyse_css_attributes(
 	file_string: &mut str,
@@ -100,7 +157,7 @@ pub fn analyse_css_attributes(
 		let attribute_flag: &str = capture.at(6).unwrap_or("");
 		let mut attribute_value: String = capture.at(4).unwrap().to_string();
 
-		if !WHITELIST.get().unwrap().contains_key(&attribute_name) {
+		if get_attribute_designation(&attribute_name, None).is_none() {
 			continue;
 		}
 
@@ -112,10 +169,10 @@ pub fn analyse_css_attributes(
 
 		// Work out if value(s) are classes, IDs or selectors.
 		let attribute_type_designation: &str =
-			WHITELIST.get().unwrap().get(&attribute_name).unwrap();
+			get_attribute_designation(&attribute_name, None).unwrap();
 
 		match attribute_type_designation {
-			"id" | "class" => {
+			"id" | "class" | "part" => {
 				super::analyse_string_of_tokens(
 					&mut attribute_value,
 					selectors,
@@ -156,7 +213,7 @@ pub fn rewrite_css_attributes(
 		let mut attribute_value: String = capture.at(4).unwrap().to_string();
 
 		// Attribute does not contain classes and/or IDs. Leave it as is.
-		if !WHITELIST.get().unwrap().contains_key(&attribute_name) {
+		if get_attribute_designation(&attribute_name, None).is_none() {
 			return capture.at(0).unwrap().to_string();
 		}
 
@@ -168,10 +225,10 @@ pub fn rewrite_css_attributes(
 
 		// Work out if value(s) are classes, IDs or selectors.
 		let attribute_type_designation: &str =
-			WHITELIST.get().unwrap().get(&attribute_name).unwrap();
+			get_attribute_designation(&attribute_name, None).unwrap();
 
 		match attribute_type_designation {
-			"id" | "class" => {
+			"id" | "class" | "part" => {
 				super::rewrite_string_of_tokens(
 					&mut attribute_value,
 					selectors,
@@ -0,0 +1,238 @@
+use std::collections::BTreeMap;
+
+use minify_selectors_utils::*;
+
+use crate::markup::html_attributes::get_attribute_designation;
+use crate::markup::*;
+use crate::pragmas::*;
+use crate::script::*;
+
+
+
+
+/// Value of an attribute that is not processed, along with where it is.
+#[derive(Clone, Debug)]
+pub struct AttributeValue {
+	pub location: String,
+	pub value: String,
+}
+
+/// Custom attribute suggested from values that refer to known classes or IDs.
+#[derive(Clone, Debug)]
+pub struct AttributeSuggestion {
+	pub name: String,
+	pub kind: String,
+	// Share of the values that refer to known classes or IDs.
+	pub confidence: f64,
+	pub matches: usize,
+	pub occurrences: usize,
+	pub examples: Vec<String>,
+}
+
+/// Collect values of attributes in markup that are not processed.
+///
+/// Notes:
+///  - Only attributes with a hyphen in the name (i.e. `data-*` or those of
+///    custom elements) are collected, the rest are standard attributes.
+pub fn collect_attribute_values(
+	file_string: &str,
+	file_path: &str,
+	values: &mut BTreeMap<String, Vec<AttributeValue>>,
+) {
+	let tokens = tokenizer::tokenize(file_string, false);
+	let disabled_regions = find_disabled_regions(file_string, CommentSyntax::Markup);
+	let mut element = None;
+
+	for (tag, attribute) in get_processable_attributes(&tokens) {
+		if attribute.expression || !attribute.name.contains('-') {
+			continue;
+		}
+
+		let (Some(value), Some((start, _))) = (attribute.value(file_string), attribute.value)
+		else {
+			continue;
+		};
+
+		if disabled_regions
+			.iter()
+			.any(|(region_start, region_end)| (*region_start..*region_end).contains(&start))
+		{
+			continue;
+		}
+
+		if get_attribute_designation(
+			&attribute.name,
+			Some(get_cached_element(&mut element, file_string, tag)),
+		)
+		.is_some()
+		{
+			continue;
+		}
+
+		let line = file_string[..start].matches('\n').count() + 1;
+
+		values
+			.entry(attribute.name.clone())
+			.or_default()
+			.push(AttributeValue {
+				location: format!("{file_path}:{line}"),
+				value: unescape_html_chars(value),
+			});
+	}
+}
+
+/// Collect values of JSX element attributes that are not processed, same as
+/// `collect_attribute_values`.
+pub fn collect_jsx_attribute_values(
+	file_string: &str,
+	file_path: &str,
+	values: &mut BTreeMap<String, Vec<AttributeValue>>,
+) {
+	let tokens = tokenize_js(file_string);
+	let disabled_regions = find_disabled_regions(file_string, CommentSyntax::Script);
+
+	for jsx_element in find_jsx_elements(file_string, &tokens) {
+		let element = get_jsx_element(file_string, &jsx_element);
+
+		for ((name, argument), (_, value)) in jsx_element.attributes.iter().zip(&element.attributes)
+		{
+			if !name.contains('-')
+				|| disabled_regions
+					.iter()
+					.any(|(start, end)| (*start..*end).contains(&argument.start))
+				|| get_attribute_designation(name, Some(&element)).is_some()
+			{
+				continue;
+			}
+
+			let line = file_string[..argument.start].matches('\n').count() + 1;
+
+			values
+				.entry(name.to_string())
+				.or_default()
+				.push(AttributeValue {
+					location: format!("{file_path}:{line}"),
+					value: value.to_string(),
+				});
+		}
+	}
+}
+
+/// Suggest custom attributes from the collected values, sorted by confidence.
+///
+/// Notes:
+///  - Values are checked against the classes and IDs found when reading the
+///    files, so selectors need to be analysed first.
+pub fn suggest_custom_attributes(
+	values: &BTreeMap<String, Vec<AttributeValue>>,
+	selectors: &Selectors,
+) -> Vec<AttributeSuggestion> {
+	let mut suggestions: Vec<AttributeSuggestion> = vec![];
+
+	for (name, values) in values {
+		let mut kinds: BTreeMap<&str, Vec<&AttributeValue>> = BTreeMap::new();
+
+		for value in values {
+			if let Some(kind) = get_value_kind(&value.value, selectors) {
+				kinds.entry(kind).or_default().push(value);
+			}
+		}
+
+		let Some((kind, matches)) = kinds.into_iter().max_by_key(|(_, matches)| matches.len())
+		else {
+			continue;
+		};
+
+		suggestions.push(AttributeSuggestion {
+			name: name.to_string(),
+			kind: kind.to_string(),
+			confidence: matches.len() as f64 / values.len() as f64,
+			matches: matches.len(),
+			occurrences: values.len(),
+			examples: matches
+				.iter()
+				.take(3)
+				.map(|value| format!("{} {}=\"{}\"", value.location, name, value.value))
+				.collect(),
+		});
+	}
+
+	suggestions.sort_by(|a, b| {
+		b.confidence
+			.total_cmp(&a.confidence)
+			.then(b.matches.cmp(&a.matches))
+			.then(a.name.cmp(&b.name))
+	});
+
+	suggestions
+}
+
+// Work out what an attribute value contains, if all of it refers to known
+// classes or IDs.
+fn get_value_kind(
+	value: &str,
+	selectors: &Selectors,
+) -> Option<&'static str> {
+	let value = value.trim();
+	let is_known = |selector: String| selectors.map.contains_key(&selector);
+
+	if value.is_empty() {
+		return None;
+	}
+
+	// Space-separated list of IDs or classes.
+	if value
+		.split_whitespace()
+		.all(|name| is_known(format!("#{name}")))
+	{
+		return Some("id");
+	}
+	if value
+		.split_whitespace()
+		.all(|name| is_known(format!(".{name}")))
+	{
+		return Some("class");
+	}
+
+	// Link to an ID, i.e. `/page#foo`.
+	if let Some((url, id)) = value.rsplit_once('#') {
+		if !url.is_empty() && !value.contains(char::is_whitespace) && is_known(format!("#{id}")) {
+			return Some("anchor");
+		}
+	}
+
+	// Selector made up of known classes and IDs, i.e. `#menu > .item`.
+	let names = find_selector_names(value);
+
+	if !names.is_empty() && names.into_iter().all(|name| is_known(name.to_string())) {
+		return Some("selector");
+	}
+
+	None
+}
+
+// Find class and ID names (including the `.` or `#`) in a selector.
+fn find_selector_names(value: &str) -> Vec<&str> {
+	let mut names = vec![];
+	let mut start: Option<usize> = None;
+
+	for (index, character) in value.char_indices().chain([(value.len(), ' ')]) {
+		let is_name_character = character.is_alphanumeric() || matches!(character, '-' | '_');
+
+		if let Some(name_start) = start {
+			if is_name_character {
+				continue;
+			}
+			if index > name_start + 1 {
+				names.push(&value[name_start..index]);
+			}
+			start = None;
+		}
+
+		if matches!(character, '.' | '#') {
+			start = Some(index);
+		}
+	}
+
+	names
+}
@@ -0,0 +1,367 @@
+use minify_selectors_utils::*;
+
+use crate::markup::html_attributes::get_attribute_designation;
+use crate::markup::tokenizer;
+use crate::markup::*;
+use crate::pragmas::*;
+
+
+
+
+// Template tags are swapped out for placeholders while the markup is being
+// processed, which are made up of characters from the private use area so
+// that they do not clash with the rest of the file. Tags that output a value
+// and control flow tags (or comments) are told apart, as the latter are
+// treated like whitespace between class and ID tokens. Any of these characters
+// that are already in the file are escaped, with the code point written out
+// in place of the index.
+const OUTPUT_PLACEHOLDER: (char, char, u32) = ('\u{E000}', '\u{E001}', 0xe010);
+const CONTROL_PLACEHOLDER: (char, char, u32) = ('\u{E002}', '\u{E003}', 0xe020);
+const ESCAPE_PLACEHOLDER: (char, char, u32) = ('\u{E004}', '\u{E005}', 0xe010);
+
+
+
+
+/// Analyse server-side template (Handlebars, Liquid, Jinja, ERB or PHP),
+/// returning any class and ID attribute values that are partly made up of
+/// template tags, which are left as is.
+pub fn analyse_template(
+	file_string: &mut str,
+	selectors: &mut Selectors,
+	config: &Config,
+	dialect: &str,
+) -> Vec<String> {
+	let tags = find_template_tags(file_string, dialect);
+	let mut masked = mask_template_tags(file_string, &tags, dialect);
+
+	analyse_html(&mut masked, selectors, config, None);
+	find_dynamic_parts(&masked, file_string, &tags)
+}
+
+/// Rewrite server-side template (Handlebars, Liquid, Jinja, ERB or PHP).
+pub fn rewrite_template(
+	file_string: &mut String,
+	selectors: &Selectors,
+	config: &Config,
+	dialect: &str,
+) {
+	let tags = find_template_tags(file_string, dialect);
+	let mut masked = mask_template_tags(file_string, &tags, dialect);
+
+	rewrite_html(&mut masked, selectors, config);
+	*file_string = unmask_template_tags(&masked, file_string, &tags);
+}
+
+/// Find byte ranges of the template tags in the file.
+///
+/// Notes:
+///  - Tags that are not closed run up until the end of the file, as PHP files
+///    may leave off the closing tag.
+pub fn find_template_tags(
+	file_string: &str,
+	dialect: &str,
+) -> Vec<(usize, usize)> {
+	let bytes = file_string.as_bytes();
+	let delimiters = get_delimiters(dialect);
+	let mut tags = vec![];
+	let mut position: usize = 0;
+
+	while position < bytes.len() {
+		let Some((open, close)) = delimiters
+			.iter()
+			.find(|(open, _)| bytes[position..].starts_with(open.as_bytes()))
+		else {
+			position += 1;
+			continue;
+		};
+
+		let end = match file_string[position + open.len()..].find(close) {
+			Some(offset) => position + open.len() + offset + close.len(),
+			None => bytes.len(),
+		};
+
+		tags.push((position, end));
+		position = end;
+	}
+
+	tags
+}
+
+/// Join static parts of a template with output placeholders in between,
+/// so that the dynamic parts are left as is when processed.
+pub fn join_with_placeholders(parts: &[&str]) -> String {
+	let mut joined = String::new();
+
+	for (index, part) in parts.iter().enumerate() {
+		if index > 0 {
+			joined.push_str(&get_placeholder(index - 1, OUTPUT_PLACEHOLDER));
+		}
+		joined.push_str(&escape_placeholder_chars(part));
+	}
+
+	joined
+}
+
+/// Split string back into the static parts either side of placeholders.
+pub fn split_at_placeholders(masked: &str) -> Vec<String> {
+	let mut parts = vec![String::new()];
+
+	for placeholder in parse_placeholders(masked) {
+		match placeholder {
+			Placeholder::Text(text) => parts.last_mut().unwrap().push_str(text),
+			Placeholder::Escaped(character) => parts.last_mut().unwrap().push(character),
+			Placeholder::Tag(_) => parts.push(String::new()),
+		}
+	}
+
+	parts
+}
+
+/// Check if the string contains a template tag placeholder, selectors
+/// that do are left as is.
+pub fn contains_placeholder(string: &str) -> bool {
+	string.contains(is_placeholder_char)
+}
+
+// Opening and closing delimiters of template tags, longest first where
+// they share the same start.
+fn get_delimiters(dialect: &str) -> &'static [(&'static str, &'static str)] {
+	match dialect {
+		"handlebars" => &[("{{!--", "--}}"), ("{{{", "}}}"), ("{{", "}}")],
+		"liquid" => &[("{%", "%}"), ("{{", "}}")],
+		"jinja" => &[("{#", "#}"), ("{%", "%}"), ("{{", "}}")],
+		"erb" => &[("<%", "%>")],
+		"php" => &[("<?php", "?>"), ("<?=", "?>")],
+		_ => &[],
+	}
+}
+
+// Check if template tag outputs a value, rather than being a comment
+// or control flow (i.e. `{{#if foo}}` or `{% if foo %}`).
+fn is_output_tag(
+	tag: &str,
+	dialect: &str,
+) -> bool {
+	match dialect {
+		"handlebars" => {
+			let expression = tag
+				.trim_start_matches('{')
+				.trim_start_matches('~')
+				.trim_start();
+
+			!expression.starts_with(['#', '/', '!', '^', '>']) && !expression.starts_with("else")
+		},
+		"liquid" | "jinja" => tag.starts_with("{{"),
+		"erb" => tag.starts_with("<%="),
+		"php" => {
+			let expression = tag.trim_start_matches("<?php").trim_start();
+			tag.starts_with("<?=")
+				|| expression.starts_with("echo")
+				|| expression.starts_with("print")
+		},
+		_ => false,
+	}
+}
+
+// Swap out template tags for placeholders, with the index of the tag
+// written out in private use area digits.
+fn mask_template_tags(
+	file_string: &str,
+	tags: &[(usize, usize)],
+	dialect: &str,
+) -> String {
+	let mut masked = String::with_capacity(file_string.len());
+	let mut position: usize = 0;
+
+	for (index, (start, end)) in tags.iter().enumerate() {
+		let placeholder = match is_output_tag(&file_string[*start..*end], dialect) {
+			true => OUTPUT_PLACEHOLDER,
+			false => CONTROL_PLACEHOLDER,
+		};
+
+		masked.push_str(&escape_placeholder_chars(&file_string[position..*start]));
+		masked.push_str(&get_placeholder(index, placeholder));
+		position = *end;
+	}
+
+	masked.push_str(&escape_placeholder_chars(&file_string[position..]));
+	masked
+}
+
+// Escape any characters in the string that are used for placeholders.
+fn escape_placeholder_chars(string: &str) -> String {
+	if !contains_placeholder(string) {
+		return string.to_string();
+	}
+
+	let mut escaped = String::with_capacity(string.len());
+
+	for character in string.chars() {
+		match is_placeholder_char(character) {
+			true => {
+				escaped.push_str(&get_placeholder(character as usize, ESCAPE_PLACEHOLDER));
+			},
+			false => escaped.push(character),
+		}
+	}
+
+	escaped
+}
+
+fn is_placeholder_char(character: char) -> bool {
+	('\u{E000}'..='\u{E02F}').contains(&character)
+}
+
+// Placeholder for the template tag at the given index, with the index
+// written out in private use area digits.
+fn get_placeholder(
+	index: usize,
+	(open, close, zero): (char, char, u32),
+) -> String {
+	let mut placeholder = String::from(open);
+
+	for digit in index.to_string().chars() {
+		placeholder.push(char::from_u32(zero + digit.to_digit(10).unwrap()).unwrap());
+	}
+	placeholder.push(close);
+
+	placeholder
+}
+
+// Swap placeholders back to their original template tags.
+fn unmask_template_tags(
+	masked: &str,
+	file_string: &str,
+	tags: &[(usize, usize)],
+) -> String {
+	let mut unmasked = String::with_capacity(file_string.len());
+
+	for placeholder in parse_placeholders(masked) {
+		match placeholder {
+			Placeholder::Text(text) => unmasked.push_str(text),
+			Placeholder::Escaped(character) => unmasked.push(character),
+			Placeholder::Tag(index) => {
+				if let Some((start, end)) = tags.get(index) {
+					unmasked.push_str(&file_string[*start..*end]);
+				}
+			},
+		}
+	}
+
+	unmasked
+}
+
+enum Placeholder<'a> {
+	Text(&'a str),
+	Escaped(char),
+	// Index of the template tag.
+	Tag(usize),
+}
+
+// Split masked string into text and placeholders, along with the index (or
+// escaped character) written out in each placeholder. Anything that is not
+// a whole placeholder is left as text.
+fn parse_placeholders(masked: &str) -> Vec<Placeholder<'_>> {
+	let mut placeholders = vec![];
+	let mut text_start: usize = 0;
+	// Opening character, index so far and start of the current placeholder.
+	let mut current: Option<(char, usize, usize)> = None;
+
+	for (offset, character) in masked.char_indices() {
+		match (current, character) {
+			(_, '\u{E000}' | '\u{E002}' | '\u{E004}') => current = Some((character, 0, offset)),
+			(Some((open, index, start)), '\u{E010}'..='\u{E019}' | '\u{E020}'..='\u{E029}') => {
+				current = Some((open, index * 10 + (character as usize & 0xf), start));
+			},
+			(Some((open, index, start)), '\u{E001}' | '\u{E003}' | '\u{E005}')
+				if open as u32 + 1 == character as u32 =>
+			{
+				if text_start < start {
+					placeholders.push(Placeholder::Text(&masked[text_start..start]));
+				}
+				placeholders.push(match open {
+					'\u{E004}' => {
+						Placeholder::Escaped(
+							char::from_u32(index as u32).unwrap_or(char::REPLACEMENT_CHARACTER),
+						)
+					},
+					_ => Placeholder::Tag(index),
+				});
+				text_start = offset + character.len_utf8();
+				current = None;
+			},
+			_ => current = None,
+		}
+	}
+
+	if text_start < masked.len() {
+		placeholders.push(Placeholder::Text(&masked[text_start..]));
+	}
+
+	placeholders
+}
+
+// Find class and ID tokens that are output by, or joined with, template
+// tags (e.g. `btn-{{ variant }}`), leaving out any in disabled regions.
+fn find_dynamic_parts(
+	masked: &str,
+	file_string: &str,
+	tags: &[(usize, usize)],
+) -> Vec<String> {
+	let tokens = tokenizer::tokenize(masked, false);
+	let disabled_regions = find_disabled_regions(masked, CommentSyntax::Markup);
+	let mut dynamic_parts = vec![];
+	let mut element = None;
+
+	for (tag, attribute) in get_processable_attributes(&tokens) {
+		let (Some(value), Some(designation @ ("class" | "id" | "anchor"))) = (
+			attribute.value(masked),
+			get_attribute_designation(
+				&attribute.name,
+				Some(get_cached_element(&mut element, masked, tag)),
+			)
+			.map(String::as_str),
+		) else {
+			continue;
+		};
+
+		if disabled_regions
+			.iter()
+			.any(|(start, end)| (*start..*end).contains(&attribute.name_start))
+		{
+			continue;
+		}
+
+		// Only the target ID of a link is a selector.
+		if designation == "anchor" {
+			if value
+				.rsplit_once('#')
+				.is_some_and(|(_, target_id)| target_id.contains(OUTPUT_PLACEHOLDER.0))
+			{
+				dynamic_parts.push(format!(
+					"{name}=\"{value}\"",
+					name = attribute.name,
+					value = unmask_template_tags(value, file_string, tags),
+				));
+			}
+
+			continue;
+		}
+
+		// Control flow tags separate tokens, same as whitespace.
+		for part in value.split(|character: char| {
+			character.is_whitespace()
+				|| matches!(character, '\u{E002}' | '\u{E003}' | '\u{E020}'..='\u{E029}')
+		}) {
+			if part.contains(OUTPUT_PLACEHOLDER.0) {
+				dynamic_parts.push(format!(
+					"{name}=\"{part}\"",
+					name = attribute.name,
+					part = unmask_template_tags(part, file_string, tags),
+				));
+			}
+		}
+	}
+
+	dynamic_parts
+}
@@ -0,0 +1,383 @@
+use minify_selectors_utils::*;
+
+use crate::Replacement;
+
+
+
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum TokenKind {
+	// String literal, i.e. `'foo'` or `"foo"`.
+	Literal,
+	// Attribute, i.e. `@class`.
+	Attribute,
+	// Function and element names, axes, numbers, etc.
+	Name,
+	Punctuator,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Token {
+	kind: TokenKind,
+	start: usize,
+	end: usize,
+}
+
+// Class or ID literal found in an XPath expression, or predicate that
+// cannot be rewritten.
+enum Reference {
+	// Literal that is compared to the whole attribute value, i.e.
+	// `@class = 'foo bar'`, `id('foo')` or the padded form of a class
+	// token, i.e. `contains(concat(' ', normalize-space(@class), ' '), ' foo ')`.
+	Literal(&'static str, usize, usize),
+	// Class that is checked with `contains(@class, 'foo')`, along with
+	// the range of the call and of the literal.
+	ContainsClass(usize, usize, usize, usize),
+	// Predicate that matches part of a class or ID, i.e.
+	// `starts-with(@id, 'foo')`, along with its range.
+	Partial(usize, usize),
+}
+
+
+
+
+/// Analyse XPath expression (i.e. passed to `document.evaluate`).
+pub fn analyse_xpath(
+	expression: &str,
+	selectors: &mut Selectors,
+) {
+	for reference in find_references(expression) {
+		match reference {
+			Reference::Literal(context, start, end) => {
+				super::analyse_string_of_tokens(
+					&mut expression[start + 1..end - 1].to_string(),
+					selectors,
+					context,
+					Some(SelectorUsage::Script),
+				);
+			},
+
+			Reference::ContainsClass(_, _, start, end) => {
+				super::analyse_string_of_tokens(
+					&mut expression[start + 1..end - 1].to_string(),
+					selectors,
+					"class",
+					Some(SelectorUsage::Script),
+				);
+			},
+
+			Reference::Partial(start, end) => {
+				selectors.warnings.push(format!(
+					"XPath predicate left as is: {}",
+					&expression[start..end],
+				));
+			},
+		}
+	}
+}
+
+/// Rewrite XPath expression.
+///
+/// Notes:
+///  - `contains(@class, 'foo')` is rewritten to only match the whole class
+///    name, as the encoded name could be part of other encoded names.
+pub fn rewrite_xpath(
+	expression: &mut String,
+	selectors: &Selectors,
+) {
+	let mut replacements: Vec<Replacement> = vec![];
+
+	for reference in find_references(expression) {
+		match reference {
+			Reference::Literal(context, start, end) => {
+				let mut value = expression[start + 1..end - 1].to_string();
+				super::rewrite_string_of_tokens(&mut value, selectors, context);
+
+				if value != expression[start + 1..end - 1] {
+					replacements.push(Replacement {
+						start: start + 1,
+						end: end - 1,
+						value,
+					});
+				}
+			},
+
+			Reference::ContainsClass(call_start, call_end, start, end) => {
+				let mut class = expression[start + 1..end - 1].to_string();
+				super::rewrite_string_of_tokens(&mut class, selectors, "class");
+
+				if class != expression[start + 1..end - 1] {
+					let quote = &expression[start..start + 1];
+					replacements.push(Replacement {
+						start: call_start,
+						end: call_end,
+						value: format!(
+							"contains(concat({quote} {quote}, normalize-space(@class), {quote} \
+							 {quote}), {quote} {class} {quote})"
+						),
+					});
+				}
+			},
+
+			Reference::Partial(..) => {},
+		}
+	}
+
+	super::splice_replacements(expression, replacements);
+}
+
+// Find class and ID literals, along with predicates that can only be
+// left as is.
+fn find_references(expression: &str) -> Vec<Reference> {
+	let tokens = tokenize(expression);
+	let mut references = vec![];
+
+	for index in 0..tokens.len() {
+		let token = &tokens[index];
+
+		match token.kind {
+			// `@class = 'foo'`
+			TokenKind::Attribute => {
+				if let (Some(context), Some(literal)) = (
+					get_context(expression, token),
+					get_compared_token(expression, &tokens, index + 1)
+						.filter(|literal| literal.kind == TokenKind::Literal),
+				) {
+					references.push(Reference::Literal(context, literal.start, literal.end));
+				}
+			},
+
+			// `'foo' = @class`
+			TokenKind::Literal => {
+				if let Some(context) = get_compared_token(expression, &tokens, index + 1)
+					.filter(|attribute| attribute.kind == TokenKind::Attribute)
+					.and_then(|attribute| get_context(expression, attribute))
+				{
+					references.push(Reference::Literal(context, token.start, token.end));
+				}
+			},
+
+			TokenKind::Name => {
+				let name = &expression[token.start..token.end];
+
+				if !tokens
+					.get(index + 1)
+					.is_some_and(|token| is_punctuator(expression, token, "("))
+				{
+					continue;
+				}
+
+				let Some((arguments, close)) = get_arguments(expression, &tokens, index + 1) else {
+					continue;
+				};
+
+				match (name, &arguments[..]) {
+					// `id('foo bar')`
+					("id", [[literal]]) if literal.kind == TokenKind::Literal => {
+						references.push(Reference::Literal("id", literal.start, literal.end));
+					},
+
+					("contains" | "starts-with" | "ends-with", [haystack, needle]) => {
+						let Some(context) = haystack
+							.iter()
+							.filter(|token| token.kind == TokenKind::Attribute)
+							.find_map(|token| get_context(expression, token))
+						else {
+							continue;
+						};
+
+						let literal = match needle {
+							[literal] if literal.kind == TokenKind::Literal => Some(literal),
+							_ => None,
+						};
+						let value =
+							literal.map(|literal| &expression[literal.start + 1..literal.end - 1]);
+
+						match (name, context, haystack.len(), literal, value) {
+							("contains", "class", 1, Some(literal), Some(value))
+								if is_single_name(value) =>
+							{
+								references.push(Reference::ContainsClass(
+									token.start,
+									tokens[close].end,
+									literal.start,
+									literal.end,
+								));
+							},
+							("contains", "class", _, Some(literal), Some(value))
+								if value.starts_with(' ')
+									&& value.ends_with(' ') && is_single_name(value.trim()) =>
+							{
+								references.push(Reference::Literal(
+									"class",
+									literal.start,
+									literal.end,
+								));
+							},
+							_ => {
+								references.push(Reference::Partial(token.start, tokens[close].end));
+							},
+						}
+					},
+
+					_ => {},
+				}
+			},
+
+			TokenKind::Punctuator => {},
+		}
+	}
+
+	references
+}
+
+// Get whether attribute contains classes or an ID.
+fn get_context(
+	expression: &str,
+	attribute: &Token,
+) -> Option<&'static str> {
+	match &expression[attribute.start..attribute.end] {
+		"@class" => Some("class"),
+		"@id" => Some("id"),
+		_ => None,
+	}
+}
+
+// Get literal or attribute that is compared (with `=` or `!=`) to the
+// token before the operator at the given index.
+fn get_compared_token<'a>(
+	expression: &str,
+	tokens: &'a [Token],
+	index: usize,
+) -> Option<&'a Token> {
+	let operator = tokens.get(index)?;
+
+	if !is_punctuator(expression, operator, "=") && !is_punctuator(expression, operator, "!=") {
+		return None;
+	}
+
+	tokens
+		.get(index + 1)
+		.filter(|token| matches!(token.kind, TokenKind::Literal | TokenKind::Attribute))
+}
+
+// Get tokens of each argument of a function call, split by commas, along
+// with the index of the closing bracket.
+fn get_arguments<'a>(
+	expression: &str,
+	tokens: &'a [Token],
+	open: usize,
+) -> Option<(Vec<&'a [Token]>, usize)> {
+	let mut arguments = vec![];
+	let mut depth: usize = 0;
+	let mut start = open + 1;
+
+	for (index, token) in tokens.iter().enumerate().skip(open) {
+		match &expression[token.start..token.end] {
+			"(" | "[" if token.kind == TokenKind::Punctuator => depth += 1,
+			")" | "]" if token.kind == TokenKind::Punctuator => {
+				depth -= 1;
+
+				if depth == 0 {
+					if index > start {
+						arguments.push(&tokens[start..index]);
+					}
+					return Some((arguments, index));
+				}
+			},
+			"," if token.kind == TokenKind::Punctuator && depth == 1 => {
+				arguments.push(&tokens[start..index]);
+				start = index + 1;
+			},
+			_ => {},
+		}
+	}
+
+	None
+}
+
+fn is_punctuator(
+	expression: &str,
+	token: &Token,
+	punctuator: &str,
+) -> bool {
+	token.kind == TokenKind::Punctuator && &expression[token.start..token.end] == punctuator
+}
+
+// Check value is a single class or ID name, without any whitespace.
+fn is_single_name(value: &str) -> bool {
+	!value.is_empty() && !value.contains(char::is_whitespace)
+}
+
+// Split XPath expression into tokens, whitespace is skipped over.
+fn tokenize(expression: &str) -> Vec<Token> {
+	let bytes = expression.as_bytes();
+	let mut tokens = vec![];
+	let mut index: usize = 0;
+
+	while index < bytes.len() {
+		let start = index;
+
+		let kind = match bytes[index] {
+			byte if byte.is_ascii_whitespace() => {
+				index += 1;
+				continue;
+			},
+
+			quote @ (b'\'' | b'"') => {
+				match expression[index + 1..].find(quote as char) {
+					Some(offset) => {
+						index += 1 + offset + 1;
+						TokenKind::Literal
+					},
+					// Literal that is not closed.
+					None => {
+						index = bytes.len();
+						TokenKind::Punctuator
+					},
+				}
+			},
+
+			b'@' => {
+				index += 1 + get_name_length(&expression[index + 1..]);
+				TokenKind::Attribute
+			},
+
+			byte if byte.is_ascii_alphanumeric() || byte == b'_' || byte >= 0x80 => {
+				// Any other non-ASCII character (i.e. a placeholder for a
+				// template substitution) is taken as a name on its own.
+				index += match get_name_length(&expression[index..]) {
+					0 => expression[index..].chars().next().map_or(1, char::len_utf8),
+					length => length,
+				};
+				TokenKind::Name
+			},
+
+			_ => {
+				index += match &expression[index..] {
+					rest if rest.starts_with("!=") || rest.starts_with("//") => 2,
+					rest => rest.chars().next().map_or(1, char::len_utf8),
+				};
+				TokenKind::Punctuator
+			},
+		};
+
+		tokens.push(Token {
+			kind,
+			start,
+			end: index,
+		});
+	}
+
+	tokens
+}
+
+// Get length of the name at the start of the string, names may contain
+// hyphens, periods and colons (i.e. `normalize-space` or `xml:lang`).
+fn get_name_length(string: &str) -> usize {
+	string
+		.find(|character: char| {
+			!(character.is_alphanumeric() || matches!(character, '-' | '_' | '.' | ':' | '*'))
+		})
+		.unwrap_or(string.len())
+}
@@ -0,0 +1,20 @@
+describe('checkout', () => {
+	it('opens the modal', () => {
+		cy.visit('/');
+		cy.get('.d').click();
+		cy.get('#b').find('.e .g').should('be.visible');
+		cy.get('.e').should('have.class', 'f').and('not.have.class', 'b');
+		cy.get('nav > a').first().should('have.id', 'c').parents('#a');
+		cy.contains('.a', 'Cart').closest('#c');
+		cy.contains('Checkout');
+		cy.get('.c').children('input#d').type('.checkout-button');
+		cy.get('[data-test=missing]').should('match', '.c.b');
+		cy.get('.test-only').should('have.class', 'test-only').find('#test-only-id .c');
+		cy.get('#test-only-id').should('have.id', 'test-only-id').and('match', '.test-only');
+		// Not Cypress commands or assertions
+		const names = ['.modal', '.card'];
+		names.contains('.modal', '.card');
+		names.filter('.modal').find('.card');
+		expect(names).to.include('.card').and('have.class', 'active');
+	});
+});
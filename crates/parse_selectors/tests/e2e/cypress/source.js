@@ -0,0 +1,20 @@
+describe('checkout', () => {
+	it('opens the modal', () => {
+		cy.visit('/');
+		cy.get('.checkout-button').click();
+		cy.get('#cart').find('.modal .close').should('be.visible');
+		cy.get('.modal').should('have.class', 'is-open').and('not.have.class', 'active');
+		cy.get('nav > a').first().should('have.id', 'nav').parents('#main');
+		cy.contains('.nav-item', 'Cart').closest('#nav');
+		cy.contains('Checkout');
+		cy.get('.card').children('input#email').type('.checkout-button');
+		cy.get('[data-test=missing]').should('match', '.card.active');
+		cy.get('.test-only').should('have.class', 'test-only').find('#test-only-id .card');
+		cy.get('#test-only-id').should('have.id', 'test-only-id').and('match', '.test-only');
+		// Not Cypress commands or assertions
+		const names = ['.modal', '.card'];
+		names.contains('.modal', '.card');
+		names.filter('.modal').find('.card');
+		expect(names).to.include('.card').and('have.class', 'active');
+	});
+});
@@ -0,0 +1,15 @@
+import { test, expect } from '@playwright/test';
+
+test('checkout', async ({ page }) => {
+	await page.goto('/');
+	await page.fill('#d', 'user@example.com');
+	await page.click('.d');
+	await page.locator('.e >> text=Close').click();
+	await page.locator('css=.c >> .g').click();
+	await page.locator('id=c').isVisible();
+	await page.locator('text=.card').isVisible();
+	await page.locator('xpath=//div[@class="card"]').isVisible();
+	await expect(page.locator('.e')).toHaveClass('e f');
+	await expect(page.locator('#c')).toHaveId('c');
+	await page.evaluate(() => document.querySelector('.a.b'));
+});
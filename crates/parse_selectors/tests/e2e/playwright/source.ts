@@ -0,0 +1,15 @@
+import { test, expect } from '@playwright/test';
+
+test('checkout', async ({ page }) => {
+	await page.goto('/');
+	await page.fill('#email', 'user@example.com');
+	await page.click('.checkout-button');
+	await page.locator('.modal >> text=Close').click();
+	await page.locator('css=.card >> .close').click();
+	await page.locator('id=cart').isVisible();
+	await page.locator('text=.card').isVisible();
+	await page.locator('xpath=//div[@class="card"]').isVisible();
+	await expect(page.locator('.modal')).toHaveClass('modal is-open');
+	await expect(page.locator('#cart')).toHaveId('cart');
+	await page.evaluate(() => document.querySelector('.nav-item.active'));
+});
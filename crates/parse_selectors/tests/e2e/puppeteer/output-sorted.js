@@ -0,0 +1,11 @@
+const page = await browser.newPage();
+
+await page.goto('http://localhost:8080');
+await page.waitForSelector('#a .c');
+await page.type('#d', 'user@example.com');
+await page.click('.d');
+const links = await page.$$('.a');
+const label = await page.$eval('.e .g', (button) => button.textContent);
+await page.$('pierce/.c');
+await page.$('text/Checkout');
+await page.$('::-p-text(Checkout)');
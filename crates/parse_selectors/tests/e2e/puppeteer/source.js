@@ -0,0 +1,11 @@
+const page = await browser.newPage();
+
+await page.goto('http://localhost:8080');
+await page.waitForSelector('#main .card');
+await page.type('#email', 'user@example.com');
+await page.click('.checkout-button');
+const links = await page.$$('.nav-item');
+const label = await page.$eval('.modal .close', (button) => button.textContent);
+await page.$('pierce/.card');
+await page.$('text/Checkout');
+await page.$('::-p-text(Checkout)');
@@ -0,0 +1,12 @@
+describe('checkout', () => {
+	it('opens the modal', async () => {
+		await browser.url('/');
+		await $('.d').click();
+		await $('#c').$('.e .g').click();
+		const items = await $$('#a .a');
+		await $('=Cart').click();
+		await $('*=Check').click();
+		await $('//div[@id="cart"]').click();
+		await expect($('.e')).toHaveElementClass('f');
+	});
+});
@@ -0,0 +1,12 @@
+describe('checkout', () => {
+	it('opens the modal', async () => {
+		await browser.url('/');
+		await $('.checkout-button').click();
+		await $('#cart').$('.modal .close').click();
+		const items = await $$('#nav .nav-item');
+		await $('=Cart').click();
+		await $('*=Check').click();
+		await $('//div[@id="cart"]').click();
+		await expect($('.modal')).toHaveElementClass('is-open');
+	});
+});
@@ -0,0 +1,23 @@
+document.querySelector('#a .a').classList.add('b');
+
+const ACTIVE = /* @class */ 'b';
+const STATES = [/* @class */ 'b c', /** @class */ "d"];
+const CARD = /* @class */ `a ${open ? ACTIVE : ''}`;
+const MAIN = /* @id */ 'a';
+
+const routes = {
+	home: /* @selector */ '#a > .a',
+	menu: /* @selector */ `.a.c, #b`,
+	escaped: /* @selector */ '.e',
+};
+
+// Annotations only apply to the literal straight after them
+const label = /* @class */ prefix + 'is-active';
+const text = /* @id */ `main-${index}`;
+const untouched = 'is-active'; /* @class */
+
+// Literals that are already processed are not processed twice
+document.getElementById(/* @id */ 'a');
+element.className = /* @class */ 'c';
+
+/* @unknown */ 'is-active';
@@ -0,0 +1,23 @@
+document.querySelector('#main .card').classList.add('is-active');
+
+const ACTIVE = /* @class */ 'is-active';
+const STATES = [/* @class */ 'is-active is-hidden', /** @class */ "is-loading"];
+const CARD = /* @class */ `card ${open ? ACTIVE : ''}`;
+const MAIN = /* @id */ 'main';
+
+const routes = {
+	home: /* @selector */ '#main > .card',
+	menu: /* @selector */ `.card.is-hidden, #sidebar`,
+	escaped: /* @selector */ '.card\\:hover',
+};
+
+// Annotations only apply to the literal straight after them
+const label = /* @class */ prefix + 'is-active';
+const text = /* @id */ `main-${index}`;
+const untouched = 'is-active'; /* @class */
+
+// Literals that are already processed are not processed twice
+document.getElementById(/* @id */ 'main');
+element.className = /* @class */ 'is-hidden';
+
+/* @unknown */ 'is-active';
@@ -0,0 +1,17 @@
+sheet.insertRule('.a > .b { color: red; }', 0);
+sheet.insertRule(`#a:hover { color: blue; }`);
+sheet.insertRule(rule);
+
+const stylesheet = new CSSStyleSheet();
+stylesheet.replaceSync('.c { mask: url(#b); }');
+
+styleEl.textContent = '.d, .a { display: none; }';
+styleEl.textContent += `#c .b { display: block; }`;
+
+// Not a stylesheet
+label.textContent = 'Total: 3.50 {approx}';
+label.textContent = '{count} items';
+
+// Substitutions are left as is
+sheet.insertRule(`.a { width: ${width}px; }`);
+styleEl.textContent = `.a { color: ${color}; }`;
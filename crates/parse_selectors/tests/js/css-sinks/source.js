@@ -0,0 +1,17 @@
+sheet.insertRule('.CLASS-1 > .CLASS-2 { color: red; }', 0);
+sheet.insertRule(`#ID-1:hover { color: blue; }`);
+sheet.insertRule(rule);
+
+const stylesheet = new CSSStyleSheet();
+stylesheet.replaceSync('.CLASS-3 { mask: url(#ID-2); }');
+
+styleEl.textContent = '.CLASS-4, .CLASS-1 { display: none; }';
+styleEl.textContent += `#ID-3 .CLASS-2 { display: block; }`;
+
+// Not a stylesheet
+label.textContent = 'Total: 3.50 {approx}';
+label.textContent = '{count} items';
+
+// Substitutions are left as is
+sheet.insertRule(`.CLASS-1 { width: ${width}px; }`);
+styleEl.textContent = `.CLASS-1 { color: ${color}; }`;
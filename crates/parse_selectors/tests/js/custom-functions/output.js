@@ -0,0 +1,12 @@
+const button = clsx('a', { 'b': active, c: disabled }, size === 'large' && 'd');
+const link = classnames(['a e', isPrimary ? 'f' : 'g']);
+const card = utils.cn('h', 'SELECTOR-9');
+const label = cn(`i`, { [computed]: true, variant: 'SELECTOR-11' });
+const menu = clsx(styles['SELECTOR-15'], t('SELECTOR-16'), { 'j': open ? 'SELECTOR-18' : '' });
+
+byId('a', 'b');
+$query('.a > #a');
+$query(selector);
+
+// Not a custom function
+notClsx('SELECTOR-14');
@@ -0,0 +1,12 @@
+const button = clsx('SELECTOR-1', { 'SELECTOR-2': active, SELECTOR_3: disabled }, size === 'large' && 'SELECTOR-4');
+const link = classnames(['SELECTOR-1 SELECTOR-5', isPrimary ? 'SELECTOR-6' : 'SELECTOR-7']);
+const card = utils.cn('SELECTOR-8', 'SELECTOR-9');
+const label = cn(`SELECTOR-10`, { [computed]: true, variant: 'SELECTOR-11' });
+const menu = clsx(styles['SELECTOR-15'], t('SELECTOR-16'), { 'SELECTOR-17': open ? 'SELECTOR-18' : '' });
+
+byId('SELECTOR-12', 'SELECTOR-13');
+$query('.SELECTOR-1 > #SELECTOR-12');
+$query(selector);
+
+// Not a custom function
+notClsx('SELECTOR-14');
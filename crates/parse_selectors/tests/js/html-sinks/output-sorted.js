@@ -0,0 +1,20 @@
+const doc = new DOMParser().parseFromString('<body class="a"><div id="a"></div></body>', 'text/html');
+const fragment = document.createRange().createContextualFragment('<a href="#a" class="b">Link</a>');
+element.setHTMLUnsafe('<div class="d"></div>');
+element.setHTML(markup);
+const parsed = Document.parseHTMLUnsafe("<p class='e'></p>");
+
+document.write('<div class="a">', '<span id="c"></span>', '</div>');
+document.writeln(`<p class="b"></p>`);
+
+template.innerHTML = `
+	<style>
+		.c > #b { color: red; }
+	</style>
+	<div class="c" id="b"></div>
+`;
+
+// Substitutions are left as is
+document.write(`<div class="a ${modifier}" id="a"></div>`);
+element.insertAdjacentHTML('beforeend', `<a href="#ID-${index}" class="CLASS-1-${size}">${label}</a>`);
+card.innerHTML = `<div class="a" id="ID-${id}">${title}</div>`;
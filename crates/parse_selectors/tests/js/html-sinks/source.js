@@ -0,0 +1,20 @@
+const doc = new DOMParser().parseFromString('<body class="CLASS-1"><div id="ID-1"></div></body>', 'text/html');
+const fragment = document.createRange().createContextualFragment('<a href="#ID-1" class="CLASS-2">Link</a>');
+element.setHTMLUnsafe('<div class="CLASS-3"></div>');
+element.setHTML(markup);
+const parsed = Document.parseHTMLUnsafe("<p class='CLASS-4'></p>");
+
+document.write('<div class="CLASS-1">', '<span id="ID-2"></span>', '</div>');
+document.writeln(`<p class="CLASS-2"></p>`);
+
+template.innerHTML = `
+	<style>
+		.CLASS-5 > #ID-3 { color: red; }
+	</style>
+	<div class="CLASS-5" id="ID-3"></div>
+`;
+
+// Substitutions are left as is
+document.write(`<div class="CLASS-1 ${modifier}" id="ID-1"></div>`);
+element.insertAdjacentHTML('beforeend', `<a href="#ID-${index}" class="CLASS-1-${size}">${label}</a>`);
+card.innerHTML = `<div class="CLASS-1" id="ID-${id}">${title}</div>`;
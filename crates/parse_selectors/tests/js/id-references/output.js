@@ -0,0 +1,12 @@
+label.htmlFor = 'd';
+button.setAttribute('popovertarget', 'a');
+button.setAttribute('commandfor', 'a');
+listbox.setAttribute('aria-activedescendant', 'b');
+listbox.setAttribute('aria-owns', 'b c');
+
+// Element reflections take an element rather than an ID.
+button.popoverTargetElement = document.getElementById('a');
+listbox.ariaActiveDescendantElement = document.querySelector('#b');
+
+// Element is not known, timing values are only processed on animation elements.
+animation.setAttribute('begin', 'ID-2.click+1s; ID-3.end');
@@ -0,0 +1,12 @@
+label.htmlFor = 'ID-1';
+button.setAttribute('popovertarget', 'ID-2');
+button.setAttribute('commandfor', 'ID-2');
+listbox.setAttribute('aria-activedescendant', 'ID-3');
+listbox.setAttribute('aria-owns', 'ID-3 ID-4');
+
+// Element reflections take an element rather than an ID.
+button.popoverTargetElement = document.getElementById('ID-2');
+listbox.ariaActiveDescendantElement = document.querySelector('#ID-3');
+
+// Element is not known, timing values are only processed on animation elements.
+animation.setAttribute('begin', 'ID-2.click+1s; ID-3.end');
@@ -0,0 +1,29 @@
+$('.a > .c').addClass('b g');
+jQuery('#b').removeClass('b').toggleClass('d', isOpen);
+
+const $menu = $('#a');
+if ($menu.hasClass('d')) {
+	$menu.find('.c').not('.e').children('li.a');
+	$menu.closest('.f').siblings('#c');
+}
+
+$menu.is(':not(.e)');
+$menu.filter(function() { return true; });
+$(document).on('click', '.a .h', toggle);
+$menu.off('click', toggle);
+
+$menu.attr('id', 'd');
+$menu.attr('data-toggle', 'SELECTOR-3');
+
+$('<div class="b i" id="e"></div>').appendTo('body');
+$menu.append('<a href="#a">Back to top</a>');
+$menu.html('SELECTOR-3');
+this.$menu.children('.a');
+$(`<div class="b ${cls}">`).appendTo('body');
+$menu.html(`<span class="a ${x}">`);
+$menu.find(`.c .${cls}`);
+
+// Not jQuery calls
+array.find(item => item.id === id);
+map.has('SELECTOR-15');
+items.filter('.SELECTOR-8').is('.SELECTOR-2');
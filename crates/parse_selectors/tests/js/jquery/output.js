@@ -0,0 +1,29 @@
+$('.b > .c').addClass('d e');
+jQuery('#a').removeClass('d').toggleClass('f', isOpen);
+
+const $menu = $('#b');
+if ($menu.hasClass('f')) {
+	$menu.find('.c').not('.g').children('li.b');
+	$menu.closest('.a').siblings('#c');
+}
+
+$menu.is(':not(.g)');
+$menu.filter(function() { return true; });
+$(document).on('click', '.b .h', toggle);
+$menu.off('click', toggle);
+
+$menu.attr('id', 'd');
+$menu.attr('data-toggle', 'SELECTOR-3');
+
+$('<div class="d i" id="e"></div>').appendTo('body');
+$menu.append('<a href="#b">Back to top</a>');
+$menu.html('SELECTOR-3');
+this.$menu.children('.b');
+$(`<div class="d ${cls}">`).appendTo('body');
+$menu.html(`<span class="b ${x}">`);
+$menu.find(`.c .${cls}`);
+
+// Not jQuery calls
+array.find(item => item.id === id);
+map.has('SELECTOR-15');
+items.filter('.SELECTOR-8').is('.SELECTOR-2');
@@ -0,0 +1,29 @@
+$('.SELECTOR-1 > .SELECTOR-2').addClass('SELECTOR-3 SELECTOR-4');
+jQuery('#SELECTOR-5').removeClass('SELECTOR-3').toggleClass('SELECTOR-6', isOpen);
+
+const $menu = $('#SELECTOR-7');
+if ($menu.hasClass('SELECTOR-6')) {
+	$menu.find('.SELECTOR-2').not('.SELECTOR-8').children('li.SELECTOR-1');
+	$menu.closest('.SELECTOR-9').siblings('#SELECTOR-10');
+}
+
+$menu.is(':not(.SELECTOR-8)');
+$menu.filter(function() { return true; });
+$(document).on('click', '.SELECTOR-1 .SELECTOR-11', toggle);
+$menu.off('click', toggle);
+
+$menu.attr('id', 'SELECTOR-12');
+$menu.attr('data-toggle', 'SELECTOR-3');
+
+$('<div class="SELECTOR-3 SELECTOR-13" id="SELECTOR-14"></div>').appendTo('body');
+$menu.append('<a href="#SELECTOR-7">Back to top</a>');
+$menu.html('SELECTOR-3');
+this.$menu.children('.SELECTOR-1');
+$(`<div class="SELECTOR-3 ${cls}">`).appendTo('body');
+$menu.html(`<span class="SELECTOR-1 ${x}">`);
+$menu.find(`.SELECTOR-2 .${cls}`);
+
+// Not jQuery calls
+array.find(item => item.id === id);
+map.has('SELECTOR-15');
+items.filter('.SELECTOR-8').is('.SELECTOR-2');
@@ -0,0 +1,22 @@
+const Button = ({ active }: { active: boolean }) => (
+	<button className="a b" id="a" onClick={() => document.querySelector('.a')}>
+		Don't use 'SELECTOR-4' or "SELECTOR-5" here
+	</button>
+);
+
+const Label = () => <label htmlFor={'a'} className={`c`}>{/* comment */}</label>;
+
+const Link = () => (
+	<>
+		<a href="#a" aria-describedby='b'>Link</a>
+		<a className="nav-link" data-target="#a">Tab</a>
+		<svg><use xlinkHref="#c" /></svg>
+		<svg><animate begin="a.click; 0s" /></svg>
+		<div className={active ? 'SELECTOR-9' : 'SELECTOR-10'} {...props} data-target="d" />
+		<Namespace.Component className="d">
+			{items.map((item) => <li key={item} className="e">{item}</li>)}
+		</Namespace.Component>
+	</>
+);
+
+el.classList.add('b', 'c', 'd', 'e');
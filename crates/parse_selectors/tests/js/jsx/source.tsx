@@ -0,0 +1,22 @@
+const Button = ({ active }: { active: boolean }) => (
+	<button className="SELECTOR-1 SELECTOR-2" id="SELECTOR-3" onClick={() => document.querySelector('.SELECTOR-1')}>
+		Don't use 'SELECTOR-4' or "SELECTOR-5" here
+	</button>
+);
+
+const Label = () => <label htmlFor={'SELECTOR-3'} className={`SELECTOR-6`}>{/* comment */}</label>;
+
+const Link = () => (
+	<>
+		<a href="#SELECTOR-3" aria-describedby='SELECTOR-7'>Link</a>
+		<a className="nav-link" data-target="#SELECTOR-3">Tab</a>
+		<svg><use xlinkHref="#SELECTOR-8" /></svg>
+		<svg><animate begin="SELECTOR-3.click; 0s" /></svg>
+		<div className={active ? 'SELECTOR-9' : 'SELECTOR-10'} {...props} data-target="SELECTOR-11" />
+		<Namespace.Component className="SELECTOR-12">
+			{items.map((item) => <li key={item} className="SELECTOR-13">{item}</li>)}
+		</Namespace.Component>
+	</>
+);
+
+el.classList.add('SELECTOR-2', 'SELECTOR-6', 'SELECTOR-12', 'SELECTOR-13');
@@ -0,0 +1,21 @@
+// document.querySelector('.SELECTOR-1');
+/* document.getElementById('SELECTOR-2'); */
+/**
+ * el.classList.add('SELECTOR-3');
+ */
+
+fetch('https://example.com'); el.classList.add('a');
+const glob = 'src/*.js'; el.classList.add('b'); const end = '*/';
+
+const pattern = /\.querySelector\('.SELECTOR-6'\)/g;
+const ratio = width / height; el.classList.add('c') / 2;
+const escaped = "document.querySelector(\".SELECTOR-8\")"; el.id = 'b';
+
+const nested = `${`${foo}`}`; el.classList.toggle('d');
+const markup = `<div class="${foo ? `a` : `b`}">`; el.className = 'g';
+
+const multiline = 'foo \
+bar'; document.getElementById('a');
+
+document.querySelector(/* '.SELECTOR-13' */ '.e');
+document.querySelector('.f' /* , '.SELECTOR-16' */);
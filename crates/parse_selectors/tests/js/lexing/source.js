@@ -0,0 +1,21 @@
+// document.querySelector('.SELECTOR-1');
+/* document.getElementById('SELECTOR-2'); */
+/**
+ * el.classList.add('SELECTOR-3');
+ */
+
+fetch('https://example.com'); el.classList.add('SELECTOR-4');
+const glob = 'src/*.js'; el.classList.add('SELECTOR-5'); const end = '*/';
+
+const pattern = /\.querySelector\('.SELECTOR-6'\)/g;
+const ratio = width / height; el.classList.add('SELECTOR-7') / 2;
+const escaped = "document.querySelector(\".SELECTOR-8\")"; el.id = 'SELECTOR-9';
+
+const nested = `${`${foo}`}`; el.classList.toggle('SELECTOR-10');
+const markup = `<div class="${foo ? `a` : `b`}">`; el.className = 'SELECTOR-11';
+
+const multiline = 'foo \
+bar'; document.getElementById('SELECTOR-12');
+
+document.querySelector(/* '.SELECTOR-13' */ '.SELECTOR-14');
+document.querySelector('.SELECTOR-15' /* , '.SELECTOR-16' */);
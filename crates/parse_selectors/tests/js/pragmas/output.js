@@ -0,0 +1,22 @@
+const card = document.querySelector('.a');
+card.classList.add('b');
+
+/* minify-selectors-disable */
+const widget = document.querySelector('.third-party-widget');
+widget.classList.add('is-open');
+document.getElementById('header');
+/* minify-selectors-enable */
+
+document.getElementById('a').className = 'a b';
+
+// minify-selectors-disable-next-line
+thirdParty.init({ selector: '.card' }); document.querySelector('.card');
+document.querySelector('.a .c');
+
+const skipped = document.querySelector('.a'); /* minify-selectors-disable-next-line */
+card.classList.toggle('is-open');
+card.classList.toggle('d');
+
+// Pragmas only apply in comments, not in strings
+const text = 'minify-selectors-disable';
+document.querySelector('.d');
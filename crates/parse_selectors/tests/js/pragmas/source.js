@@ -0,0 +1,22 @@
+const card = document.querySelector('.card');
+card.classList.add('is-open');
+
+/* minify-selectors-disable */
+const widget = document.querySelector('.third-party-widget');
+widget.classList.add('is-open');
+document.getElementById('header');
+/* minify-selectors-enable */
+
+document.getElementById('header').className = 'card is-open';
+
+// minify-selectors-disable-next-line
+thirdParty.init({ selector: '.card' }); document.querySelector('.card');
+document.querySelector('.card .third-party-widget');
+
+const skipped = document.querySelector('.card'); /* minify-selectors-disable-next-line */
+card.classList.toggle('is-open');
+card.classList.toggle('is-closed');
+
+// Pragmas only apply in comments, not in strings
+const text = 'minify-selectors-disable';
+document.querySelector('.is-closed');
@@ -37,7 +37,7 @@ var m = document.querySelector(
 		? getStuff()
 		: '.CLASS-13'
 );
-var n = document.querySelector(foo.classList.contains('CLASS-14') && ".CLASS-15");
+var n = document.querySelector(foo.classList.contains('j') && ".CLASS-15");
 var o = document.querySelector(!isSomething ?? ".CLASS-16");
 var p = document.querySelector(doSomething() || ".CLASS-17");
 
@@ -39,3 +39,11 @@ p.setAttribute("id", "a");
 q.setAttribute("style", "");
 q.setAttribute("style", "position:absolute; top:0; left:0;");
 q.setAttribute("style", "grid-template: \"a a a\"\"b c c\"");
+
+r.setAttribute('popoverTarget', 'a');
+r.setAttribute('commandfor', 'a');
+r.setAttribute('ARIA-ACTIVEDESCENDANT', 'a');
+
+s.setAttributeNS('http://www.w3.org/1999/xlink', 'xlink:href', '#a');
+s.setAttributeNS(null, 'href', '#a');
+s.setAttributeNS(null, 'fill', 'url(#a)');
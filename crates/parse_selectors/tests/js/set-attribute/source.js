@@ -39,3 +39,11 @@ p.setAttribute("id", "SELECTOR-\u{00031}");
 q.setAttribute("style", "");
 q.setAttribute("style", "position:absolute; top:0; left:0;");
 q.setAttribute("style", "grid-template: \"a a a\"\"b c c\"");
+
+r.setAttribute('popoverTarget', 'SELECTOR-1');
+r.setAttribute('commandfor', 'SELECTOR-1');
+r.setAttribute('ARIA-ACTIVEDESCENDANT', 'SELECTOR-1');
+
+s.setAttributeNS('http://www.w3.org/1999/xlink', 'xlink:href', '#SELECTOR-1');
+s.setAttributeNS(null, 'href', '#SELECTOR-1');
+s.setAttributeNS(null, 'fill', 'url(#SELECTOR-1)');
@@ -0,0 +1,16 @@
+const Menu = () => (
+	<nav id="menu" className="menu">
+		{/* Refer to known IDs and classes */}
+		<button data-menu-ref="menu" data-active-class="open">Menu</button>
+		<button data-menu-ref={'cart'} data-open-selector="#cart > .cart__item">Cart</button>
+
+		{/* Do not refer to known IDs or classes */}
+		<x-tooltip data-label="Open menu" data-version={'1.5'} />
+		<x-icon data-glyph={'\uD800'} data-code={'\u{DFFF}'} />
+
+		{/* Already processed */}
+		<button aria-controls="menu">Cart</button>
+	</nav>
+);
+
+document.querySelector('#cart .cart__item.open');
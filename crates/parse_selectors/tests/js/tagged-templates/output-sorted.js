@@ -0,0 +1,41 @@
+import { LitElement, html, css } from 'lit';
+import styled, { createGlobalStyle } from 'styled-components';
+
+class Card extends LitElement {
+	static styles = css`
+		:host { display: block; }
+		.b > .a { color: ${unsafeCSS(color)}; }
+		#a:hover { opacity: 0.5; }
+	`;
+
+	render() {
+		return html`
+			<div class="b ${this.open ? 'SELECTOR-4' : ''}" id="a">
+				<a href="#a" class="a" @click=${this.toggle}>Toggle</a>
+				${this.items.map((item) => html`<span class="c SELECTOR-6-${item.size}">${item.label}</span>`)}
+			</div>
+		`;
+	}
+}
+
+const Button = styled.button`
+	padding: 0;
+	color: #fff;
+	background: url(icon.png) no-repeat;
+	&.e { color: red; }
+	.c & { color: blue; }
+`;
+
+const Link = styled(Button).attrs({ type: 'button' })`
+	${Button}:hover .a { color: green; }
+`;
+
+const GlobalStyle = createGlobalStyle`
+	body.d { margin: 0; }
+`;
+
+const view = htm`<p class="d"></p>`;
+
+// Not tagged templates
+const plain = `<div class="SELECTOR-8"></div>`;
+const other = sql`SELECT * FROM cards WHERE class = '.SELECTOR-1'`;
@@ -0,0 +1,41 @@
+import { LitElement, html, css } from 'lit';
+import styled, { createGlobalStyle } from 'styled-components';
+
+class Card extends LitElement {
+	static styles = css`
+		:host { display: block; }
+		.a > .b { color: ${unsafeCSS(color)}; }
+		#a:hover { opacity: 0.5; }
+	`;
+
+	render() {
+		return html`
+			<div class="a ${this.open ? 'SELECTOR-4' : ''}" id="a">
+				<a href="#a" class="b" @click=${this.toggle}>Toggle</a>
+				${this.items.map((item) => html`<span class="c SELECTOR-6-${item.size}">${item.label}</span>`)}
+			</div>
+		`;
+	}
+}
+
+const Button = styled.button`
+	padding: 0;
+	color: #fff;
+	background: url(icon.png) no-repeat;
+	&.d { color: red; }
+	.c & { color: blue; }
+`;
+
+const Link = styled(Button).attrs({ type: 'button' })`
+	${Button}:hover .b { color: green; }
+`;
+
+const GlobalStyle = createGlobalStyle`
+	body.e { margin: 0; }
+`;
+
+const view = htm`<p class="e"></p>`;
+
+// Not tagged templates
+const plain = `<div class="SELECTOR-8"></div>`;
+const other = sql`SELECT * FROM cards WHERE class = '.SELECTOR-1'`;
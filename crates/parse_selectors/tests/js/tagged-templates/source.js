@@ -0,0 +1,41 @@
+import { LitElement, html, css } from 'lit';
+import styled, { createGlobalStyle } from 'styled-components';
+
+class Card extends LitElement {
+	static styles = css`
+		:host { display: block; }
+		.SELECTOR-1 > .SELECTOR-2 { color: ${unsafeCSS(color)}; }
+		#SELECTOR-3:hover { opacity: 0.5; }
+	`;
+
+	render() {
+		return html`
+			<div class="SELECTOR-1 ${this.open ? 'SELECTOR-4' : ''}" id="SELECTOR-3">
+				<a href="#SELECTOR-3" class="SELECTOR-2" @click=${this.toggle}>Toggle</a>
+				${this.items.map((item) => html`<span class="SELECTOR-5 SELECTOR-6-${item.size}">${item.label}</span>`)}
+			</div>
+		`;
+	}
+}
+
+const Button = styled.button`
+	padding: 0;
+	color: #fff;
+	background: url(icon.png) no-repeat;
+	&.SELECTOR-4 { color: red; }
+	.SELECTOR-5 & { color: blue; }
+`;
+
+const Link = styled(Button).attrs({ type: 'button' })`
+	${Button}:hover .SELECTOR-2 { color: green; }
+`;
+
+const GlobalStyle = createGlobalStyle`
+	body.SELECTOR-7 { margin: 0; }
+`;
+
+const view = htm`<p class="SELECTOR-7"></p>`;
+
+// Not tagged templates
+const plain = `<div class="SELECTOR-8"></div>`;
+const other = sql`SELECT * FROM cards WHERE class = '.SELECTOR-1'`;
@@ -0,0 +1,24 @@
+interface Props {
+	el: HTMLElement;
+	items: Array<Map<string, number>>;
+}
+
+const cast = <HTMLElement>document.querySelector('.a');
+const asserted = document.querySelector('#a') as HTMLDivElement;
+const generic = document.querySelector<HTMLButtonElement>('.b');
+const nested = document.querySelectorAll<HTMLElement | SVGElement>('.c');
+
+function toggle<T extends HTMLElement>(el: T, active: boolean): T {
+	el.classList.toggle('d', active);
+	return el;
+}
+
+const identity = <T,>(value: T): T => value;
+const compare = count < limit && limit > 0; el.classList.add('e');
+const shifted = value >> 2; document.getElementById('b')!.className = 'g';
+
+class Component<T = {}> {
+	private el?: HTMLElement = document.getElementById('c') ?? undefined;
+}
+
+const visible = <number>el.offsetWidth > 0 && el.classList.contains('f');
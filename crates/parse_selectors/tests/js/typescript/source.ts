@@ -0,0 +1,24 @@
+interface Props {
+	el: HTMLElement;
+	items: Array<Map<string, number>>;
+}
+
+const cast = <HTMLElement>document.querySelector('.SELECTOR-1');
+const asserted = document.querySelector('#SELECTOR-2') as HTMLDivElement;
+const generic = document.querySelector<HTMLButtonElement>('.SELECTOR-3');
+const nested = document.querySelectorAll<HTMLElement | SVGElement>('.SELECTOR-4');
+
+function toggle<T extends HTMLElement>(el: T, active: boolean): T {
+	el.classList.toggle('SELECTOR-5', active);
+	return el;
+}
+
+const identity = <T,>(value: T): T => value;
+const compare = count < limit && limit > 0; el.classList.add('SELECTOR-6');
+const shifted = value >> 2; document.getElementById('SELECTOR-7')!.className = 'SELECTOR-8';
+
+class Component<T = {}> {
+	private el?: HTMLElement = document.getElementById('SELECTOR-9') ?? undefined;
+}
+
+const visible = <number>el.offsetWidth > 0 && el.classList.contains('SELECTOR-10');
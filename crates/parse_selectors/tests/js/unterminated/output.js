@@ -0,0 +1,2 @@
+document.querySelector('.a');
+const markup = html`<p class="a">${label}é
\ No newline at end of file
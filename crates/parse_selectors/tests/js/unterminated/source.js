@@ -0,0 +1,2 @@
+document.querySelector('.SELECTOR-1');
+const markup = html`<p class="SELECTOR-1">${label}é
\ No newline at end of file
@@ -0,0 +1,15 @@
+document.evaluate("//div[contains(concat(' ', normalize-space(@class), ' '), ' a ')]//*[@id='a']", document, null, XPathResult.ANY_TYPE, null);
+document.evaluate('//ul[@class="b c"]/li[@id != "b"]', document);
+document.evaluate(`//a[contains(concat(' ', normalize-space(@class), ' '), ' c ')]`, document);
+document.evaluate('id("a b")//span["b" = @class]', document);
+new XPathEvaluator().evaluate('//section[@class=\'a\']', document);
+const expression = document.createExpression('//p[@data-id="SELECTOR-2"][contains(text(), "SELECTOR-1")]');
+document.evaluate(`//div[@id='${id}']//*[contains(concat(' ', normalize-space(@class), ' '), ' a ')][@id='SELECTOR-2-${index}']`, document);
+evaluator.evaluate('//li[@id="a"][text() = "C:\\temp\n"]', document, null, XPathResult.ANY_TYPE, null);
+
+// Left as is
+document.evaluate('//div[starts-with(@id, "SELECTOR-2")]', document);
+document.evaluate('//div[contains(@class, "SELECTOR-1 SELECTOR-3")]', document);
+document.evaluate(xpath, document);
+page.evaluate('//div[@id="SELECTOR-2"]');
+parser.evaluate('//div[@id="SELECTOR-2"]', scope);
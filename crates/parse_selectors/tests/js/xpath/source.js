@@ -0,0 +1,15 @@
+document.evaluate("//div[contains(@class, 'SELECTOR-1')]//*[@id='SELECTOR-2']", document, null, XPathResult.ANY_TYPE, null);
+document.evaluate('//ul[@class="SELECTOR-3 SELECTOR-4"]/li[@id != "SELECTOR-5"]', document);
+document.evaluate(`//a[contains(concat(' ', normalize-space(@class), ' '), ' SELECTOR-4 ')]`, document);
+document.evaluate('id("SELECTOR-2 SELECTOR-5")//span["SELECTOR-3" = @class]', document);
+new XPathEvaluator().evaluate('//section[@class=\'SELECTOR-1\']', document);
+const expression = document.createExpression('//p[@data-id="SELECTOR-2"][contains(text(), "SELECTOR-1")]');
+document.evaluate(`//div[@id='${id}']//*[contains(@class, 'SELECTOR-1')][@id='SELECTOR-2-${index}']`, document);
+evaluator.evaluate('//li[@id="SELECTOR-2"][text() = "C:\\temp\n"]', document, null, XPathResult.ANY_TYPE, null);
+
+// Left as is
+document.evaluate('//div[starts-with(@id, "SELECTOR-2")]', document);
+document.evaluate('//div[contains(@class, "SELECTOR-1 SELECTOR-3")]', document);
+document.evaluate(xpath, document);
+page.evaluate('//div[@id="SELECTOR-2"]');
+parser.evaluate('//div[@id="SELECTOR-2"]', scope);
@@ -0,0 +1,48 @@
+use std::fs;
+use std::path::PathBuf;
+
+use minify_selectors_utils::*;
+
+
+
+
+// Markers are set once for the whole process (see `regexes::init`), so
+// files with custom markers are tested in their own test binary.
+#[test]
+fn custom_markers() {
+	let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/html/");
+
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("custom-markers/output.html")).unwrap(),
+		process_file(&dir.clone().join("custom-markers/source.html"), false),
+	);
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("custom-markers/output-sorted.html")).unwrap(),
+		process_file(&dir.clone().join("custom-markers/source.html"), true),
+	);
+}
+
+fn process_file(
+	file_path: &PathBuf,
+	sort: bool,
+) -> String {
+	let mut file = fs::read_to_string(file_path).unwrap();
+	let mut selectors = Selectors::new();
+	let mut config = Config {
+		markers: (String::from("{{"), String::from("}}")),
+		..Default::default()
+	};
+
+	parse_selectors::read_from_html(&mut file, &mut selectors, &config);
+
+	config.current_step = ProcessingSteps::EncodingSelectors;
+	if sort {
+		selectors.sort_by_frequency();
+	}
+	selectors.process(&mut config);
+	config.current_step = ProcessingSteps::WritingToFiles;
+
+	parse_selectors::write_to_html(&mut file, &selectors, &config);
+
+	file
+}
@@ -0,0 +1,10 @@
+<?php
+$class = "<div class='SELECTOR-1'>";
+?>
+<style>.b, .a { color: red; }</style>
+<div class="b <?= $class ?>" id="a">
+	<a href="#a" class="<?php echo $active ? 'SELECTOR-2' : ''; ?> a">Link</a>
+</div>
+<?php if ($footer): ?>
+<footer class="a"></footer>
+<?php endif;
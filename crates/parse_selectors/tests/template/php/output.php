@@ -0,0 +1,10 @@
+<?php
+$class = "<div class='SELECTOR-1'>";
+?>
+<style>.a, .b { color: red; }</style>
+<div class="a <?= $class ?>" id="a">
+	<a href="#a" class="<?php echo $active ? 'SELECTOR-2' : ''; ?> b">Link</a>
+</div>
+<?php if ($footer): ?>
+<footer class="b"></footer>
+<?php endif;
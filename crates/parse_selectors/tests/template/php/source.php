@@ -0,0 +1,10 @@
+<?php
+$class = "<div class='SELECTOR-1'>";
+?>
+<style>.SELECTOR-1, .SELECTOR-2 { color: red; }</style>
+<div class="SELECTOR-1 <?= $class ?>" id="SELECTOR-3">
+	<a href="#SELECTOR-3" class="<?php echo $active ? 'SELECTOR-2' : ''; ?> SELECTOR-2">Link</a>
+</div>
+<?php if ($footer): ?>
+<footer class="SELECTOR-2"></footer>
+<?php endif;
@@ -1,8 +1,8 @@
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::PathBuf;
 
 use minify_selectors_utils::*;
-use parse_selectors;
 
 
 
@@ -90,6 +90,16 @@ fn css_files() {
 		fs::read_to_string(dir.clone().join("pseudo-elements/output-sorted.css")).unwrap(),
 		process_file_with_sort("css", &dir.clone().join("pseudo-elements/source.css")),
 	);
+
+	// pragmas
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("pragmas/output.css")).unwrap(),
+		process_file("css", &dir.clone().join("pragmas/source.css")),
+	);
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("pragmas/output-sorted.css")).unwrap(),
+		process_file_with_sort("css", &dir.clone().join("pragmas/source.css")),
+	);
 }
 
 #[test]
@@ -375,6 +385,173 @@ fn js_files() {
 		fs::read_to_string(dir.clone().join("window-open/output-sorted.js")).unwrap(),
 		process_file_with_sort("js", &dir.clone().join("window-open/source.js"))
 	);
+
+	// Comments, strings, regular expressions and template literals
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("lexing/output.js")).unwrap(),
+		process_file("js", &dir.clone().join("lexing/source.js"))
+	);
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("lexing/output-sorted.js")).unwrap(),
+		process_file_with_sort("js", &dir.clone().join("lexing/source.js"))
+	);
+
+	// Unterminated template literal at the end of the file
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("unterminated/output.js")).unwrap(),
+		process_file("js", &dir.clone().join("unterminated/source.js"))
+	);
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("unterminated/output-sorted.js")).unwrap(),
+		process_file_with_sort("js", &dir.clone().join("unterminated/source.js"))
+	);
+
+	// Custom functions
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("custom-functions/output.js")).unwrap(),
+		process_file("js", &dir.clone().join("custom-functions/source.js"))
+	);
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("custom-functions/output-sorted.js")).unwrap(),
+		process_file_with_sort("js", &dir.clone().join("custom-functions/source.js"))
+	);
+
+	// HTML sinks
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("html-sinks/output.js")).unwrap(),
+		process_file("js", &dir.clone().join("html-sinks/source.js"))
+	);
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("html-sinks/output-sorted.js")).unwrap(),
+		process_file_with_sort("js", &dir.clone().join("html-sinks/source.js"))
+	);
+
+	// CSS sinks
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("css-sinks/output.js")).unwrap(),
+		process_file("js", &dir.clone().join("css-sinks/source.js"))
+	);
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("css-sinks/output-sorted.js")).unwrap(),
+		process_file_with_sort("js", &dir.clone().join("css-sinks/source.js"))
+	);
+
+	// Tagged templates
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("tagged-templates/output.js")).unwrap(),
+		process_file("js", &dir.clone().join("tagged-templates/source.js"))
+	);
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("tagged-templates/output-sorted.js")).unwrap(),
+		process_file_with_sort("js", &dir.clone().join("tagged-templates/source.js"))
+	);
+
+	// ID references
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("id-references/output.js")).unwrap(),
+		process_file("js", &dir.clone().join("id-references/source.js"))
+	);
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("id-references/output-sorted.js")).unwrap(),
+		process_file_with_sort("js", &dir.clone().join("id-references/source.js"))
+	);
+
+	// XPath
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("xpath/output.js")).unwrap(),
+		process_file("js", &dir.clone().join("xpath/source.js"))
+	);
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("xpath/output-sorted.js")).unwrap(),
+		process_file_with_sort("js", &dir.clone().join("xpath/source.js"))
+	);
+
+	// XPath predicates that are left as is
+	let mut file = fs::read_to_string(dir.clone().join("xpath/source.js")).unwrap();
+	let mut selectors = Selectors::new();
+	parse_selectors::read_from_js(&mut file, &mut selectors, &get_config());
+	assert_eq!(
+		selectors.warnings,
+		vec![
+			"XPath predicate left as is: starts-with(@id, \"SELECTOR-2\")",
+			"XPath predicate left as is: contains(@class, \"SELECTOR-1 SELECTOR-3\")",
+		],
+	);
+
+	// jQuery
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("jquery/output.js")).unwrap(),
+		process_file("js", &dir.clone().join("jquery/source.js"))
+	);
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("jquery/output-sorted.js")).unwrap(),
+		process_file_with_sort("js", &dir.clone().join("jquery/source.js"))
+	);
+
+	// Annotated string literals
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("annotated-strings/output.js")).unwrap(),
+		process_file("js", &dir.clone().join("annotated-strings/source.js"))
+	);
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("annotated-strings/output-sorted.js")).unwrap(),
+		process_file_with_sort("js", &dir.clone().join("annotated-strings/source.js"))
+	);
+
+	// TypeScript
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("typescript/output.ts")).unwrap(),
+		process_file("ts", &dir.clone().join("typescript/source.ts"))
+	);
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("typescript/output-sorted.ts")).unwrap(),
+		process_file_with_sort("ts", &dir.clone().join("typescript/source.ts"))
+	);
+
+	// JSX elements
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("jsx/output.tsx")).unwrap(),
+		process_file("tsx", &dir.clone().join("jsx/source.tsx"))
+	);
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("jsx/output-sorted.tsx")).unwrap(),
+		process_file_with_sort("tsx", &dir.clone().join("jsx/source.tsx"))
+	);
+
+	// Pragma comments
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("pragmas/output.js")).unwrap(),
+		process_file("js", &dir.clone().join("pragmas/source.js"))
+	);
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("pragmas/output-sorted.js")).unwrap(),
+		process_file_with_sort("js", &dir.clone().join("pragmas/source.js"))
+	);
+
+	// Suggested custom attributes (JSX)
+	let mut file = fs::read_to_string(dir.clone().join("suggest-attributes/source.jsx")).unwrap();
+	let mut selectors = Selectors::new();
+	let mut values = BTreeMap::new();
+	parse_selectors::read_from_js(&mut file, &mut selectors, &get_config());
+	parse_selectors::read_jsx_attribute_values(&file, "source.jsx", &mut values, &get_config());
+	assert_eq!(
+		parse_selectors::suggest::suggest_custom_attributes(&values, &selectors)
+			.iter()
+			.map(|suggestion| {
+				(
+					suggestion.name.as_str(),
+					suggestion.kind.as_str(),
+					suggestion.matches,
+					suggestion.occurrences,
+				)
+			})
+			.collect::<Vec<_>>(),
+		vec![
+			("data-menu-ref", "id", 2, 2),
+			("data-active-class", "class", 1, 1),
+			("data-open-selector", "selector", 1, 1),
+		],
+	);
 }
 
 #[test]
@@ -401,6 +578,122 @@ fn html_files() {
 		process_file_with_sort("html", &dir.clone().join("attributes/source.html")),
 	);
 
+	// Attributes on <head>, <script> and <style> elements and in <head>
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("head-and-raw-text-elements/output.html")).unwrap(),
+		process_file(
+			"html",
+			&dir.clone().join("head-and-raw-text-elements/source.html")
+		),
+	);
+	assert_eq!(
+		fs::read_to_string(
+			dir.clone()
+				.join("head-and-raw-text-elements/output-sorted.html")
+		)
+		.unwrap(),
+		process_file_with_sort(
+			"html",
+			&dir.clone().join("head-and-raw-text-elements/source.html")
+		),
+	);
+
+	// Script element types
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("script-types/output.html")).unwrap(),
+		process_file("html", &dir.clone().join("script-types/source.html")),
+	);
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("script-types/output-sorted.html")).unwrap(),
+		process_file_with_sort("html", &dir.clone().join("script-types/source.html")),
+	);
+
+	// ID reference attributes
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("id-references/output.html")).unwrap(),
+		process_file("html", &dir.clone().join("id-references/source.html")),
+	);
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("id-references/output-sorted.html")).unwrap(),
+		process_file_with_sort("html", &dir.clone().join("id-references/source.html")),
+	);
+
+	// Custom attributes limited to elements
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("scoped-attributes/output.html")).unwrap(),
+		process_file("html", &dir.clone().join("scoped-attributes/source.html")),
+	);
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("scoped-attributes/output-sorted.html")).unwrap(),
+		process_file_with_sort("html", &dir.clone().join("scoped-attributes/source.html")),
+	);
+
+	// Custom attributes with JSON values
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("json-attributes/output.html")).unwrap(),
+		process_file("html", &dir.clone().join("json-attributes/source.html")),
+	);
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("json-attributes/output-sorted.html")).unwrap(),
+		process_file_with_sort("html", &dir.clone().join("json-attributes/source.html")),
+	);
+
+	// Framework presets
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("presets/output.html")).unwrap(),
+		process_file("html", &dir.clone().join("presets/source.html")),
+	);
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("presets/output-sorted.html")).unwrap(),
+		process_file_with_sort("html", &dir.clone().join("presets/source.html")),
+	);
+
+	// Suggested custom attributes
+	let mut file = fs::read_to_string(dir.clone().join("suggest-attributes/source.html")).unwrap();
+	let mut selectors = Selectors::new();
+	let mut values = BTreeMap::new();
+	parse_selectors::read_from_html(&mut file, &mut selectors, &get_config());
+	parse_selectors::read_attribute_values(&file, "source.html", &mut values, &get_config());
+	assert_eq!(
+		parse_selectors::suggest::suggest_custom_attributes(&values, &selectors)
+			.iter()
+			.map(|suggestion| {
+				(
+					suggestion.name.as_str(),
+					suggestion.kind.as_str(),
+					suggestion.matches,
+					suggestion.occurrences,
+				)
+			})
+			.collect::<Vec<_>>(),
+		vec![
+			("data-active-class", "class", 2, 2),
+			("data-open-selector", "selector", 1, 1),
+			("data-return-url", "anchor", 1, 1),
+			("data-menu-ref", "id", 2, 3),
+		],
+	);
+
+	// Shadow parts
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("shadow-parts/output.html")).unwrap(),
+		process_file("html", &dir.clone().join("shadow-parts/source.html")),
+	);
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("shadow-parts/output-sorted.html")).unwrap(),
+		process_file_with_sort("html", &dir.clone().join("shadow-parts/source.html")),
+	);
+
+	// Markup in attributes
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("srcdoc/output.html")).unwrap(),
+		process_file("html", &dir.clone().join("srcdoc/source.html")),
+	);
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("srcdoc/output-sorted.html")).unwrap(),
+		process_file_with_sort("html", &dir.clone().join("srcdoc/source.html")),
+	);
+
 	// Body only
 	assert_eq!(
 		fs::read_to_string(dir.clone().join("body-only/output.html")).unwrap(),
@@ -431,6 +724,16 @@ fn html_files() {
 		process_file_with_sort("html", &dir.clone().join("edge-cases/source.html")),
 	);
 
+	// Pragma comments
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("pragmas/output.html")).unwrap(),
+		process_file("html", &dir.clone().join("pragmas/source.html")),
+	);
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("pragmas/output-sorted.html")).unwrap(),
+		process_file_with_sort("html", &dir.clone().join("pragmas/source.html")),
+	);
+
 	// Placeholders
 	assert_eq!(
 		fs::read_to_string(dir.clone().join("prefixed-selectors/output.html")).unwrap(),
@@ -440,6 +743,15 @@ fn html_files() {
 		fs::read_to_string(dir.clone().join("prefixed-selectors/output-sorted.html")).unwrap(),
 		process_file_with_sort("html", &dir.clone().join("prefixed-selectors/source.html")),
 	);
+
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("tokenizer/output.html")).unwrap(),
+		process_file("html", &dir.clone().join("tokenizer/source.html")),
+	);
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("tokenizer/output-sorted.html")).unwrap(),
+		process_file_with_sort("html", &dir.clone().join("tokenizer/source.html")),
+	);
 }
 
 #[test]
@@ -465,28 +777,300 @@ fn svg_files() {
 		fs::read_to_string(dir.clone().join("attributes/output-sorted.svg")).unwrap(),
 		process_file_with_sort("svg", &dir.clone().join("attributes/source.svg")),
 	);
+
+	// References to IDs
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("references/output.svg")).unwrap(),
+		process_file("svg", &dir.clone().join("references/source.svg")),
+	);
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("references/output-sorted.svg")).unwrap(),
+		process_file_with_sort("svg", &dir.clone().join("references/source.svg")),
+	);
+
+	// Animation timing
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("animation/output.svg")).unwrap(),
+		process_file("svg", &dir.clone().join("animation/source.svg")),
+	);
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("animation/output-sorted.svg")).unwrap(),
+		process_file_with_sort("svg", &dir.clone().join("animation/source.svg")),
+	);
+
+	// Animation timing with encoded IDs that need to be escaped
+	let mut file = fs::read_to_string(dir.clone().join("animation/source.svg")).unwrap();
+	let mut selectors = Selectors::new();
+	let mut config = Config {
+		alphabet: encode_selector::into_alphabet_set("a-"),
+		..get_config()
+	};
+	parse_selectors::read_from_html(&mut file, &mut selectors, &config);
+	config.current_step = ProcessingSteps::EncodingSelectors;
+	selectors.process(&mut config);
+	config.current_step = ProcessingSteps::WritingToFiles;
+	parse_selectors::write_to_html(&mut file, &selectors, &config);
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("animation/output-escaped.svg")).unwrap(),
+		file,
+	);
+}
+
+#[test]
+fn component_files() {
+	let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/component/");
+
+	// Vue
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("vue/output.vue")).unwrap(),
+		process_file("vue", &dir.clone().join("vue/source.vue")),
+	);
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("vue/output-sorted.vue")).unwrap(),
+		process_file_with_sort("vue", &dir.clone().join("vue/source.vue")),
+	);
+
+	// Svelte
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("svelte/output.svelte")).unwrap(),
+		process_file("svelte", &dir.clone().join("svelte/source.svelte")),
+	);
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("svelte/output-sorted.svelte")).unwrap(),
+		process_file_with_sort("svelte", &dir.clone().join("svelte/source.svelte")),
+	);
+
+	// Astro
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("astro/output.astro")).unwrap(),
+		process_file("astro", &dir.clone().join("astro/source.astro")),
+	);
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("astro/output-sorted.astro")).unwrap(),
+		process_file_with_sort("astro", &dir.clone().join("astro/source.astro")),
+	);
+}
+
+#[test]
+fn template_files() {
+	let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/template/");
+
+	// Handlebars
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("handlebars/output.hbs")).unwrap(),
+		process_file("hbs", &dir.clone().join("handlebars/source.hbs")),
+	);
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("handlebars/output-sorted.hbs")).unwrap(),
+		process_file_with_sort("hbs", &dir.clone().join("handlebars/source.hbs")),
+	);
+
+	// Liquid
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("liquid/output.liquid")).unwrap(),
+		process_file("liquid", &dir.clone().join("liquid/source.liquid")),
+	);
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("liquid/output-sorted.liquid")).unwrap(),
+		process_file_with_sort("liquid", &dir.clone().join("liquid/source.liquid")),
+	);
+
+	// Jinja
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("jinja/output.njk")).unwrap(),
+		process_file("njk", &dir.clone().join("jinja/source.njk")),
+	);
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("jinja/output-sorted.njk")).unwrap(),
+		process_file_with_sort("njk", &dir.clone().join("jinja/source.njk")),
+	);
+
+	// ERB
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("erb/output.erb")).unwrap(),
+		process_file("erb", &dir.clone().join("erb/source.erb")),
+	);
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("erb/output-sorted.erb")).unwrap(),
+		process_file_with_sort("erb", &dir.clone().join("erb/source.erb")),
+	);
+
+	// PHP
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("php/output.php")).unwrap(),
+		process_file("php", &dir.clone().join("php/source.php")),
+	);
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("php/output-sorted.php")).unwrap(),
+		process_file_with_sort("php", &dir.clone().join("php/source.php")),
+	);
+
+	// Dynamic parts that are left as is
+	let mut file = fs::read_to_string(dir.clone().join("handlebars/source.hbs")).unwrap();
+	assert_eq!(
+		parse_selectors::read_from_template(
+			&mut file,
+			&mut Selectors::new(),
+			&get_config(),
+			"handlebars",
+		),
+		vec![
+			"class=\"{{ variant }}\"",
+			"class=\"SELECTOR-1-{{ size }}\"",
+			"id=\"{{ id }}\"",
+			"class=\"{{this}}\"",
+			"href=\"#{{ id }}\"",
+		],
+	);
+}
+
+#[test]
+fn e2e_files() {
+	let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/e2e/");
+	let page = dir.clone().join("page.html");
+
+	// Cypress
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("cypress/output.js")).unwrap(),
+		process_e2e_file(&page, &dir.clone().join("cypress/source.js"))
+	);
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("cypress/output-sorted.js")).unwrap(),
+		process_e2e_file_with_sort(&page, &dir.clone().join("cypress/source.js"))
+	);
+
+	// Playwright
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("playwright/output.ts")).unwrap(),
+		process_e2e_file(&page, &dir.clone().join("playwright/source.ts"))
+	);
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("playwright/output-sorted.ts")).unwrap(),
+		process_e2e_file_with_sort(&page, &dir.clone().join("playwright/source.ts"))
+	);
+
+	// Puppeteer
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("puppeteer/output.js")).unwrap(),
+		process_e2e_file(&page, &dir.clone().join("puppeteer/source.js"))
+	);
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("puppeteer/output-sorted.js")).unwrap(),
+		process_e2e_file_with_sort(&page, &dir.clone().join("puppeteer/source.js"))
+	);
+
+	// WebdriverIO
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("webdriverio/output.js")).unwrap(),
+		process_e2e_file(&page, &dir.clone().join("webdriverio/source.js"))
+	);
+	assert_eq!(
+		fs::read_to_string(dir.clone().join("webdriverio/output-sorted.js")).unwrap(),
+		process_e2e_file_with_sort(&page, &dir.clone().join("webdriverio/source.js"))
+	);
 }
 
 
 
 
+// Config that all fixtures are processed with. Lookup tables are only set up
+// once from the config that is read with first, so any other tests that read
+// files need to use the same config.
+fn get_config() -> Config {
+	let mut config = Config::default();
+	// Inserting custom attribute to check
+	config
+		.custom_attributes
+		.push(("data-target".to_string(), "id".to_string()));
+	// Inserting custom attribute rules limited to elements, or with wildcards
+	for (rule, kind) in [
+		("a.nav-link[data-target]", "anchor"),
+		("x-tooltip[for]", "selector"),
+		("data-*-target", "id"),
+		("data-menu-target", "selector"),
+		("[data-toggle=collapse][data-parent]", "selector"),
+	] {
+		config
+			.custom_attributes
+			.push((rule.to_string(), kind.to_string()));
+	}
+	// Inserting custom attributes with JSON values
+	for (attribute, path, kind) in [
+		("data-options", "/target", "selector"),
+		("data-options", "/activeClass", "class"),
+		("data-options", "/panels/*/id", "id"),
+		("data-options", "links[*].href", "anchor"),
+		("data-options", "/part", "part"),
+		("div.carousel[data-config]", "/slides/*", "id"),
+	] {
+		config
+			.json_attributes
+			.push((attribute.to_string(), path.to_string(), kind.to_string()));
+	}
+	for attribute in ["data-options", "div.carousel[data-config]"] {
+		config
+			.custom_attributes
+			.push((attribute.to_string(), "json".to_string()));
+	}
+	config
+		.custom_script_types
+		.push(("text/x-custom-template".to_string(), "markup".to_string()));
+	config.jquery = true;
+	config.presets = ["alpine", "angular", "bootstrap", "htmx", "vue"]
+		.iter()
+		.map(|preset| preset.to_string())
+		.collect();
+	config.spec_level = "experimental".to_string();
+	config
+		.template_tags
+		.push(("htm".to_string(), "markup".to_string()));
+	// Inserting custom functions to check
+	for (signature, context) in [
+		("clsx", "class"),
+		("classnames", "class"),
+		("cn(0)", "class"),
+		("byId", "id"),
+		("$query", "selector"),
+	] {
+		config
+			.custom_functions
+			.push((signature.to_string(), context.to_string()));
+	}
+
+	config
+}
+
 fn process_file(
 	file_type: &str,
 	file_path: &PathBuf,
 ) -> String {
 	let mut file = fs::read_to_string(file_path).unwrap();
 	let mut selectors = Selectors::new();
-	let mut config = Config::default();
-	// Inserting custom attribute to check
-	config
-		.custom_attributes
-		.push(("data-target".to_string(), "id".to_string()));
+	let mut config = get_config();
 
 	match file_type {
 		"css" => parse_selectors::read_from_css(&mut file, &mut selectors, &config),
-		"js" => parse_selectors::read_from_js(&mut file, &mut selectors, &config),
+		"js" | "jsx" | "ts" | "tsx" => {
+			parse_selectors::read_from_js(&mut file, &mut selectors, &config)
+		},
 		"html" | "svg" => parse_selectors::read_from_html(&mut file, &mut selectors, &config),
-		_ => panic!("file_type not one of the following: css, js, html or svg."),
+		"hbs" | "liquid" | "njk" | "erb" | "php" => {
+			parse_selectors::read_from_template(
+				&mut file,
+				&mut selectors,
+				&config,
+				get_template_dialect(file_type),
+			);
+		},
+		"vue" | "svelte" | "astro" => {
+			parse_selectors::read_from_component(&mut file, &mut selectors, &config)
+		},
+		_ => {
+			panic!(
+				"file_type not one of the following: css, js, jsx, ts, tsx, html, svg, vue, \
+				 svelte or astro."
+			)
+		},
 	}
 
 	config.current_step = ProcessingSteps::EncodingSelectors;
@@ -494,10 +1078,26 @@ fn process_file(
 	config.current_step = ProcessingSteps::WritingToFiles;
 
 	match file_type {
-		"css" => parse_selectors::write_to_css(&mut file, &mut selectors, &config),
-		"js" => parse_selectors::write_to_js(&mut file, &mut selectors, &config),
-		"html" | "svg" => parse_selectors::write_to_html(&mut file, &mut selectors, &config),
-		_ => panic!("file_type not one of the following: css, js, html or svg."),
+		"css" => parse_selectors::write_to_css(&mut file, &selectors, &config),
+		"js" | "jsx" | "ts" | "tsx" => parse_selectors::write_to_js(&mut file, &selectors, &config),
+		"html" | "svg" => parse_selectors::write_to_html(&mut file, &selectors, &config),
+		"hbs" | "liquid" | "njk" | "erb" | "php" => {
+			parse_selectors::write_to_template(
+				&mut file,
+				&selectors,
+				&config,
+				get_template_dialect(file_type),
+			)
+		},
+		"vue" | "svelte" | "astro" => {
+			parse_selectors::write_to_component(&mut file, &selectors, &config)
+		},
+		_ => {
+			panic!(
+				"file_type not one of the following: css, js, jsx, ts, tsx, html, svg, vue, \
+				 svelte or astro."
+			)
+		},
 	}
 
 	file.to_owned()
@@ -509,17 +1109,31 @@ fn process_file_with_sort(
 ) -> String {
 	let mut file = fs::read_to_string(file_path).unwrap();
 	let mut selectors = Selectors::new();
-	let mut config = Config::default();
-	// Inserting custom attribute to check
-	config
-		.custom_attributes
-		.push(("data-target".to_string(), "id".to_string()));
+	let mut config = get_config();
 
 	match file_type {
 		"css" => parse_selectors::read_from_css(&mut file, &mut selectors, &config),
-		"js" => parse_selectors::read_from_js(&mut file, &mut selectors, &config),
+		"js" | "jsx" | "ts" | "tsx" => {
+			parse_selectors::read_from_js(&mut file, &mut selectors, &config)
+		},
 		"html" | "svg" => parse_selectors::read_from_html(&mut file, &mut selectors, &config),
-		_ => panic!("file_type not one of the following: css, js, html or svg."),
+		"hbs" | "liquid" | "njk" | "erb" | "php" => {
+			parse_selectors::read_from_template(
+				&mut file,
+				&mut selectors,
+				&config,
+				get_template_dialect(file_type),
+			);
+		},
+		"vue" | "svelte" | "astro" => {
+			parse_selectors::read_from_component(&mut file, &mut selectors, &config)
+		},
+		_ => {
+			panic!(
+				"file_type not one of the following: css, js, jsx, ts, tsx, html, svg, vue, \
+				 svelte or astro."
+			)
+		},
 	}
 
 	config.current_step = ProcessingSteps::EncodingSelectors;
@@ -528,11 +1142,78 @@ fn process_file_with_sort(
 	config.current_step = ProcessingSteps::WritingToFiles;
 
 	match file_type {
-		"css" => parse_selectors::write_to_css(&mut file, &mut selectors, &config),
-		"js" => parse_selectors::write_to_js(&mut file, &mut selectors, &config),
-		"html" | "svg" => parse_selectors::write_to_html(&mut file, &mut selectors, &config),
-		_ => panic!("file_type not one of the following: css, js, html or svg."),
+		"css" => parse_selectors::write_to_css(&mut file, &selectors, &config),
+		"js" | "jsx" | "ts" | "tsx" => parse_selectors::write_to_js(&mut file, &selectors, &config),
+		"html" | "svg" => parse_selectors::write_to_html(&mut file, &selectors, &config),
+		"hbs" | "liquid" | "njk" | "erb" | "php" => {
+			parse_selectors::write_to_template(
+				&mut file,
+				&selectors,
+				&config,
+				get_template_dialect(file_type),
+			)
+		},
+		"vue" | "svelte" | "astro" => {
+			parse_selectors::write_to_component(&mut file, &selectors, &config)
+		},
+		_ => {
+			panic!(
+				"file_type not one of the following: css, js, jsx, ts, tsx, html, svg, vue, \
+				 svelte or astro."
+			)
+		},
 	}
 
 	file.to_owned()
 }
+
+// End-to-end test files are only written to, using the selectors
+// found in the given HTML page.
+fn process_e2e_file(
+	page_path: &PathBuf,
+	file_path: &PathBuf,
+) -> String {
+	let mut page = fs::read_to_string(page_path).unwrap();
+	let mut file = fs::read_to_string(file_path).unwrap();
+	let mut selectors = Selectors::new();
+	let mut config = get_config();
+
+	parse_selectors::read_from_html(&mut page, &mut selectors, &config);
+
+	config.current_step = ProcessingSteps::EncodingSelectors;
+	selectors.process(&mut config);
+	config.current_step = ProcessingSteps::WritingToFiles;
+
+	parse_selectors::write_to_e2e(&mut file, &selectors, &config);
+
+	file.to_owned()
+}
+
+fn process_e2e_file_with_sort(
+	page_path: &PathBuf,
+	file_path: &PathBuf,
+) -> String {
+	let mut page = fs::read_to_string(page_path).unwrap();
+	let mut file = fs::read_to_string(file_path).unwrap();
+	let mut selectors = Selectors::new();
+	let mut config = get_config();
+
+	parse_selectors::read_from_html(&mut page, &mut selectors, &config);
+
+	config.current_step = ProcessingSteps::EncodingSelectors;
+	selectors.sort_by_frequency();
+	selectors.process(&mut config);
+	config.current_step = ProcessingSteps::WritingToFiles;
+
+	parse_selectors::write_to_e2e(&mut file, &selectors, &config);
+
+	file.to_owned()
+}
+
+fn get_template_dialect(file_type: &str) -> &str {
+	match file_type {
+		"hbs" => "handlebars",
+		"njk" => "jinja",
+		_ => file_type,
+	}
+}